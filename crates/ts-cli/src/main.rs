@@ -43,6 +43,10 @@ enum Commands {
         /// Generate source maps
         #[arg(long)]
         sourcemap: bool,
+
+        /// Use legacy experimental decorators instead of standard decorators
+        #[arg(long)]
+        experimental_decorators: bool,
     },
     /// Check TypeScript files for errors
     Check {
@@ -68,6 +72,20 @@ enum Commands {
         #[arg(short, long)]
         verbose: bool,
     },
+    /// Parse test cases and report how many parse without error
+    Parse {
+        /// Test name pattern to filter tests
+        #[arg(short, long)]
+        pattern: Option<String>,
+        
+        /// Directory containing test cases
+        #[arg(long, default_value = "tests/cases/compiler")]
+        test_dir: PathBuf,
+        
+        /// List the files that failed to parse
+        #[arg(short, long)]
+        verbose: bool,
+    },
     /// Show version information
     Version,
 }
@@ -77,8 +95,8 @@ async fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Compile { files, outdir, target, module, strict, sourcemap } => {
-            compile_files(files, outdir, target, module, strict, sourcemap).await;
+        Commands::Compile { files, outdir, target, module, strict, sourcemap, experimental_decorators } => {
+            compile_files(files, outdir, target, module, strict, sourcemap, experimental_decorators).await;
         }
         Commands::Check { files } => {
             check_files(files).await;
@@ -86,6 +104,9 @@ async fn main() {
         Commands::Test { pattern, test_dir, baseline_dir, verbose } => {
             run_baseline_tests(pattern, test_dir, baseline_dir, verbose).await;
         }
+        Commands::Parse { pattern, test_dir, verbose } => {
+            run_parse_tests(pattern, test_dir, verbose);
+        }
         Commands::Version => {
             println!("ts-cli version {}", env!("CARGO_PKG_VERSION"));
         }
@@ -99,6 +120,7 @@ async fn compile_files(
     module: String,
     _strict: bool,
     sourcemap: bool,
+    experimental_decorators: bool,
 ) {
     for file in files {
         match std::fs::read_to_string(&file) {
@@ -108,6 +130,7 @@ async fn compile_files(
                     module: module.clone(),
                     source_map: sourcemap,
                     file_name: file.to_string_lossy().to_string(),
+                    experimental_decorators,
                 };
                 
                 match compile(&file, &options) {
@@ -169,4 +192,36 @@ async fn run_baseline_tests(
     } else {
         println!("{}", format!("✅ All {} tests passed!", total_tests).green().bold());
     }
-}
\ No newline at end of file
+}
+
+fn run_parse_tests(pattern: Option<String>, test_dir: PathBuf, verbose: bool) {
+    let runner = BaselineTestRunner::new(test_dir, PathBuf::new());
+    let report = runner.run_parse_tests(pattern.as_deref());
+    
+    if verbose {
+        for (test_name, diagnostic) in &report.failures {
+            let code = diagnostic.code.map(|code| format!(" TS{}", code)).unwrap_or_default();
+            println!(
+                "{}: {}:{}:{} -{} {}",
+                "error".red(),
+                test_name,
+                diagnostic.span.start.line,
+                diagnostic.span.start.column,
+                code,
+                diagnostic.message
+            );
+        }
+    }
+    
+    let summary = format!(
+        "{}/{} parsed without error ({:.1}%)",
+        report.passed,
+        report.total,
+        report.pass_rate()
+    );
+    if report.failures.is_empty() {
+        println!("{}", summary.green().bold());
+    } else {
+        println!("{}", summary.yellow().bold());
+    }
+}
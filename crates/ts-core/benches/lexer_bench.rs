@@ -1,6 +1,13 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 use ts_core::lexer::Lexer;
 
+/// Consume every token of the source
+fn lex_all(source: &str) {
+    for token in Lexer::new(source) {
+        black_box(token);
+    }
+}
+
 fn bench_lexer_simple(c: &mut Criterion) {
     let source = r#"
         function hello(name: string): string {
@@ -13,10 +20,7 @@ fn bench_lexer_simple(c: &mut Criterion) {
     
     c.bench_function("lexer_simple", |b| {
         b.iter(|| {
-            let mut lexer = Lexer::new(black_box(source));
-            while let Some(_token) = lexer.next_token() {
-                // Consume all tokens
-            }
+            lex_all(black_box(source));
         })
     });
 }
@@ -46,13 +50,38 @@ fn bench_lexer_complex(c: &mut Criterion) {
     
     c.bench_function("lexer_complex", |b| {
         b.iter(|| {
-            let mut lexer = Lexer::new(black_box(source));
-            while let Some(_token) = lexer.next_token() {
-                // Consume all tokens
-            }
+            lex_all(black_box(source));
         })
     });
 }
 
-criterion_group!(benches, bench_lexer_simple, bench_lexer_complex);
+fn bench_lexer_throughput(c: &mut Criterion) {
+    // A large-monorepo-sized file: many declarations with long identifiers, strings and templates
+    let unit = r#"
+        export class RepositoryService<TEntity extends { id: string }> {
+            private readonly cache = new Map<string, TEntity>();
+
+            async findById(identifier: string): Promise<TEntity | undefined> {
+                const cachedEntity = this.cache.get(identifier);
+                if (cachedEntity !== undefined) {
+                    return cachedEntity;
+                }
+                const response = await fetch(`/api/entities/${identifier}?expand=true`);
+                const payload = (await response.json()) as TEntity;
+                this.cache.set(identifier, payload);
+                return payload ?? undefined;
+            }
+        }
+    "#;
+    let source = unit.repeat(500);
+
+    let mut group = c.benchmark_group("lexer_throughput");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.bench_function("large_file", |b| {
+        b.iter(|| lex_all(black_box(&source)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_lexer_simple, bench_lexer_complex, bench_lexer_throughput);
 criterion_main!(benches);
\ No newline at end of file
@@ -1,8 +1,21 @@
 //! Abstract Syntax Tree definitions for TypeScript
 
+use crate::lexer::CommentDirective;
 use crate::utils::span::Span;
 use serde::{Serialize, Deserialize};
 
+/// The root of a parsed source file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Program {
+    pub statements: Vec<Statement>,
+    /// Whether the program was parsed with the legacy `experimentalDecorators`
+    /// rules, which decide where decorators are allowed
+    pub experimental_decorators: bool,
+    /// `// @key: value` directive comments, in source order
+    pub directives: Vec<CommentDirective>,
+    pub span: Span,
+}
+
 /// AST node types
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum AstNode {
@@ -14,10 +27,10 @@ pub enum AstNode {
 /// Statement types
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Statement {
-    Empty,
+    Empty(Span),
     Expression(Expression),
-    Variable(VariableDeclaration),
-    Function(FunctionDeclaration),
+    Variable(Box<VariableDeclaration>),
+    Function(Box<FunctionDeclaration>),
     Return(Option<Expression>),
     If {
         condition: Expression,
@@ -30,43 +43,82 @@ pub enum Statement {
     },
     Block(Vec<Statement>),
     BlockStatement(BlockStatement),
-    IfStatement(IfStatement),
-    WhileStatement(WhileStatement),
-    ForStatement(ForStatement),
-    ReturnStatement(ReturnStatement),
+    IfStatement(Box<IfStatement>),
+    WhileStatement(Box<WhileStatement>),
+    WithStatement(Box<WithStatement>),
+    DoWhileStatement(Box<DoWhileStatement>),
+    ForStatement(Box<ForStatement>),
+    ForInStatement(Box<ForInStatement>),
+    ForOfStatement(Box<ForOfStatement>),
+    ReturnStatement(Box<ReturnStatement>),
     BreakStatement(BreakStatement),
     ContinueStatement(ContinueStatement),
+    ThrowStatement(Box<ThrowStatement>),
+    LabeledStatement(LabeledStatement),
+    SwitchStatement(Box<SwitchStatement>),
+    TryStatement(Box<TryStatement>),
+    DebuggerStatement(Span),
     VariableStatement(VariableStatement),
+    Declaration(Box<Declaration>),
 }
 
 /// Expression types
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Expression {
     Identifier(Identifier),
-    Literal(Literal),
+    /// `#name` as the left operand of `in`, named with its `#` like private members
+    PrivateName(Identifier),
+    Literal(LiteralExpression),
+    This(Span),
     Binary(BinaryExpression),
     Unary(UnaryExpression),
-    Call(CallExpression),
+    Call(Box<CallExpression>),
     Member(MemberExpression),
-    Assignment(AssignmentExpression),
-    Function(FunctionExpression),
+    Assignment(Box<AssignmentExpression>),
+    Function(Box<FunctionExpression>),
+    Class(Box<ClassDeclaration>),
     Arrow(Box<ArrowFunctionExpression>),
     Object(ObjectExpression),
     Array(ArrayExpression),
+    Super(Span),
+    Parenthesized(ParenthesizedExpression),
+    Conditional(ConditionalExpression),
+    Sequence(SequenceExpression),
+    Spread(SpreadElement),
+    New(Box<NewExpression>),
+    Template(TemplateLiteral),
+    TaggedTemplate(Box<TaggedTemplateExpression>),
+    OptionalChain(OptionalChainExpression),
+    MetaProperty(Box<MetaProperty>),
+    Await(AwaitExpression),
+    Yield(YieldExpression),
+    As(Box<AsExpression>),
+    Satisfies(Box<SatisfiesExpression>),
+    NonNull(NonNullExpression),
+    TypeAssertion(Box<TypeAssertionExpression>),
+    Instantiation(InstantiationExpression),
+    /// `<div>...</div>` or `<br />`
+    JsxElement(Box<JsxElement>),
+    /// `<>...</>`
+    JsxFragment(JsxFragment),
 }
 
 /// Declaration types
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Declaration {
-    Function(FunctionDeclaration),
-    Variable(VariableDeclaration),
-    Class(ClassDeclaration),
-    Interface(InterfaceDeclaration),
-    Type(TypeDeclaration),
+    Function(Box<FunctionDeclaration>),
+    Variable(VariableStatement),
+    Class(Box<ClassDeclaration>),
+    Interface(Box<InterfaceDeclaration>),
+    Type(Box<TypeDeclaration>),
     Enum(EnumDeclaration),
     Namespace(NamespaceDeclaration),
     Import(ImportDeclaration),
+    ImportEquals(Box<ImportEqualsDeclaration>),
     Export(Box<ExportDeclaration>),
+    ExportDefault(Box<ExportDefaultDeclaration>),
+    ExportAssignment(ExportAssignment),
+    NamespaceExport(NamespaceExportDeclaration),
 }
 
 /// Identifier
@@ -76,14 +128,27 @@ pub struct Identifier {
     pub span: Span,
 }
 
+/// Literal expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiteralExpression {
+    pub value: Literal,
+    pub span: Span,
+}
+
 /// Literal values
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Literal {
     String(String),
     Number(f64),
+    /// BigInt digits, including any radix prefix, without the `n` suffix
+    BigInt(String),
     Boolean(bool),
     Null,
     Undefined,
+    RegExp {
+        pattern: String,
+        flags: String,
+    },
 }
 
 /// Binary expression
@@ -119,6 +184,10 @@ pub enum BinaryOperator {
     LeftShift,
     RightShift,
     UnsignedRightShift,
+    Exponent,
+    NullishCoalescing,
+    In,
+    Instanceof,
 }
 
 /// Unary expression
@@ -149,7 +218,10 @@ pub enum UnaryOperator {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CallExpression {
     pub callee: Box<Expression>,
+    pub type_arguments: Vec<TypeNode>,
     pub arguments: Vec<Expression>,
+    /// Whether the call is written `callee?.()`
+    pub optional: bool,
     pub span: Span,
 }
 
@@ -159,13 +231,15 @@ pub struct MemberExpression {
     pub object: Box<Expression>,
     pub property: Box<Expression>,
     pub computed: bool, // true for obj[prop], false for obj.prop
+    /// Whether the access is written `obj?.prop` or `obj?.[prop]`
+    pub optional: bool,
     pub span: Span,
 }
 
 /// Assignment expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AssignmentExpression {
-    pub left: Box<Expression>,
+    pub left: AssignmentTarget,
     pub operator: AssignmentOperator,
     pub right: Box<Expression>,
     pub span: Span,
@@ -186,6 +260,10 @@ pub enum AssignmentOperator {
     LeftShiftAssign,
     RightShiftAssign,
     UnsignedRightShiftAssign,
+    ExponentAssign,
+    LogicalAndAssign,
+    LogicalOrAssign,
+    NullishAssign,
 }
 
 /// Block statement
@@ -212,6 +290,14 @@ pub struct WhileStatement {
     pub span: Span,
 }
 
+/// `with (object) body`. It is an error in strict mode, which the checker reports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WithStatement {
+    pub object: Expression,
+    pub body: Box<Statement>,
+    pub span: Span,
+}
+
 /// For statement
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ForStatement {
@@ -222,6 +308,84 @@ pub struct ForStatement {
     pub span: Span,
 }
 
+/// Do-while statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoWhileStatement {
+    pub body: Box<Statement>,
+    pub test: Expression,
+    pub span: Span,
+}
+
+/// For-in statement: `for (const key in object)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForInStatement {
+    pub left: ForInOfLeft,
+    pub right: Expression,
+    pub body: Box<Statement>,
+    pub span: Span,
+}
+
+/// For-of statement: `for (const item of items)` or `for await (const item of items)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForOfStatement {
+    pub is_await: bool,
+    pub left: ForInOfLeft,
+    pub right: Expression,
+    pub body: Box<Statement>,
+    pub span: Span,
+}
+
+/// What a for-in or for-of loop assigns each value to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ForInOfLeft {
+    Variable(VariableStatement),
+    Target(AssignmentTarget),
+}
+
+/// Labeled statement: `outer: for (...)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabeledStatement {
+    pub label: Identifier,
+    pub body: Box<Statement>,
+    pub span: Span,
+}
+
+/// Switch statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwitchStatement {
+    pub discriminant: Expression,
+    pub cases: Vec<SwitchCase>,
+    pub span: Span,
+}
+
+/// A `case` or `default` clause of a switch statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwitchCase {
+    /// `None` for the `default` clause
+    pub test: Option<Expression>,
+    pub consequent: Vec<Statement>,
+    pub span: Span,
+}
+
+/// Try statement, with a catch clause, a finally block or both
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TryStatement {
+    pub block: BlockStatement,
+    pub handler: Option<CatchClause>,
+    pub finalizer: Option<BlockStatement>,
+    pub span: Span,
+}
+
+/// Catch clause
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatchClause {
+    /// `None` for `catch { ... }` without a binding
+    pub param: Option<BindingPattern>,
+    pub type_annotation: Option<TypeNode>,
+    pub body: BlockStatement,
+    pub span: Span,
+}
+
 /// Return statement
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ReturnStatement {
@@ -243,18 +407,44 @@ pub struct ContinueStatement {
     pub span: Span,
 }
 
+/// Throw statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThrowStatement {
+    /// An empty identifier if the line ends after `throw`
+    pub argument: Expression,
+    pub span: Span,
+}
+
 /// Variable statement
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VariableStatement {
+    pub kind: VariableKind,
     pub declarations: Vec<VariableDeclaration>,
+    pub is_declare: bool,
     pub span: Span,
 }
 
+/// The keyword introducing a variable statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VariableKind {
+    Var,
+    Let,
+    Const,
+    /// `using`, which disposes of the value at the end of the block
+    Using,
+    /// `await using`
+    AwaitUsing,
+}
+
 /// Function expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FunctionExpression {
     pub name: Option<Identifier>,
+    pub is_async: bool,
+    pub is_generator: bool,
+    pub type_parameters: Vec<TypeParameter>,
     pub parameters: Vec<Parameter>,
+    pub return_type: Option<TypeNode>,
     pub body: BlockStatement,
     pub span: Span,
 }
@@ -262,7 +452,10 @@ pub struct FunctionExpression {
 /// Arrow function expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrowFunctionExpression {
+    pub is_async: bool,
+    pub type_parameters: Vec<TypeParameter>,
     pub parameters: Vec<Parameter>,
+    pub return_type: Option<TypeNode>,
     pub body: Box<ArrowFunctionBody>,
     pub span: Span,
 }
@@ -277,16 +470,43 @@ pub enum ArrowFunctionBody {
 /// Object expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ObjectExpression {
-    pub properties: Vec<ObjectProperty>,
+    pub properties: Vec<ObjectMember>,
+    /// Whether the last member is followed by a comma, as in `{ a, }`
+    pub trailing_comma: bool,
     pub span: Span,
 }
 
+/// Member of an object literal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ObjectMember {
+    Property(Box<ObjectProperty>),
+    /// `...expression`
+    Spread(SpreadElement),
+}
+
+/// How an object literal property defines its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PropertyKind {
+    /// `a: 1` or the shorthand `a`
+    Init,
+    /// `m() {}`
+    Method,
+    /// `get a() {}`
+    Get,
+    /// `set a(v) {}`
+    Set,
+}
+
 /// Object property
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ObjectProperty {
     pub key: Expression,
+    /// The value, or the function of a method or accessor
     pub value: Expression,
+    pub kind: PropertyKind,
     pub computed: bool,
+    /// Whether the property is written as just its name, as in `{ x }`
+    pub shorthand: bool,
     pub span: Span,
 }
 
@@ -294,83 +514,782 @@ pub struct ObjectProperty {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ArrayExpression {
     pub elements: Vec<Option<Expression>>,
+    /// Whether the last element is followed by a comma, as in `[a, ...b,]`
+    pub trailing_comma: bool,
+    pub span: Span,
+}
+
+/// Expression wrapped in parentheses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParenthesizedExpression {
+    pub expression: Box<Expression>,
+    pub span: Span,
+}
+
+/// Conditional expression: `test ? consequent : alternate`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionalExpression {
+    pub test: Box<Expression>,
+    pub consequent: Box<Expression>,
+    pub alternate: Box<Expression>,
+    pub span: Span,
+}
+
+/// Comma-separated sequence of expressions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequenceExpression {
+    pub expressions: Vec<Expression>,
+    pub span: Span,
+}
+
+/// Spread element in an array literal, object literal or argument list: `...argument`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpreadElement {
+    pub argument: Box<Expression>,
+    pub span: Span,
+}
+
+/// `new` expression. `arguments` is `None` when written without parentheses, as in `new Foo`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewExpression {
+    pub callee: Box<Expression>,
+    pub type_arguments: Vec<TypeNode>,
+    pub arguments: Option<Vec<Expression>>,
+    pub span: Span,
+}
+
+/// Template literal. `quasis` holds one more element than `expressions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateLiteral {
+    pub quasis: Vec<TemplateElement>,
+    pub expressions: Vec<Expression>,
+    pub span: Span,
+}
+
+/// Text part of a template literal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateElement {
+    /// Text with escapes processed, or `None` if it contains an invalid escape
+    pub cooked: Option<String>,
+    /// Text as written
+    pub raw: String,
+    pub span: Span,
+}
+
+/// Tagged template: `` tag`text ${value}` ``
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaggedTemplateExpression {
+    pub tag: Box<Expression>,
+    pub type_arguments: Vec<TypeNode>,
+    pub quasi: TemplateLiteral,
+    pub span: Span,
+}
+
+/// A chain of member accesses and calls containing at least one `?.`. The links
+/// written with `?.` are marked `optional`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptionalChainExpression {
+    pub expression: Box<Expression>,
+    pub span: Span,
+}
+
+/// `new.target` or `import.meta`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaProperty {
+    pub meta: Identifier,
+    pub property: Identifier,
+    pub span: Span,
+}
+
+/// `await argument`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AwaitExpression {
+    pub argument: Box<Expression>,
+    pub span: Span,
+}
+
+/// `yield argument` or `yield* argument`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YieldExpression {
+    pub argument: Option<Box<Expression>>,
+    /// Whether this is `yield*`
+    pub delegate: bool,
+    pub span: Span,
+}
+
+/// `expression as Type`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsExpression {
+    pub expression: Box<Expression>,
+    pub type_annotation: TypeNode,
+    pub span: Span,
+}
+
+/// `expression satisfies Type`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SatisfiesExpression {
+    pub expression: Box<Expression>,
+    pub type_annotation: TypeNode,
+    pub span: Span,
+}
+
+/// Non-null assertion: `expression!`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NonNullExpression {
+    pub expression: Box<Expression>,
+    pub span: Span,
+}
+
+/// Instantiation expression: `f<string>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstantiationExpression {
+    pub expression: Box<Expression>,
+    pub type_arguments: Vec<TypeNode>,
+    pub span: Span,
+}
+
+/// Angle-bracket type assertion: `<Type>expression`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeAssertionExpression {
+    pub type_annotation: TypeNode,
+    pub expression: Box<Expression>,
+    pub span: Span,
+}
+
+/// JSX element, such as `<a href="x">text</a>` or `<br />`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxElement {
+    pub opening: JsxOpeningElement,
+    pub children: Vec<JsxChild>,
+    /// `None` for a self-closing element
+    pub closing: Option<JsxClosingElement>,
+    pub span: Span,
+}
+
+/// Opening tag of a JSX element: `<Foo<T> a="1" {...rest}>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxOpeningElement {
+    pub name: JsxTagName,
+    pub type_arguments: Vec<TypeNode>,
+    pub attributes: Vec<JsxAttributeItem>,
+    /// Whether the tag is written `<br />`, with no children or closing tag
+    pub self_closing: bool,
+    pub span: Span,
+}
+
+/// Closing tag of a JSX element: `</a>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxClosingElement {
+    pub name: JsxTagName,
+    pub span: Span,
+}
+
+/// JSX fragment: `<>...</>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxFragment {
+    pub children: Vec<JsxChild>,
+    pub span: Span,
+}
+
+/// The name of a JSX element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JsxTagName {
+    /// `div`, `my-element` or `this`
+    Identifier(Identifier),
+    /// `svg:rect`
+    Namespaced(JsxNamespacedName),
+    /// `Foo.Bar`
+    Member(Box<JsxMemberName>),
+}
+
+/// A JSX name with a namespace prefix, such as `xlink:href`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxNamespacedName {
+    pub namespace: Identifier,
+    pub name: Identifier,
+    pub span: Span,
+}
+
+/// A dotted JSX tag name: `object.property`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxMemberName {
+    pub object: JsxTagName,
+    pub property: Identifier,
+    pub span: Span,
+}
+
+/// An attribute in a JSX opening tag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JsxAttributeItem {
+    Attribute(Box<JsxAttribute>),
+    /// `{...props}`, spanning the braces
+    Spread(SpreadElement),
+}
+
+/// JSX attribute: `name`, `name="value"` or `name={expression}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxAttribute {
+    pub name: JsxAttributeName,
+    /// `None` for an attribute written without a value, as in `<input disabled />`
+    pub value: Option<JsxAttributeValue>,
+    pub span: Span,
+}
+
+/// The name of a JSX attribute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JsxAttributeName {
+    /// `class` or `data-id`
+    Identifier(Identifier),
+    /// `xlink:href`
+    Namespaced(JsxNamespacedName),
+}
+
+/// The value of a JSX attribute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JsxAttributeValue {
+    /// A quoted string, which has HTML entities rather than escape sequences
+    String(LiteralExpression),
+    Expression(JsxExpressionContainer),
+    /// An element or fragment
+    Element(Box<Expression>),
+}
+
+/// `{expression}` in an attribute value or among the children of an element
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxExpressionContainer {
+    /// `None` for `{}`, or for braces holding only comments
+    pub expression: Option<Box<Expression>>,
+    pub span: Span,
+}
+
+/// A child of a JSX element or fragment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum JsxChild {
+    Text(JsxText),
+    Expression(JsxExpressionContainer),
+    /// `{...children}`, spanning the braces
+    Spread(SpreadElement),
+    /// A nested element or fragment
+    Element(Box<Expression>),
+}
+
+/// Text between JSX tags
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsxText {
+    /// Text with HTML entities decoded
+    pub value: String,
+    /// Text as written
+    pub raw: String,
     pub span: Span,
 }
 
 /// Function declaration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FunctionDeclaration {
-    pub name: Identifier,
+    /// `None` for `export default function () {}`
+    pub name: Option<Identifier>,
+    pub is_async: bool,
+    pub is_generator: bool,
+    pub type_parameters: Vec<TypeParameter>,
     pub parameters: Vec<Parameter>,
-    pub body: BlockStatement,
+    pub return_type: Option<TypeNode>,
+    /// `None` for overload signatures and ambient declarations
+    pub body: Option<BlockStatement>,
+    pub is_declare: bool,
     pub span: Span,
 }
 
 /// Variable declaration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VariableDeclaration {
-    pub name: Identifier,
+    pub name: BindingPattern,
+    pub type_annotation: Option<TypeNode>,
     pub init: Option<Expression>,
     pub span: Span,
 }
 
+/// The name bound by a declaration or parameter: an identifier or a
+/// destructuring pattern
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BindingPattern {
+    Identifier(Identifier),
+    /// `{ a, b: c = 1, ...rest }`
+    Object(ObjectBindingPattern),
+    /// `[a, , b = 1, ...rest]`
+    Array(ArrayBindingPattern),
+}
+
+/// Object destructuring pattern in a declaration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectBindingPattern {
+    pub elements: Vec<BindingElement>,
+    pub span: Span,
+}
+
+/// Array destructuring pattern in a declaration. Elisions are `None`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArrayBindingPattern {
+    pub elements: Vec<Option<BindingElement>>,
+    pub span: Span,
+}
+
+/// One element of a binding pattern, such as `b: c = 1` or `...rest`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BindingElement {
+    /// The property read in an object pattern when it differs from the name
+    /// bound, as `b` in `{ b: c }`
+    pub property_name: Option<Expression>,
+    /// Whether `property_name` is written in brackets: `{ [key]: value }`
+    pub computed: bool,
+    pub name: BindingPattern,
+    pub initializer: Option<Expression>,
+    /// Whether this is a `...rest` element
+    pub rest: bool,
+    pub span: Span,
+}
+
+/// The left-hand side of an assignment. Object and array literals on the left
+/// of `=` are reinterpreted as destructuring patterns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AssignmentTarget {
+    /// An identifier, property access or other expression assigned directly
+    Simple(Box<Expression>),
+    /// `({ a, b: x.y = 1, ...rest } = value)`
+    Object(ObjectAssignmentPattern),
+    /// `[a, , b.c = 1, ...rest] = value`
+    Array(ArrayAssignmentPattern),
+}
+
+/// Object destructuring pattern in an assignment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObjectAssignmentPattern {
+    pub elements: Vec<AssignmentElement>,
+    pub span: Span,
+}
+
+/// Array destructuring pattern in an assignment. Elisions are `None`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArrayAssignmentPattern {
+    pub elements: Vec<Option<AssignmentElement>>,
+    pub span: Span,
+}
+
+/// One element of an assignment pattern, such as `b: x.y = 1` or `...rest`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AssignmentElement {
+    /// The property read in an object pattern when it is not a shorthand
+    pub property_name: Option<Expression>,
+    pub computed: bool,
+    pub target: AssignmentTarget,
+    pub initializer: Option<Expression>,
+    pub rest: bool,
+    pub span: Span,
+}
+
 /// Function parameter
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
-    pub name: Identifier,
-    pub type_annotation: Option<TypeAnnotation>,
+    /// Only valid on class method and constructor parameters under `experimentalDecorators`
+    pub decorators: Vec<Decorator>,
+    pub name: BindingPattern,
+    /// Modifiers making this a parameter property: `constructor(private x: T)`
+    pub modifiers: Modifiers,
+    pub type_annotation: Option<TypeNode>,
     pub optional: bool,
+    /// Whether this is a `...rest` parameter
+    pub rest: bool,
+    pub initializer: Option<Expression>,
     pub span: Span,
 }
 
-/// Type annotation
+/// Type node, as written in a type annotation
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct TypeAnnotation {
-    pub type_name: String,
+pub enum TypeNode {
+    /// A primitive or special type named by a keyword, such as `number` or `never`
+    Keyword(KeywordType),
+    /// `this`
+    This(Span),
+    Reference(TypeReference),
+    Literal(LiteralType),
+    TemplateLiteral(TemplateLiteralType),
+    Union(UnionType),
+    Intersection(IntersectionType),
+    Array(ArrayType),
+    Tuple(TupleType),
+    Function(Box<FunctionType>),
+    Constructor(Box<FunctionType>),
+    TypeLiteral(TypeLiteral),
+    Query(TypeQuery),
+    Operator(TypeOperator),
+    IndexedAccess(IndexedAccessType),
+    Conditional(ConditionalType),
+    Infer(InferType),
+    Mapped(Box<MappedType>),
+    Predicate(TypePredicate),
+    Import(ImportType),
+    Parenthesized(ParenthesizedType),
+}
+
+/// Type named by a keyword
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeywordType {
+    pub kind: KeywordTypeKind,
     pub span: Span,
 }
 
-/// Class declaration
+/// The keywords that name types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeywordTypeKind {
+    Any,
+    Unknown,
+    Number,
+    Bigint,
+    Boolean,
+    String,
+    Symbol,
+    Object,
+    Never,
+    Void,
+    Undefined,
+    /// `intrinsic`, the body of the built-in string manipulation types
+    Intrinsic,
+}
+
+/// A possibly qualified name such as `A` or `A.B.C`
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct ClassDeclaration {
+pub enum EntityName {
+    Identifier(Identifier),
+    Qualified(Box<QualifiedName>),
+}
+
+/// `left.right`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualifiedName {
+    pub left: EntityName,
+    pub right: Identifier,
+    pub span: Span,
+}
+
+/// Named type with optional type arguments, such as `Map<string, number>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeReference {
+    pub name: EntityName,
+    pub type_arguments: Vec<TypeNode>,
+    pub span: Span,
+}
+
+/// Literal type such as `"a"`, `-1`, `10n`, `true` or `null`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiteralType {
+    pub value: Literal,
+    pub span: Span,
+}
+
+/// Template literal type such as `` `prefix-${string}` ``. `quasis` holds one more
+/// element than `types`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateLiteralType {
+    pub quasis: Vec<TemplateElement>,
+    pub types: Vec<TypeNode>,
+    pub span: Span,
+}
+
+/// `A | B`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnionType {
+    pub types: Vec<TypeNode>,
+    pub span: Span,
+}
+
+/// `A & B`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntersectionType {
+    pub types: Vec<TypeNode>,
+    pub span: Span,
+}
+
+/// `T[]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArrayType {
+    pub element_type: Box<TypeNode>,
+    pub span: Span,
+}
+
+/// `[A, B?, ...C[]]` or `[name: A, rest?: B]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TupleType {
+    pub elements: Vec<TupleElement>,
+    pub span: Span,
+}
+
+/// Element of a tuple type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TupleElement {
+    /// Label of a named element, as in `[x: number]`
+    pub name: Option<Identifier>,
+    pub type_node: TypeNode,
+    /// Whether the element is marked optional with `?`
+    pub optional: bool,
+    /// Whether the element is a rest element written with `...`
+    pub rest: bool,
+    pub span: Span,
+}
+
+/// Function type `(x: T) => U` or constructor type `new (x: T) => U`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionType {
+    pub type_parameters: Vec<TypeParameter>,
+    pub parameters: Vec<Parameter>,
+    pub return_type: TypeNode,
+    /// Whether a constructor type is written `abstract new`
+    pub is_abstract: bool,
+    pub span: Span,
+}
+
+/// Type parameter such as `T extends U = V`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeParameter {
     pub name: Identifier,
+    /// `in T`
+    pub is_in: bool,
+    /// `out T`
+    pub is_out: bool,
+    /// `const T`
+    pub is_const: bool,
+    pub constraint: Option<Box<TypeNode>>,
+    pub default: Option<Box<TypeNode>>,
+    pub span: Span,
+}
+
+/// Object type written inline: `{ a: string; m(): void }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeLiteral {
+    pub members: Vec<InterfaceMember>,
+    pub span: Span,
+}
+
+/// `typeof a.b`, with optional type arguments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeQuery {
+    pub expr_name: EntityName,
+    pub type_arguments: Vec<TypeNode>,
+    pub span: Span,
+}
+
+/// `keyof T`, `unique symbol` or `readonly T[]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeOperator {
+    pub operator: TypeOperatorKind,
+    pub type_node: Box<TypeNode>,
+    pub span: Span,
+}
+
+/// Type operator keywords
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypeOperatorKind {
+    Keyof,
+    Unique,
+    Readonly,
+}
+
+/// `T[K]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedAccessType {
+    pub object_type: Box<TypeNode>,
+    pub index_type: Box<TypeNode>,
+    pub span: Span,
+}
+
+/// `T extends U ? X : Y`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConditionalType {
+    pub check_type: Box<TypeNode>,
+    pub extends_type: Box<TypeNode>,
+    pub true_type: Box<TypeNode>,
+    pub false_type: Box<TypeNode>,
+    pub span: Span,
+}
+
+/// `infer U` or `infer U extends C`, in the extends clause of a conditional type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InferType {
+    pub type_parameter: TypeParameter,
+    pub span: Span,
+}
+
+/// `{ readonly [K in T as N]?: V }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MappedType {
+    pub readonly_modifier: Option<MappedTypeModifier>,
+    /// The iteration variable, whose constraint is the type after `in`
+    pub type_parameter: TypeParameter,
+    /// The type after `as`, remapping keys
+    pub name_type: Option<TypeNode>,
+    pub optional_modifier: Option<MappedTypeModifier>,
+    pub type_node: Option<TypeNode>,
+    pub span: Span,
+}
+
+/// How a mapped type changes the `readonly` or `?` modifier of its properties
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MappedTypeModifier {
+    /// Written without a sign, or with `+`: add the modifier
+    Add,
+    /// Written with `-`: remove the modifier
+    Remove,
+}
+
+/// Type predicate in a return type: `x is T`, `asserts x` or `asserts this is T`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypePredicate {
+    pub asserts: bool,
+    /// The parameter name, or `this`
+    pub parameter_name: Identifier,
+    pub type_node: Option<Box<TypeNode>>,
+    pub span: Span,
+}
+
+/// `import("module").Name<T>`, or `typeof import("module")`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportType {
+    pub argument: String,
+    pub qualifier: Option<EntityName>,
+    pub type_arguments: Vec<TypeNode>,
+    pub is_type_of: bool,
+    pub span: Span,
+}
+
+/// `(T)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParenthesizedType {
+    pub type_node: Box<TypeNode>,
+    pub span: Span,
+}
+
+/// Class declaration, also used for class expressions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassDeclaration {
+    pub decorators: Vec<Decorator>,
+    /// `None` for anonymous class expressions and `export default class {}`
+    pub name: Option<Identifier>,
+    pub type_parameters: Vec<TypeParameter>,
     pub super_class: Option<Expression>,
+    /// Type arguments of the base class: `extends Base<T>`
+    pub super_type_arguments: Vec<TypeNode>,
+    pub implements: Vec<TypeNode>,
     pub body: Vec<ClassMember>,
+    pub is_abstract: bool,
+    pub is_declare: bool,
+    pub span: Span,
+}
+
+/// `@expression` before a class, class member or parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Decorator {
+    pub expression: Expression,
     pub span: Span,
 }
 
 /// Class member
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ClassMember {
-    Method(MethodDefinition),
-    Property(PropertyDefinition),
+    Method(Box<MethodDefinition>),
+    Property(Box<PropertyDefinition>),
     Constructor(ConstructorDefinition),
+    IndexSignature(Box<IndexSignature>),
+    /// `static { ... }`
+    StaticBlock(BlockStatement),
+}
+
+/// Modifiers written before a class member or constructor parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Modifiers {
+    pub accessibility: Option<Accessibility>,
+    pub is_static: bool,
+    pub is_readonly: bool,
+    pub is_abstract: bool,
+    pub is_override: bool,
+    pub is_declare: bool,
+    /// `accessor x`, an auto-accessor field
+    pub is_accessor: bool,
+}
+
+/// `public`, `private` or `protected`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Accessibility {
+    Public,
+    Private,
+    Protected,
+}
+
+/// The name of a class or interface member
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PropertyName {
+    Identifier(Identifier),
+    /// A string or numeric name: `"a-b"() {}`, `0: T`
+    Literal(LiteralExpression),
+    /// `#name`, stored without the `#`
+    Private(Identifier),
+    /// `[expression]`
+    Computed(ComputedPropertyName),
+}
+
+/// Computed member name: `[Symbol.iterator]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComputedPropertyName {
+    pub expression: Box<Expression>,
+    pub span: Span,
 }
 
-/// Method definition
+/// Whether a method is a plain method or an accessor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MethodKind {
+    Method,
+    Get,
+    Set,
+}
+
+/// Method or accessor definition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MethodDefinition {
-    pub name: Identifier,
+    pub decorators: Vec<Decorator>,
+    pub name: PropertyName,
+    pub kind: MethodKind,
+    pub modifiers: Modifiers,
+    pub is_async: bool,
+    pub is_generator: bool,
+    pub type_parameters: Vec<TypeParameter>,
     pub parameters: Vec<Parameter>,
-    pub body: BlockStatement,
-    pub is_static: bool,
+    pub return_type: Option<TypeNode>,
+    /// `None` for overload signatures and abstract methods
+    pub body: Option<BlockStatement>,
+    pub optional: bool,
     pub span: Span,
 }
 
 /// Property definition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PropertyDefinition {
-    pub name: Identifier,
+    pub decorators: Vec<Decorator>,
+    pub name: PropertyName,
+    pub modifiers: Modifiers,
+    pub type_annotation: Option<TypeNode>,
+    pub optional: bool,
+    /// Definite assignment assertion: `x!: number`
+    pub definite: bool,
     pub value: Option<Expression>,
-    pub is_static: bool,
     pub span: Span,
 }
 
 /// Constructor definition
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConstructorDefinition {
+    pub modifiers: Modifiers,
+    /// Parameters with modifiers, such as `private x: number`, are parameter
+    /// properties
     pub parameters: Vec<Parameter>,
-    pub body: BlockStatement,
+    /// `None` for overload signatures
+    pub body: Option<BlockStatement>,
     pub span: Span,
 }
 
@@ -378,7 +1297,10 @@ pub struct ConstructorDefinition {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceDeclaration {
     pub name: Identifier,
+    pub type_parameters: Vec<TypeParameter>,
+    pub extends: Vec<TypeNode>,
     pub members: Vec<InterfaceMember>,
+    pub is_declare: bool,
     pub span: Span,
 }
 
@@ -386,24 +1308,52 @@ pub struct InterfaceDeclaration {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum InterfaceMember {
     Property(InterfaceProperty),
-    Method(InterfaceMethod),
+    Method(Box<InterfaceMethod>),
+    CallSignature(SignatureDeclaration),
+    ConstructSignature(SignatureDeclaration),
+    IndexSignature(Box<IndexSignature>),
 }
 
 /// Interface property
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceProperty {
-    pub name: Identifier,
-    pub type_annotation: TypeAnnotation,
+    pub name: PropertyName,
+    /// `readonly`, the only modifier an interface property can have
+    pub modifiers: Modifiers,
+    pub type_annotation: Option<TypeNode>,
     pub optional: bool,
     pub span: Span,
 }
 
-/// Interface method
+/// Interface method or accessor signature
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InterfaceMethod {
-    pub name: Identifier,
+    pub name: PropertyName,
+    pub kind: MethodKind,
+    pub modifiers: Modifiers,
+    pub type_parameters: Vec<TypeParameter>,
     pub parameters: Vec<Parameter>,
-    pub return_type: Option<TypeAnnotation>,
+    pub return_type: Option<TypeNode>,
+    pub optional: bool,
+    pub span: Span,
+}
+
+/// Call or construct signature, such as `(x: number): string` or `new (): T`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignatureDeclaration {
+    pub type_parameters: Vec<TypeParameter>,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<TypeNode>,
+    pub span: Span,
+}
+
+/// Index signature, such as `[key: string]: number`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexSignature {
+    /// `static` and `readonly`, the modifiers an index signature can have
+    pub modifiers: Modifiers,
+    pub parameter: Parameter,
+    pub type_annotation: TypeNode,
     pub span: Span,
 }
 
@@ -411,7 +1361,9 @@ pub struct InterfaceMethod {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TypeDeclaration {
     pub name: Identifier,
-    pub type_annotation: TypeAnnotation,
+    pub type_parameters: Vec<TypeParameter>,
+    pub type_annotation: TypeNode,
+    pub is_declare: bool,
     pub span: Span,
 }
 
@@ -419,23 +1371,28 @@ pub struct TypeDeclaration {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumDeclaration {
     pub name: Identifier,
+    pub is_const: bool,
     pub members: Vec<EnumMember>,
+    pub is_declare: bool,
     pub span: Span,
 }
 
 /// Enum member
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnumMember {
-    pub name: Identifier,
+    pub name: PropertyName,
     pub value: Option<Expression>,
     pub span: Span,
 }
 
-/// Namespace declaration
+/// Namespace or module declaration. A dotted name such as `A.B` is represented as
+/// nested declarations, and an ambient module such as `declare module "fs"` is
+/// named by its string.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NamespaceDeclaration {
     pub name: Identifier,
     pub body: Vec<Statement>,
+    pub is_declare: bool,
     pub span: Span,
 }
 
@@ -444,9 +1401,47 @@ pub struct NamespaceDeclaration {
 pub struct ImportDeclaration {
     pub specifiers: Vec<ImportSpecifier>,
     pub source: String,
+    /// Whether this is an `import type` declaration
+    pub is_type_only: bool,
+    pub attributes: Option<ImportAttributes>,
+    pub span: Span,
+}
+
+/// `with { type: "json" }` after a module specifier, or the older `assert { ... }`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportAttributes {
+    /// Whether the attributes follow the deprecated `assert` keyword
+    pub is_assert: bool,
+    pub elements: Vec<ImportAttribute>,
     pub span: Span,
 }
 
+/// One `key: value` entry of import attributes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportAttribute {
+    /// An identifier or a string literal
+    pub key: Expression,
+    pub value: Expression,
+    pub span: Span,
+}
+
+/// Import alias, such as `import fs = require("fs")` or `import Q = A.B`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportEqualsDeclaration {
+    pub name: Identifier,
+    pub module_reference: ModuleReference,
+    pub span: Span,
+}
+
+/// The right-hand side of an import alias
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ModuleReference {
+    /// `require("module")`
+    External(String),
+    /// A possibly dotted entity name such as `A.B`
+    Entity(EntityName),
+}
+
 /// Import specifier
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ImportSpecifier {
@@ -460,6 +1455,7 @@ pub enum ImportSpecifier {
 pub struct NamedImportSpecifier {
     pub imported: Identifier,
     pub local: Option<Identifier>,
+    pub is_type_only: bool,
     pub span: Span,
 }
 
@@ -469,6 +1465,37 @@ pub struct ExportDeclaration {
     pub declaration: Option<Box<Declaration>>,
     pub specifiers: Vec<ExportSpecifier>,
     pub source: Option<String>,
+    /// Whether this is an `export type` declaration
+    pub is_type_only: bool,
+    pub attributes: Option<ImportAttributes>,
+    pub span: Span,
+}
+
+/// `export default` followed by a declaration or an expression
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportDefaultDeclaration {
+    pub value: ExportDefault,
+    pub span: Span,
+}
+
+/// The value of an `export default`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExportDefault {
+    Declaration(Declaration),
+    Expression(Expression),
+}
+
+/// `export = expression`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportAssignment {
+    pub expression: Expression,
+    pub span: Span,
+}
+
+/// `export as namespace Name;`, which makes a module's exports global in a UMD declaration file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamespaceExportDeclaration {
+    pub name: Identifier,
     pub span: Span,
 }
 
@@ -478,6 +1505,8 @@ pub enum ExportSpecifier {
     Named(NamedExportSpecifier),
     Default(Identifier),
     All,
+    /// `* as name`
+    Namespace(Identifier),
 }
 
 /// Named export specifier
@@ -485,5 +1514,117 @@ pub enum ExportSpecifier {
 pub struct NamedExportSpecifier {
     pub local: Identifier,
     pub exported: Option<Identifier>,
+    pub is_type_only: bool,
     pub span: Span,
-}
\ No newline at end of file
+}
+
+impl Expression {
+    /// Get the source span of this expression
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::Identifier(identifier) | Expression::PrivateName(identifier) => identifier.span,
+            Expression::Literal(literal) => literal.span,
+            Expression::This(span) => *span,
+            Expression::Binary(binary) => binary.span,
+            Expression::Unary(unary) => unary.span,
+            Expression::Call(call) => call.span,
+            Expression::Member(member) => member.span,
+            Expression::Assignment(assignment) => assignment.span,
+            Expression::Function(function) => function.span,
+            Expression::Class(class) => class.span,
+            Expression::Arrow(arrow) => arrow.span,
+            Expression::Object(object) => object.span,
+            Expression::Array(array) => array.span,
+            Expression::Super(span) => *span,
+            Expression::Parenthesized(parenthesized) => parenthesized.span,
+            Expression::Conditional(conditional) => conditional.span,
+            Expression::Sequence(sequence) => sequence.span,
+            Expression::Spread(spread) => spread.span,
+            Expression::New(new) => new.span,
+            Expression::Template(template) => template.span,
+            Expression::TaggedTemplate(tagged) => tagged.span,
+            Expression::OptionalChain(chain) => chain.span,
+            Expression::MetaProperty(meta) => meta.span,
+            Expression::Await(await_expression) => await_expression.span,
+            Expression::Yield(yield_expression) => yield_expression.span,
+            Expression::As(as_expression) => as_expression.span,
+            Expression::Satisfies(satisfies) => satisfies.span,
+            Expression::NonNull(non_null) => non_null.span,
+            Expression::Instantiation(instantiation) => instantiation.span,
+            Expression::TypeAssertion(assertion) => assertion.span,
+            Expression::JsxElement(element) => element.span,
+            Expression::JsxFragment(fragment) => fragment.span,
+        }
+    }
+}
+
+impl TypeNode {
+    /// Get the source span of this type
+    pub fn span(&self) -> Span {
+        match self {
+            TypeNode::Keyword(keyword) => keyword.span,
+            TypeNode::This(span) => *span,
+            TypeNode::Reference(reference) => reference.span,
+            TypeNode::Literal(literal) => literal.span,
+            TypeNode::TemplateLiteral(template) => template.span,
+            TypeNode::Union(union) => union.span,
+            TypeNode::Intersection(intersection) => intersection.span,
+            TypeNode::Array(array) => array.span,
+            TypeNode::Tuple(tuple) => tuple.span,
+            TypeNode::Function(function) | TypeNode::Constructor(function) => function.span,
+            TypeNode::TypeLiteral(literal) => literal.span,
+            TypeNode::Query(query) => query.span,
+            TypeNode::Operator(operator) => operator.span,
+            TypeNode::IndexedAccess(access) => access.span,
+            TypeNode::Conditional(conditional) => conditional.span,
+            TypeNode::Infer(infer) => infer.span,
+            TypeNode::Mapped(mapped) => mapped.span,
+            TypeNode::Predicate(predicate) => predicate.span,
+            TypeNode::Import(import) => import.span,
+            TypeNode::Parenthesized(parenthesized) => parenthesized.span,
+        }
+    }
+}
+
+impl EntityName {
+    /// Get the source span of this name
+    pub fn span(&self) -> Span {
+        match self {
+            EntityName::Identifier(identifier) => identifier.span,
+            EntityName::Qualified(qualified) => qualified.span,
+        }
+    }
+}
+
+impl JsxTagName {
+    /// Get the source span of this name
+    pub fn span(&self) -> Span {
+        match self {
+            JsxTagName::Identifier(identifier) => identifier.span,
+            JsxTagName::Namespaced(name) => name.span,
+            JsxTagName::Member(member) => member.span,
+        }
+    }
+}
+
+impl BindingPattern {
+    /// Get the source span of this pattern
+    pub fn span(&self) -> Span {
+        match self {
+            BindingPattern::Identifier(identifier) => identifier.span,
+            BindingPattern::Object(pattern) => pattern.span,
+            BindingPattern::Array(pattern) => pattern.span,
+        }
+    }
+}
+
+impl AssignmentTarget {
+    /// Get the source span of this target
+    pub fn span(&self) -> Span {
+        match self {
+            AssignmentTarget::Simple(expression) => expression.span(),
+            AssignmentTarget::Object(pattern) => pattern.span,
+            AssignmentTarget::Array(pattern) => pattern.span,
+        }
+    }
+}
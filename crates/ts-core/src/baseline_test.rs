@@ -1,6 +1,6 @@
 use std::fs;
 use std::path::{Path, PathBuf};
-use crate::{compile, CompileOptions};
+use crate::{compile_source, CompileOptions, Diagnostic, Lexer, Parser};
 
 #[derive(Debug)]
 pub struct BaselineTestResult {
@@ -9,6 +9,27 @@ pub struct BaselineTestResult {
     pub differences: Vec<String>,
 }
 
+/// Result of parsing every test case in a directory
+#[derive(Debug, Default)]
+pub struct ParseReport {
+    /// Number of test files parsed
+    pub total: usize,
+    /// Number of test files that parsed without error
+    pub passed: usize,
+    /// Test files that failed to parse, with the first error in each
+    pub failures: Vec<(String, Diagnostic)>,
+}
+
+impl ParseReport {
+    /// Percentage of test files that parsed without error
+    pub fn pass_rate(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+        self.passed as f64 * 100.0 / self.total as f64
+    }
+}
+
 pub struct BaselineTestRunner {
     pub test_dir: PathBuf,
     pub baseline_dir: PathBuf,
@@ -28,7 +49,7 @@ impl BaselineTestRunner {
         if let Ok(entries) = fs::read_dir(&self.test_dir) {
             for entry in entries.flatten() {
                 let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "ts") {
+                if path.extension().is_some_and(|ext| ext == "ts") {
                     let file_name = path.file_stem().unwrap().to_string_lossy();
                     
                     if let Some(pattern) = pattern {
@@ -48,6 +69,13 @@ impl BaselineTestRunner {
 
     async fn run_single_test(&self, test_file: &Path) -> BaselineTestResult {
         let test_name = test_file.file_stem().unwrap().to_string_lossy().to_string();
+        let Ok(source) = fs::read_to_string(test_file) else {
+            return BaselineTestResult {
+                test_name,
+                passed: false,
+                differences: vec!["Could not read test file".to_string()],
+            };
+        };
         
         // 编译测试文件
         let options = CompileOptions {
@@ -55,9 +83,10 @@ impl BaselineTestRunner {
             module: "commonjs".to_string(),
             source_map: false,
             file_name: test_file.to_string_lossy().to_string(),
+            experimental_decorators: false,
         };
         
-        match compile(test_file, &options) {
+        match compile_source(&source, &options) {
             Ok(result) => {
                 // 比较输出与基线
                 let baseline_file = self.baseline_dir.join(format!("{}.js", test_name));
@@ -74,14 +103,14 @@ impl BaselineTestRunner {
                             BaselineTestResult {
                                 test_name,
                                 passed: false,
-                                differences: vec![format!("JS output differs from baseline")],
+                                differences: vec!["JS output differs from baseline".to_string()],
                             }
                         }
                     } else {
                         BaselineTestResult {
                             test_name,
                             passed: false,
-                            differences: vec![format!("Could not read baseline file")],
+                            differences: vec!["Could not read baseline file".to_string()],
                         }
                     }
                 } else {
@@ -90,7 +119,7 @@ impl BaselineTestRunner {
                     BaselineTestResult {
                         test_name,
                         passed: true,
-                        differences: vec![format!("Created new baseline")],
+                        differences: vec!["Created new baseline".to_string()],
                     }
                 }
             }
@@ -98,9 +127,184 @@ impl BaselineTestRunner {
                 BaselineTestResult {
                     test_name,
                     passed: false,
-                    differences: vec![format!("Compilation failed")],
+                    differences: vec!["Compilation failed".to_string()],
+                }
+            }
+        }
+    }
+
+    /// Parse every `.ts` and `.tsx` test case, counting how many parse without error
+    pub fn run_parse_tests(&self, pattern: Option<&str>) -> ParseReport {
+        let mut report = ParseReport::default();
+
+        let Ok(entries) = fs::read_dir(&self.test_dir) else {
+            return report;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ts" || ext == "tsx"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let test_name = path.file_name().unwrap().to_string_lossy().to_string();
+            if let Some(pattern) = pattern {
+                if !test_name.contains(pattern) {
+                    continue;
+                }
+            }
+            let Ok(source) = fs::read_to_string(&path) else {
+                continue;
+            };
+
+            let test_case = TestCase::parse(&test_name, &source);
+            let experimental_decorators = test_case.option_is_true("experimentalDecorators");
+            let mut units = test_case.units.iter().filter(|unit| unit.is_typescript()).peekable();
+            if units.peek().is_none() {
+                continue;
+            }
+            report.total += 1;
+            let mut failure = None;
+            for unit in units {
+                let mut parser = Parser::new(Lexer::new(&unit.content));
+                parser.set_jsx(unit.is_jsx());
+                parser.set_experimental_decorators(experimental_decorators);
+                let (_, diagnostics) = parser.parse();
+                if let Some(first) = diagnostics.into_iter().next() {
+                    let name = if test_case.units.len() == 1 {
+                        test_name.clone()
+                    } else {
+                        format!("{}/{}", test_name, unit.name.trim_start_matches('/'))
+                    };
+                    failure = Some((name, first));
+                    break;
+                }
+            }
+            match failure {
+                None => report.passed += 1,
+                Some(failure) => report.failures.push(failure),
+            }
+        }
+
+        report
+    }
+}
+
+/// A test case split into its files the way the TypeScript test harness does it
+struct TestCase {
+    /// `// @key: value` option comments, which apply to the whole case
+    options: Vec<(String, String)>,
+    units: Vec<TestUnit>,
+}
+
+/// One file of a test case
+struct TestUnit {
+    name: String,
+    content: String,
+}
+
+impl TestCase {
+    /// Split `source` into files at its `// @filename: name` comments. Option comment
+    /// lines are left out of the files; text before the first `@filename` belongs to
+    /// the first file, and a case without one is a single file named `test_name`.
+    fn parse(test_name: &str, source: &str) -> Self {
+        let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+        let mut options = Vec::new();
+        let mut units = Vec::new();
+        let mut name: Option<String> = None;
+        let mut content = String::new();
+        for line in source.split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let Some((key, value)) = parse_option_comment(line) else {
+                // Like the harness, blank lines are dropped until a file has content,
+                // so that a shebang after `@filename` starts its file
+                if !content.is_empty() {
+                    content.push('\n');
+                }
+                content.push_str(line);
+                continue;
+            };
+            if key.eq_ignore_ascii_case("filename") {
+                if let Some(name) = name.take() {
+                    units.push(TestUnit {
+                        name,
+                        content: std::mem::take(&mut content),
+                    });
                 }
+                name = Some(value.to_string());
             }
+            options.push((key.to_string(), value.to_string()));
         }
+        units.push(TestUnit {
+            name: name.unwrap_or_else(|| test_name.to_string()),
+            content,
+        });
+        Self { options, units }
+    }
+
+    /// Check whether the case sets the option `key` to `true`
+    fn option_is_true(&self, key: &str) -> bool {
+        self.options
+            .iter()
+            .any(|(option, value)| option.eq_ignore_ascii_case(key) && value.eq_ignore_ascii_case("true"))
+    }
+}
+
+impl TestUnit {
+    /// Whether the file is TypeScript source rather than JavaScript, JSON or other
+    /// files such as `package.json` that some cases include
+    fn is_typescript(&self) -> bool {
+        let name = self.name.to_ascii_lowercase();
+        [".ts", ".tsx", ".mts", ".cts"].iter().any(|extension| name.ends_with(extension))
+    }
+
+    fn is_jsx(&self) -> bool {
+        self.name.to_ascii_lowercase().ends_with(".tsx")
+    }
+}
+
+/// Read a `// @key: value` option comment line, as matched by the harness's
+/// `^\/\/\s*@(\w+)\s*:\s*([^\r\n]*)`
+fn parse_option_comment(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix("//")?.trim_start().strip_prefix('@')?;
+    let key_end = rest
+        .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+        .unwrap_or(rest.len());
+    let (key, rest) = rest.split_at(key_end);
+    let value = rest.trim_start().strip_prefix(':')?;
+    (!key.is_empty()).then(|| (key, value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cases_split_into_files_at_filename_comments() {
+        let source = "\u{feff}// @target: es5\r\n// @experimentalDecorators: true\r\n\r\n\
+                      // @Filename: a.ts\r\n#!/usr/bin/env node\r\nlet a;\r\n\r\n\
+                      //@filename:/b/c.tsx\n<div />;\n// @filename: package.json\n{}\n";
+        let test_case = TestCase::parse("case.ts", source);
+        let units: Vec<_> = test_case
+            .units
+            .iter()
+            .map(|unit| (unit.name.as_str(), unit.content.as_str(), unit.is_typescript(), unit.is_jsx()))
+            .collect();
+        assert_eq!(
+            units,
+            [
+                ("a.ts", "#!/usr/bin/env node\nlet a;\n", true, false),
+                ("/b/c.tsx", "<div />;", true, true),
+                ("package.json", "{}\n", false, false),
+            ]
+        );
+        assert!(test_case.option_is_true("experimentaldecorators"));
+        assert!(!test_case.option_is_true("target"));
+
+        let test_case = TestCase::parse("single.ts", "// @strict: true\nlet x;\n  // @filename: not.ts\n");
+        assert_eq!(test_case.units.len(), 1);
+        assert_eq!(test_case.units[0].name, "single.ts");
+        assert_eq!(test_case.units[0].content, "let x;\n  // @filename: not.ts\n");
     }
-}
\ No newline at end of file
+}
@@ -1,21 +1,14 @@
 //! Code generation for TypeScript to JavaScript
 
 use crate::ast::{AstNode, Expression, Statement};
-use std::fmt::Write;
 
 /// JavaScript code generator
-pub struct CodeGenerator {
-    output: String,
-    indent_level: usize,
-}
+pub struct CodeGenerator;
 
 impl CodeGenerator {
     /// Create a new code generator
     pub fn new() -> Self {
-        Self {
-            output: String::new(),
-            indent_level: 0,
-        }
+        Self
     }
 
     /// Generate JavaScript code from AST
@@ -42,7 +35,7 @@ impl CodeGenerator {
     
     fn generate_statement(&mut self, stmt: &Statement) -> Result<String, String> {
         match stmt {
-            Statement::Empty => Ok(String::new()),
+            Statement::Empty(_) => Ok(String::new()),
             Statement::Expression(expr) => {
                 Ok(format!("{};\n", self.generate_expression(expr)?))
             }
@@ -33,6 +33,8 @@ pub struct Diagnostic {
     pub severity: Severity,
     pub message: String,
     pub span: Span,
+    /// The matching TypeScript error code, such as `1005` for `TS1005`
+    pub code: Option<u32>,
     pub help: Option<String>,
 }
 
@@ -44,6 +46,19 @@ impl Diagnostic {
             severity: Severity::Error,
             message,
             span,
+            code: None,
+            help: None,
+        }
+    }
+
+    /// Create a new syntax error diagnostic
+    pub fn syntax_error(message: String, span: Span) -> Self {
+        Self {
+            kind: DiagnosticKind::SyntaxError,
+            severity: Severity::Error,
+            message,
+            span,
+            code: None,
             help: None,
         }
     }
@@ -55,10 +70,17 @@ impl Diagnostic {
             severity: Severity::Warning,
             message,
             span,
+            code: None,
             help: None,
         }
     }
 
+    /// Attach a TypeScript error code to this diagnostic
+    pub fn with_code(mut self, code: u32) -> Self {
+        self.code = Some(code);
+        self
+    }
+
     /// Add help text to this diagnostic
     pub fn with_help(mut self, help: String) -> Self {
         self.help = Some(help);
@@ -0,0 +1,89 @@
+//! Directive comments recognised by the scanner.
+//!
+//! Two forms are collected while scanning trivia:
+//!
+//! * `// @key: value` comments, used by test cases to set compiler options such as
+//!   `// @target: es5` or to start a new file with `// @filename: a.ts`
+//! * `/// <reference path="..." />` triple-slash directives in the file header
+
+use crate::utils::span::Span;
+use serde::{Deserialize, Serialize};
+
+/// A `// @key: value` comment that starts a line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentDirective {
+    /// The key after `@`, as written
+    pub key: String,
+    /// The value after the colon, with surrounding whitespace removed
+    pub value: String,
+    /// Span of the whole comment
+    pub span: Span,
+}
+
+/// A `/// <reference ... />` directive before the first token of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceDirective {
+    /// Attributes in source order, such as `("path", "./a.ts")` or `("lib", "es2015")`
+    pub attributes: Vec<(String, String)>,
+    /// Span of the whole comment
+    pub span: Span,
+}
+
+impl ReferenceDirective {
+    /// Get the value of the attribute with the given name
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parse the text of a `//` comment as a `@key: value` directive
+pub(super) fn parse_comment_directive(comment: &str) -> Option<(&str, &str)> {
+    let rest = comment.strip_prefix("//")?.trim_start().strip_prefix('@')?;
+    let key_len = rest
+        .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+        .unwrap_or(rest.len());
+    if key_len == 0 {
+        return None;
+    }
+    let (key, rest) = rest.split_at(key_len);
+    let value = rest.trim_start().strip_prefix(':')?;
+    Some((key, value.trim()))
+}
+
+/// Parse the text of a `//` comment as a `/// <reference ... />` directive,
+/// returning its attributes
+pub(super) fn parse_reference_directive(comment: &str) -> Option<Vec<(String, String)>> {
+    let mut rest = comment
+        .strip_prefix("///")?
+        .trim_start()
+        .strip_prefix("<reference")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.starts_with("/>") {
+            return Some(attributes);
+        }
+
+        let name_len = rest
+            .find(|ch: char| !(ch.is_alphanumeric() || ch == '-' || ch == '_'))
+            .unwrap_or(rest.len());
+        if name_len == 0 {
+            return None;
+        }
+        let (name, after_name) = rest.split_at(name_len);
+        let after_equals = after_name.trim_start().strip_prefix('=')?.trim_start();
+        let quote = after_equals.chars().next().filter(|&ch| ch == '"' || ch == '\'')?;
+        let value_and_rest = &after_equals[1..];
+        let value_len = value_and_rest.find(quote)?;
+
+        attributes.push((name.to_string(), value_and_rest[..value_len].to_string()));
+        rest = &value_and_rest[value_len + 1..];
+    }
+}
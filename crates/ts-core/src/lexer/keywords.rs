@@ -1,66 +1,299 @@
 //! TypeScript keyword definitions and utilities
+//!
+//! Only reserved words are scanned as keyword tokens. Contextual keywords such as
+//! `type`, `as` or `readonly` are scanned as identifiers, and the parser asks
+//! whether an identifier is a particular contextual keyword where the grammar
+//! allows one, so `let type = 1` and `obj.from` remain legal.
 
 use super::TokenKind;
+use crate::utils::intern::{intern, Symbol};
 use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-/// Map of keyword strings to their token kinds
+/// Map of reserved words to their token kinds
 static KEYWORDS: Lazy<HashMap<&'static str, TokenKind>> = Lazy::new(|| {
     let mut map = HashMap::new();
-    map.insert("let", TokenKind::Let);
-    map.insert("const", TokenKind::Const);
-    map.insert("var", TokenKind::Var);
-    map.insert("function", TokenKind::Function);
+    // Reserved words
+    map.insert("break", TokenKind::Break);
+    map.insert("case", TokenKind::Case);
+    map.insert("catch", TokenKind::Catch);
     map.insert("class", TokenKind::Class);
-    map.insert("interface", TokenKind::Interface);
-    map.insert("type", TokenKind::Type);
-    map.insert("enum", TokenKind::Enum);
-    map.insert("namespace", TokenKind::Namespace);
-    map.insert("import", TokenKind::Import);
-    map.insert("export", TokenKind::Export);
-    map.insert("from", TokenKind::From);
-    map.insert("as", TokenKind::As);
+    map.insert("const", TokenKind::Const);
+    map.insert("continue", TokenKind::Continue);
+    map.insert("debugger", TokenKind::Debugger);
     map.insert("default", TokenKind::Default);
-    map.insert("if", TokenKind::If);
-    map.insert("else", TokenKind::Else);
-    map.insert("for", TokenKind::For);
-    map.insert("while", TokenKind::While);
+    map.insert("delete", TokenKind::Delete);
     map.insert("do", TokenKind::Do);
-    map.insert("switch", TokenKind::Switch);
-    map.insert("case", TokenKind::Case);
-    map.insert("break", TokenKind::Break);
-    map.insert("continue", TokenKind::Continue);
-    map.insert("return", TokenKind::Return);
-    map.insert("try", TokenKind::Try);
-    map.insert("catch", TokenKind::Catch);
+    map.insert("else", TokenKind::Else);
+    map.insert("enum", TokenKind::Enum);
+    map.insert("export", TokenKind::Export);
+    map.insert("extends", TokenKind::Extends);
+    map.insert("false", TokenKind::Boolean(false));
     map.insert("finally", TokenKind::Finally);
-    map.insert("throw", TokenKind::Throw);
+    map.insert("for", TokenKind::For);
+    map.insert("function", TokenKind::Function);
+    map.insert("if", TokenKind::If);
+    map.insert("import", TokenKind::Import);
+    map.insert("in", TokenKind::In);
+    map.insert("instanceof", TokenKind::Instanceof);
     map.insert("new", TokenKind::New);
-    map.insert("this", TokenKind::This);
+    map.insert("null", TokenKind::Null);
+    map.insert("return", TokenKind::Return);
     map.insert("super", TokenKind::Super);
-    map.insert("extends", TokenKind::Extends);
+    map.insert("switch", TokenKind::Switch);
+    map.insert("this", TokenKind::This);
+    map.insert("throw", TokenKind::Throw);
+    map.insert("true", TokenKind::Boolean(true));
+    map.insert("try", TokenKind::Try);
+    map.insert("typeof", TokenKind::Typeof);
+    map.insert("var", TokenKind::Var);
+    map.insert("void", TokenKind::Void);
+    map.insert("while", TokenKind::While);
+    map.insert("with", TokenKind::With);
+    // Reserved in strict mode code
     map.insert("implements", TokenKind::Implements);
-    map.insert("public", TokenKind::Public);
+    map.insert("interface", TokenKind::Interface);
+    map.insert("let", TokenKind::Let);
+    map.insert("package", TokenKind::Package);
     map.insert("private", TokenKind::Private);
     map.insert("protected", TokenKind::Protected);
+    map.insert("public", TokenKind::Public);
     map.insert("static", TokenKind::Static);
-    map.insert("abstract", TokenKind::Abstract);
-    map.insert("readonly", TokenKind::Readonly);
-    map.insert("async", TokenKind::Async);
-    map.insert("await", TokenKind::Await);
-    map.insert("true", TokenKind::Boolean(true));
-    map.insert("false", TokenKind::Boolean(false));
-    map.insert("null", TokenKind::Null);
-    map.insert("undefined", TokenKind::Undefined);
+    map.insert("yield", TokenKind::Yield);
     map
 });
 
-/// Check if a string is a TypeScript keyword
+/// Words that act as keywords only in specific syntactic positions and are
+/// otherwise ordinary identifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ContextualKeyword {
+    Abstract,
+    Accessor,
+    Any,
+    As,
+    Assert,
+    Asserts,
+    Async,
+    Await,
+    Bigint,
+    Boolean,
+    Constructor,
+    Declare,
+    From,
+    Get,
+    Global,
+    Infer,
+    Intrinsic,
+    Is,
+    Keyof,
+    Module,
+    Namespace,
+    Never,
+    Number,
+    Object,
+    Of,
+    Out,
+    Override,
+    Readonly,
+    Require,
+    Satisfies,
+    Set,
+    String,
+    Symbol,
+    Type,
+    Undefined,
+    Unique,
+    Unknown,
+    Using,
+}
+
+/// Map of contextual keyword strings to their kinds
+static CONTEXTUAL_KEYWORDS: Lazy<HashMap<&'static str, ContextualKeyword>> = Lazy::new(|| {
+    ContextualKeyword::ALL
+        .iter()
+        .map(|&keyword| (keyword.as_str(), keyword))
+        .collect()
+});
+
+/// Interned symbols of the contextual keywords, in the order of [`ContextualKeyword::ALL`]
+static CONTEXTUAL_KEYWORD_SYMBOLS: Lazy<Vec<Symbol>> = Lazy::new(|| {
+    ContextualKeyword::ALL
+        .iter()
+        .map(|keyword| intern(keyword.as_str()))
+        .collect()
+});
+
+/// Map of interned contextual keyword symbols back to their kinds
+static CONTEXTUAL_KEYWORDS_BY_SYMBOL: Lazy<HashMap<Symbol, ContextualKeyword>> = Lazy::new(|| {
+    ContextualKeyword::ALL
+        .iter()
+        .map(|&keyword| (keyword.symbol(), keyword))
+        .collect()
+});
+
+impl ContextualKeyword {
+    /// Every contextual keyword
+    pub const ALL: [ContextualKeyword; 38] = [
+        Self::Abstract,
+        Self::Accessor,
+        Self::Any,
+        Self::As,
+        Self::Assert,
+        Self::Asserts,
+        Self::Async,
+        Self::Await,
+        Self::Bigint,
+        Self::Boolean,
+        Self::Constructor,
+        Self::Declare,
+        Self::From,
+        Self::Get,
+        Self::Global,
+        Self::Infer,
+        Self::Intrinsic,
+        Self::Is,
+        Self::Keyof,
+        Self::Module,
+        Self::Namespace,
+        Self::Never,
+        Self::Number,
+        Self::Object,
+        Self::Of,
+        Self::Out,
+        Self::Override,
+        Self::Readonly,
+        Self::Require,
+        Self::Satisfies,
+        Self::Set,
+        Self::String,
+        Self::Symbol,
+        Self::Type,
+        Self::Undefined,
+        Self::Unique,
+        Self::Unknown,
+        Self::Using,
+    ];
+
+    /// The interned symbol of this keyword's text
+    pub fn symbol(self) -> Symbol {
+        CONTEXTUAL_KEYWORD_SYMBOLS[self as usize]
+    }
+
+    /// The source text of this keyword
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Abstract => "abstract",
+            Self::Accessor => "accessor",
+            Self::Any => "any",
+            Self::As => "as",
+            Self::Assert => "assert",
+            Self::Asserts => "asserts",
+            Self::Async => "async",
+            Self::Await => "await",
+            Self::Bigint => "bigint",
+            Self::Boolean => "boolean",
+            Self::Constructor => "constructor",
+            Self::Declare => "declare",
+            Self::From => "from",
+            Self::Get => "get",
+            Self::Global => "global",
+            Self::Infer => "infer",
+            Self::Intrinsic => "intrinsic",
+            Self::Is => "is",
+            Self::Keyof => "keyof",
+            Self::Module => "module",
+            Self::Namespace => "namespace",
+            Self::Never => "never",
+            Self::Number => "number",
+            Self::Object => "object",
+            Self::Of => "of",
+            Self::Out => "out",
+            Self::Override => "override",
+            Self::Readonly => "readonly",
+            Self::Require => "require",
+            Self::Satisfies => "satisfies",
+            Self::Set => "set",
+            Self::String => "string",
+            Self::Symbol => "symbol",
+            Self::Type => "type",
+            Self::Undefined => "undefined",
+            Self::Unique => "unique",
+            Self::Unknown => "unknown",
+            Self::Using => "using",
+        }
+    }
+}
+
+/// Check if a string is a reserved word, scanned as a keyword token
 pub fn is_keyword(word: &str) -> bool {
     KEYWORDS.contains_key(word)
 }
 
 /// Get the token kind for a keyword
 pub fn keyword_kind(word: &str) -> TokenKind {
-    KEYWORDS.get(word).cloned().unwrap_or_else(|| TokenKind::Identifier(word.to_string()))
-}
\ No newline at end of file
+    KEYWORDS.get(word).copied().unwrap_or_else(|| TokenKind::Identifier(intern(word)))
+}
+
+/// Get the spelling of a reserved-word token, such as `"class"` for `TokenKind::Class`
+pub fn keyword_text(kind: TokenKind) -> Option<&'static str> {
+    let text = match kind {
+        TokenKind::Break => "break",
+        TokenKind::Case => "case",
+        TokenKind::Catch => "catch",
+        TokenKind::Class => "class",
+        TokenKind::Const => "const",
+        TokenKind::Continue => "continue",
+        TokenKind::Debugger => "debugger",
+        TokenKind::Default => "default",
+        TokenKind::Delete => "delete",
+        TokenKind::Do => "do",
+        TokenKind::Else => "else",
+        TokenKind::Enum => "enum",
+        TokenKind::Export => "export",
+        TokenKind::Extends => "extends",
+        TokenKind::Boolean(false) => "false",
+        TokenKind::Finally => "finally",
+        TokenKind::For => "for",
+        TokenKind::Function => "function",
+        TokenKind::If => "if",
+        TokenKind::Import => "import",
+        TokenKind::In => "in",
+        TokenKind::Instanceof => "instanceof",
+        TokenKind::New => "new",
+        TokenKind::Null => "null",
+        TokenKind::Return => "return",
+        TokenKind::Super => "super",
+        TokenKind::Switch => "switch",
+        TokenKind::This => "this",
+        TokenKind::Throw => "throw",
+        TokenKind::Boolean(true) => "true",
+        TokenKind::Try => "try",
+        TokenKind::Typeof => "typeof",
+        TokenKind::Var => "var",
+        TokenKind::Void => "void",
+        TokenKind::While => "while",
+        TokenKind::With => "with",
+        TokenKind::Implements => "implements",
+        TokenKind::Interface => "interface",
+        TokenKind::Let => "let",
+        TokenKind::Package => "package",
+        TokenKind::Private => "private",
+        TokenKind::Protected => "protected",
+        TokenKind::Public => "public",
+        TokenKind::Static => "static",
+        TokenKind::Yield => "yield",
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// Get the contextual keyword an identifier spells, if any
+pub fn contextual_keyword(word: &str) -> Option<ContextualKeyword> {
+    CONTEXTUAL_KEYWORDS.get(word).copied()
+}
+
+/// Get the contextual keyword an interned identifier spells, if any
+pub fn contextual_keyword_for_symbol(symbol: Symbol) -> Option<ContextualKeyword> {
+    CONTEXTUAL_KEYWORDS_BY_SYMBOL.get(&symbol).copied()
+}
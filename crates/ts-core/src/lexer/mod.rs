@@ -3,18 +3,23 @@
 mod token;
 mod scanner;
 mod keywords;
-mod lexer;
+mod entities;
+mod directives;
+mod stream;
 
-pub use token::{Token, TokenKind};
-pub use scanner::Scanner;
-pub use keywords::{is_keyword, keyword_kind};
-pub use lexer::Lexer;
+pub use token::{Token, TokenKind, Trivia, TriviaKind};
+pub use scanner::{Checkpoint, Scanner, ScriptTarget};
+pub use directives::{CommentDirective, ReferenceDirective};
+pub use keywords::{contextual_keyword, is_keyword, keyword_kind, ContextualKeyword};
+pub use stream::Lexer;
 
-use crate::utils::span::Span;
-use std::error::Error;
+use crate::diagnostics::Diagnostic;
 
-/// Tokenize a source string into a vector of tokens
-pub fn tokenize(source: &str) -> Result<Vec<Token>, Box<dyn Error>> {
+/// Tokenize a source string into its tokens and any diagnostics reported while scanning.
+///
+/// Operators are scanned by longest match, so `a >>>= b` gives a single
+/// `GreaterGreaterGreaterEqual`; only the parser splits `>` apart.
+pub fn tokenize(source: &str) -> (Vec<Token>, Vec<Diagnostic>) {
     let mut lexer = Lexer::new(source);
     lexer.tokenize()
 }
\ No newline at end of file
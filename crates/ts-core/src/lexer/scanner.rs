@@ -1,39 +1,280 @@
 //! Scanner implementation for tokenizing TypeScript source code
 
-use super::{Token, TokenKind};
+use super::{Token, TokenKind, Trivia, TriviaKind};
+use super::directives::{
+    parse_comment_directive, parse_reference_directive, CommentDirective, ReferenceDirective,
+};
+use super::entities::decode_entities;
 use super::keywords::{is_keyword, keyword_kind};
+use crate::diagnostics::Diagnostic;
+use crate::utils::intern::{intern, Symbol};
 use crate::utils::span::{Span, Position};
+use std::fmt;
+use std::str::FromStr;
 
-/// Scanner for tokenizing TypeScript source code
+/// ECMAScript version targeted by the compilation, which gates newer syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ScriptTarget {
+    ES3,
+    ES5,
+    ES2015,
+    ES2016,
+    ES2017,
+    ES2018,
+    ES2019,
+    ES2020,
+    ES2021,
+    ES2022,
+    ES2023,
+    ES2024,
+    ESNext,
+}
+
+impl FromStr for ScriptTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "es3" => Ok(Self::ES3),
+            "es5" => Ok(Self::ES5),
+            "es6" | "es2015" => Ok(Self::ES2015),
+            "es2016" => Ok(Self::ES2016),
+            "es2017" => Ok(Self::ES2017),
+            "es2018" => Ok(Self::ES2018),
+            "es2019" => Ok(Self::ES2019),
+            "es2020" => Ok(Self::ES2020),
+            "es2021" => Ok(Self::ES2021),
+            "es2022" => Ok(Self::ES2022),
+            "es2023" => Ok(Self::ES2023),
+            "es2024" => Ok(Self::ES2024),
+            "esnext" => Ok(Self::ESNext),
+            _ => Err(format!("Unknown target '{}'", s)),
+        }
+    }
+}
+
+impl fmt::Display for ScriptTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::ES3 => "ES3",
+            Self::ES5 => "ES5",
+            Self::ES2015 => "ES2015",
+            Self::ES2016 => "ES2016",
+            Self::ES2017 => "ES2017",
+            Self::ES2018 => "ES2018",
+            Self::ES2019 => "ES2019",
+            Self::ES2020 => "ES2020",
+            Self::ES2021 => "ES2021",
+            Self::ES2022 => "ES2022",
+            Self::ES2023 => "ES2023",
+            Self::ES2024 => "ES2024",
+            Self::ESNext => "ESNext",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A saved scanner state that [`Scanner::rewind`] can return to, for speculative parsing
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    position: Position,
+    token_kind: TokenKind,
+    token_start: Position,
+    token_end: Position,
+    token_leading_trivia: Vec<Trivia>,
+    token_preceded_by_newline: bool,
+    pending_newline: bool,
+    template_depths: Vec<usize>,
+    reached_eof: bool,
+    pub(super) diagnostic_count: usize,
+    pub(super) directive_count: usize,
+    pub(super) reference_count: usize,
+    in_file_header: bool,
+}
+
+/// Scanner for tokenizing TypeScript source code.
+///
+/// Positions are tracked as byte offsets into the source; columns count Unicode
+/// scalar values from the start of the line.
 #[derive(Debug)]
-pub struct Scanner {
-    source: Vec<char>,
+pub struct Scanner<'a> {
+    source: &'a str,
+    /// Byte offset of the current character
     position: usize,
     line: usize,
     column: usize,
+    strict_mode: bool,
+    target: ScriptTarget,
+    /// Scratch space for decoding escaped token text before it is interned
+    buffer: String,
+    preserve_trivia: bool,
+    /// Whether `>` is always scanned on its own, as the parser needs
+    split_greater: bool,
+    diagnostics: Vec<Diagnostic>,
+    /// `// @key: value` comments seen so far
+    directives: Vec<CommentDirective>,
+    /// `/// <reference ... />` directives in the file header
+    references: Vec<ReferenceDirective>,
+    /// Whether no token has been scanned since the start of the file or of the
+    /// last `// @filename` section, so that reference directives apply
+    in_file_header: bool,
+    /// Kind, start and end of the most recently scanned token, for rescanning
+    token_kind: TokenKind,
+    token_start: Position,
+    token_end: Position,
+    /// Leading trivia and line-break flag of the most recent token, for rescanning
+    token_leading_trivia: Vec<Trivia>,
+    token_preceded_by_newline: bool,
+    /// Whether the trailing trivia of the previous token contained a line break
+    pending_newline: bool,
+    /// Brace depth within each open template substitution, innermost last
+    template_depths: Vec<usize>,
+    /// Whether invalid escapes in templates are reported. They are allowed in tagged
+    /// templates, so the parser asks for them only through `rescan_template_token`.
+    report_template_escapes: bool,
+    /// Whether the end-of-file token has been produced
+    reached_eof: bool,
 }
 
-impl Scanner {
+impl<'a> Scanner<'a> {
     /// Create a new scanner for the given source code
-    pub fn new(source: &str) -> Self {
+    pub fn new(source: &'a str) -> Self {
         Self {
-            source: source.chars().collect(),
+            source,
             position: 0,
             line: 1,
             column: 1,
+            strict_mode: false,
+            target: ScriptTarget::ESNext,
+            buffer: String::new(),
+            preserve_trivia: false,
+            split_greater: false,
+            diagnostics: Vec::new(),
+            directives: Vec::new(),
+            references: Vec::new(),
+            in_file_header: true,
+            token_kind: TokenKind::Eof,
+            token_start: Position::default(),
+            token_end: Position::default(),
+            token_leading_trivia: Vec::new(),
+            token_preceded_by_newline: false,
+            pending_newline: false,
+            template_depths: Vec::new(),
+            report_template_escapes: false,
+            reached_eof: false,
         }
     }
 
+    /// Enable or disable strict mode, which rejects legacy octal escapes
+    pub fn set_strict_mode(&mut self, strict: bool) {
+        self.strict_mode = strict;
+    }
+
+    /// Set the target used to validate version-dependent syntax such as regex flags
+    pub fn set_target(&mut self, target: ScriptTarget) {
+        self.target = target;
+    }
+
+    /// Enable or disable lossless mode, in which every token records its leading and
+    /// trailing trivia so that the token stream reproduces the source exactly
+    pub fn set_preserve_trivia(&mut self, preserve: bool) {
+        self.preserve_trivia = preserve;
+    }
+
+    /// Enable or disable scanning `>` on its own rather than as the longest operator
+    /// starting with it. The parser needs this so that `Array<Array<T>>` closes two
+    /// type argument lists, and combines `>` with what follows through
+    /// `rescan_greater_token` where a binary operator may appear.
+    pub fn set_split_greater(&mut self, split: bool) {
+        self.split_greater = split;
+    }
+
+    /// The source text being scanned
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Diagnostics reported while scanning so far
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Take the diagnostics reported so far, leaving the list empty
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// `// @key: value` directive comments seen so far, in source order
+    pub fn directives(&self) -> &[CommentDirective] {
+        &self.directives
+    }
+
+    /// `/// <reference ... />` directives seen so far. Only those before the first
+    /// token of the file, or of a `// @filename` section, are recognised.
+    pub fn references(&self) -> &[ReferenceDirective] {
+        &self.references
+    }
+
+    /// Save the current state so scanning can later resume from here
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.current_position(),
+            token_kind: self.token_kind,
+            token_start: self.token_start,
+            token_end: self.token_end,
+            token_leading_trivia: self.token_leading_trivia.clone(),
+            token_preceded_by_newline: self.token_preceded_by_newline,
+            pending_newline: self.pending_newline,
+            template_depths: self.template_depths.clone(),
+            reached_eof: self.reached_eof,
+            diagnostic_count: self.diagnostics.len(),
+            directive_count: self.directives.len(),
+            reference_count: self.references.len(),
+            in_file_header: self.in_file_header,
+        }
+    }
+
+    /// Return to a saved state, discarding the diagnostics reported since it was taken
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.reset_to(checkpoint.position);
+        self.token_kind = checkpoint.token_kind;
+        self.token_start = checkpoint.token_start;
+        self.token_end = checkpoint.token_end;
+        self.token_leading_trivia = checkpoint.token_leading_trivia;
+        self.token_preceded_by_newline = checkpoint.token_preceded_by_newline;
+        self.pending_newline = checkpoint.pending_newline;
+        self.template_depths = checkpoint.template_depths;
+        self.reached_eof = checkpoint.reached_eof;
+        self.diagnostics.truncate(checkpoint.diagnostic_count);
+        self.directives.truncate(checkpoint.directive_count);
+        self.references.truncate(checkpoint.reference_count);
+        self.in_file_header = checkpoint.in_file_header;
+    }
+
+    /// Report a syntax error with the given TypeScript error code, spanning from the
+    /// given start to the current position
+    fn error(&mut self, code: u32, message: &str, start: Position) {
+        let span = self.make_span(start);
+        self.diagnostics
+            .push(Diagnostic::syntax_error(message.to_string(), span).with_code(code));
+    }
+
     /// Get the current character without advancing
     fn current_char(&self) -> Option<char> {
-        self.source.get(self.position).copied()
+        self.source[self.position..].chars().next()
+    }
+
+    /// Peek at the character `n` positions ahead of the current one
+    fn peek_char(&self, n: usize) -> Option<char> {
+        self.source[self.position..].chars().nth(n)
     }
 
     /// Advance to the next character
     fn advance(&mut self) -> Option<char> {
         if let Some(ch) = self.current_char() {
-            self.position += 1;
-            if ch == '\n' {
+            self.position += ch.len_utf8();
+            // `\r\n` counts as a single line break, on its `\n`
+            if ch == '\n' || (ch == '\r' && self.current_char() != Some('\n')) {
                 self.line += 1;
                 self.column = 1;
             } else {
@@ -45,116 +286,1947 @@ impl Scanner {
         }
     }
 
-    /// Skip whitespace characters
-    fn skip_whitespace(&mut self) {
+    /// Get the current position
+    fn current_position(&self) -> Position {
+        Position {
+            line: self.line,
+            column: self.column,
+            offset: self.position,
+        }
+    }
+
+    /// Move back to a previously recorded position
+    fn reset_to(&mut self, position: Position) {
+        self.position = position.offset;
+        self.line = position.line;
+        self.column = position.column;
+    }
+
+    /// Create a span from start position to current position
+    fn make_span(&self, start: Position) -> Span {
+        Span {
+            start,
+            end: self.current_position(),
+        }
+    }
+
+    /// Scan a run of trivia. Leading trivia runs up to the next token; trailing trivia
+    /// stops before the first line break. Pieces are only recorded in lossless mode.
+    /// Returns whether the run contained a line break.
+    fn scan_trivia(&mut self, trailing: bool, pieces: &mut Vec<Trivia>) -> bool {
+        let mut has_newline = false;
+
+        loop {
+            let start = self.current_position();
+            let kind = match self.current_char() {
+                Some('\u{feff}') if self.position == 0 => {
+                    self.advance();
+                    TriviaKind::ByteOrderMark
+                }
+                Some('#') if self.at_file_start() && self.peek_char(1) == Some('!') => {
+                    self.skip_line();
+                    TriviaKind::Shebang
+                }
+                Some(ch) if is_line_break(ch) => {
+                    if trailing {
+                        break;
+                    }
+                    self.advance();
+                    if ch == '\r' && self.current_char() == Some('\n') {
+                        self.advance();
+                    }
+                    has_newline = true;
+                    TriviaKind::Newline
+                }
+                Some(ch) if is_whitespace(ch) => {
+                    while self.current_char().is_some_and(is_whitespace) {
+                        self.advance();
+                    }
+                    TriviaKind::Whitespace
+                }
+                Some('/') if self.peek_char(1) == Some('/') => {
+                    self.skip_line();
+                    self.record_directive(start);
+                    TriviaKind::SingleLineComment
+                }
+                Some('/') if self.peek_char(1) == Some('*') => {
+                    self.advance();
+                    self.advance();
+                    loop {
+                        match self.current_char() {
+                            None => {
+                                self.error(1010, "'*/' expected.", start);
+                                break;
+                            }
+                            Some('*') if self.peek_char(1) == Some('/') => {
+                                self.advance();
+                                self.advance();
+                                break;
+                            }
+                            Some(ch) => {
+                                has_newline |= is_line_break(ch);
+                                self.advance();
+                            }
+                        }
+                    }
+                    TriviaKind::MultiLineComment
+                }
+                _ => break,
+            };
+
+            if self.preserve_trivia {
+                pieces.push(Trivia {
+                    kind,
+                    span: self.make_span(start),
+                });
+            }
+        }
+
+        has_newline
+    }
+
+    /// Check whether the scanner is at the start of the file, after any byte order mark
+    fn at_file_start(&self) -> bool {
+        self.position == 0 || (self.position == 3 && self.source.starts_with('\u{feff}'))
+    }
+
+    /// Record the `//` comment that started at `start` and ends at the current
+    /// position if it is a directive
+    fn record_directive(&mut self, start: Position) {
+        let comment = &self.source[start.offset..self.position];
+        if comment.starts_with("///") && self.in_file_header {
+            if let Some(attributes) = parse_reference_directive(comment) {
+                let span = self.make_span(start);
+                self.references.push(ReferenceDirective { attributes, span });
+            }
+        } else if let Some((key, value)) = parse_comment_directive(comment) {
+            let line_start = self.source[..start.offset].trim_end_matches(is_whitespace);
+            if line_start.is_empty() || line_start.ends_with(is_line_break) {
+                // Test cases with several files start each one with `// @filename`
+                if key.eq_ignore_ascii_case("filename") {
+                    self.in_file_header = true;
+                }
+                let span = self.make_span(start);
+                self.directives.push(CommentDirective {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    span,
+                });
+            }
+        }
+    }
+
+    /// Advance to the end of the current line, leaving the line break itself
+    fn skip_line(&mut self) {
+        while self.current_char().is_some_and(|ch| !is_line_break(ch)) {
+            self.advance();
+        }
+    }
+
+    /// Finish a token of the given kind that started at `start`, scanning its trailing trivia
+    fn finish_token(&mut self, kind: TokenKind, start: Position, leading_trivia: Vec<Trivia>, preceded_by_newline: bool) -> Token {
+        let span = self.make_span(start);
+        self.token_kind = kind;
+        self.token_end = span.end;
+        self.in_file_header = false;
+        let mut trailing_trivia = Vec::new();
+        self.pending_newline = self.scan_trivia(true, &mut trailing_trivia);
+
+        Token {
+            kind,
+            span,
+            leading_trivia,
+            trailing_trivia,
+            preceded_by_newline,
+        }
+    }
+
+    /// Get the next token from the source
+    pub fn next_token(&mut self) -> Option<Token> {
+        let (start, leading_trivia, preceded_by_newline) = self.start_token();
+        let kind = self.scan_token_kind(start)?;
+        Some(self.finish_token(kind, start, leading_trivia, preceded_by_newline))
+    }
+
+    /// Scan the leading trivia of the next token and record where it starts.
+    /// Returns the token's start, its leading trivia and whether a line break precedes it.
+    fn start_token(&mut self) -> (Position, Vec<Trivia>, bool) {
+        let mut leading_trivia = Vec::new();
+        let preceded_by_newline = self.scan_trivia(false, &mut leading_trivia) || self.pending_newline;
+        self.pending_newline = false;
+
+        let start = self.current_position();
+        self.token_start = start;
+        if self.preserve_trivia {
+            self.token_leading_trivia = leading_trivia.clone();
+        }
+        self.token_preceded_by_newline = preceded_by_newline;
+        (start, leading_trivia, preceded_by_newline)
+    }
+
+    /// Scan the kind of the token starting at `start`. Returns `None` once the
+    /// end-of-file token has already been produced.
+    fn scan_token_kind(&mut self, start: Position) -> Option<TokenKind> {
+        let Some(current) = self.current_char() else {
+            if self.reached_eof {
+                return None;
+            }
+            self.reached_eof = true;
+            return Some(TokenKind::Eof);
+        };
+
+        let kind = match current {
+            // Identifiers and keywords, possibly starting with a `\u` escape
+            ch if is_identifier_start(ch) || (ch == '\\' && self.peek_char(1) == Some('u')) => {
+                self.scan_identifier()
+            }
+            // Numbers, including those with a leading dot such as `.5`
+            ch if ch.is_ascii_digit()
+                || (ch == '.' && self.peek_char(1).is_some_and(|c| c.is_ascii_digit())) =>
+            {
+                self.scan_number()
+            }
+            // String literals
+            '"' | '\'' => self.scan_string(),
+            // Template literals, and the `}` that resumes one after a substitution
+            '`' => {
+                self.advance();
+                self.scan_template(true, start)
+            }
+            '}' if self.template_depths.last() == Some(&0) => {
+                self.template_depths.pop();
+                self.advance();
+                self.scan_template(false, start)
+            }
+            // Operators and punctuation
+            ch if is_punctuation_start(ch) => self.scan_punctuation(ch),
+            // Anything else is reported and kept as an unknown token so parsing can continue
+            _ => {
+                self.advance();
+                self.error(1127, "Invalid character.", start);
+                TokenKind::Unknown
+            }
+        };
+
+        Some(kind)
+    }
+
+    /// Rewind to the start of the most recent token for rescanning it differently
+    fn rewind_token(&mut self) -> (Position, Vec<Trivia>) {
+        let start = self.token_start;
+        self.reset_to(start);
+        (start, self.token_leading_trivia.clone())
+    }
+
+    /// Scan an identifier or keyword, decoding `\u` escapes into the identifier text
+    fn scan_identifier(&mut self) -> TokenKind {
+        let start = self.current_position();
+        while self.current_char().is_some_and(is_identifier_part) {
+            self.advance();
+        }
+        if self.current_char() != Some('\\') {
+            // Fast path: the identifier is exactly its source text
+            let source = self.source;
+            return keyword_kind(&source[start.offset..self.position]);
+        }
+
+        let mut value = std::mem::take(&mut self.buffer);
+        value.clear();
+        value.push_str(&self.source[start.offset..self.position]);
+
+        while let Some(ch) = self.current_char() {
+            if ch == '\\' {
+                let escape_start = self.current_position();
+                if self.peek_char(1) != Some('u') {
+                    self.advance();
+                    self.error(1127, "Invalid character.", escape_start);
+                    continue;
+                }
+                self.advance();
+                self.advance();
+
+                let decoded = self
+                    .scan_unicode_escape_value(escape_start)
+                    .and_then(char::from_u32);
+                match decoded {
+                    Some(ch) if (value.is_empty() && is_identifier_start(ch))
+                        || (!value.is_empty() && is_identifier_part(ch)) => value.push(ch),
+                    Some(_) => self.error(1127, "Invalid character.", escape_start),
+                    None => {}
+                }
+            } else if is_identifier_part(ch) {
+                value.push(ch);
+                self.advance();
+            } else {
+                break;
+            }
+        }
+
+        if is_keyword(&value) {
+            // An escaped keyword is never treated as the keyword itself
+            self.error(1260, "Keywords cannot contain escape characters.", start);
+        }
+        let kind = TokenKind::Identifier(intern(&value));
+        self.buffer = value;
+        kind
+    }
+
+    /// Scan a numeric literal: decimal, hex, octal, binary, legacy octal or BigInt
+    fn scan_number(&mut self) -> TokenKind {
+        let start = self.current_position();
+        let source = self.source;
+
+        if self.current_char() == Some('0') {
+            let radix = match self.peek_char(1) {
+                Some('x' | 'X') => Some((16, 1125, "Hexadecimal digit expected.")),
+                Some('o' | 'O') => Some((8, 1178, "Octal digit expected.")),
+                Some('b' | 'B') => Some((2, 1177, "Binary digit expected.")),
+                _ => None,
+            };
+            if let Some((radix, code, missing_digits)) = radix {
+                self.advance();
+                self.advance();
+                let digits_start = self.position;
+                if self.scan_digits(radix) == 0 {
+                    self.error(code, missing_digits, start);
+                }
+                let digits = &source[digits_start..self.position];
+                if self.current_char() == Some('n') {
+                    let text = &source[start.offset..self.position];
+                    self.advance();
+                    self.check_identifier_after_number(start);
+                    return TokenKind::BigInt(intern_without_separators(text));
+                }
+                self.check_identifier_after_number(start);
+                return TokenKind::Number(parse_radix(digits, radix));
+            }
+
+            if self.peek_char(1).is_some_and(|c| c.is_ascii_digit()) {
+                return self.scan_leading_zero_number(start);
+            }
+        }
+
+        self.scan_digits(10);
+        let mut is_integer = true;
+        if self.current_char() == Some('.') {
+            self.advance();
+            is_integer = false;
+            self.scan_digits(10);
+        }
+
+        // The end of the numeric text, excluding an exponent marker without digits
+        let mut end = self.position;
+        let mut has_exponent = false;
+        if matches!(self.current_char(), Some('e' | 'E')) {
+            let exponent_start = self.current_position();
+            self.advance();
+            if matches!(self.current_char(), Some('+' | '-')) {
+                self.advance();
+            }
+            if self.scan_digits(10) == 0 {
+                self.error(1124, "Digit expected.", exponent_start);
+            } else {
+                end = self.position;
+                has_exponent = true;
+            }
+            is_integer = false;
+        }
+        let text = &source[start.offset..end];
+
+        if self.current_char() == Some('n') {
+            self.advance();
+            if !is_integer {
+                if has_exponent {
+                    self.error(1352, "A bigint literal cannot use exponential notation.", start);
+                } else {
+                    self.error(1353, "A bigint literal must be an integer.", start);
+                }
+            }
+            self.check_identifier_after_number(start);
+            return TokenKind::BigInt(intern_without_separators(text));
+        }
+
+        self.check_identifier_after_number(start);
+        TokenKind::Number(parse_decimal(text))
+    }
+
+    /// Scan a number that starts with `0` followed by more digits: a legacy octal
+    /// literal such as `017`, or a decimal with a leading zero such as `089`
+    fn scan_leading_zero_number(&mut self, start: Position) -> TokenKind {
+        let source = self.source;
+        while self.current_char().is_some_and(|c| c.is_ascii_digit()) {
+            self.advance();
+        }
+        let digits = &source[start.offset..self.position];
+
+        if digits.chars().all(|c| c.is_digit(8)) {
+            let message = format!(
+                "Octal literals are not allowed. Use the syntax '0o{}'.",
+                digits.trim_start_matches('0')
+            );
+            self.error(1121, &message, start);
+            self.check_identifier_after_number(start);
+            return TokenKind::Number(parse_radix(digits, 8));
+        }
+
+        self.error(1489, "Decimals with leading zeros are not allowed.", start);
+        if self.current_char() == Some('.') {
+            self.advance();
+            self.scan_digits(10);
+        }
+        let text = &source[start.offset..self.position];
+        self.check_identifier_after_number(start);
+        TokenKind::Number(parse_decimal(text))
+    }
+
+    /// Scan a run of digits in the given radix, validating `_` separators.
+    /// Returns the number of digits scanned.
+    fn scan_digits(&mut self, radix: u32) -> usize {
+        let mut count = 0;
+        let mut previous_was_separator = false;
+        let mut last_separator = self.current_position();
+
         while let Some(ch) = self.current_char() {
-            if ch.is_whitespace() {
+            if ch == '_' {
+                let separator = self.current_position();
+                last_separator = separator;
+                self.advance();
+                if previous_was_separator {
+                    self.error(6189, "Multiple consecutive numeric separators are not permitted.", separator);
+                } else if count == 0 {
+                    self.error(6188, "Numeric separators are not allowed here.", separator);
+                }
+                previous_was_separator = true;
+            } else if ch.is_digit(radix) {
                 self.advance();
+                count += 1;
+                previous_was_separator = false;
             } else {
                 break;
             }
         }
+
+        if previous_was_separator {
+            // Report a trailing separator at the separator itself
+            self.error(6188, "Numeric separators are not allowed here.", last_separator);
+        }
+        count
     }
 
-    /// Create a span from start position to current position
-    fn make_span(&self, start_line: usize, start_column: usize) -> Span {
-        Span {
-            start: Position {
-                line: start_line,
-                column: start_column,
-                offset: 0, // Will be calculated properly later
-            },
-            end: Position {
-                line: self.line,
-                column: self.column,
-                offset: self.position,
-            },
+    /// Report an identifier that starts immediately after a numeric literal, as in `3in x`
+    fn check_identifier_after_number(&mut self, start: Position) {
+        if self.current_char().is_some_and(is_identifier_start) {
+            while self.current_char().is_some_and(is_identifier_part) {
+                self.advance();
+            }
+            self.error(
+                1351, "An identifier or keyword cannot immediately follow a numeric literal.",
+                start,
+            );
         }
     }
 
-    /// Get the next token from the source
-    pub fn next_token(&mut self) -> Option<Token> {
-        self.skip_whitespace();
-
-        let start_line = self.line;
-        let start_column = self.column;
-
-        match self.current_char()? {
-            // End of file
-            '\0' | _ if self.position >= self.source.len() => {
-                Some(Token {
-                    kind: TokenKind::Eof,
-                    span: self.make_span(start_line, start_column),
-                })
-            }
-            // Single character tokens
-            '+' => {
-                self.advance();
-                Some(Token {
-                    kind: TokenKind::Plus,
-                    span: self.make_span(start_line, start_column),
-                })
-            }
-            '-' => {
-                self.advance();
-                Some(Token {
-                    kind: TokenKind::Minus,
-                    span: self.make_span(start_line, start_column),
-                })
-            }
-            ';' => {
-                self.advance();
-                Some(Token {
-                    kind: TokenKind::Semicolon,
-                    span: self.make_span(start_line, start_column),
-                })
-            }
-            // Identifiers and keywords
-            ch if ch.is_alphabetic() || ch == '_' || ch == '$' => {
-                let mut value = String::new();
-                while let Some(ch) = self.current_char() {
-                    if ch.is_alphanumeric() || ch == '_' || ch == '$' {
-                        value.push(ch);
+    /// Scan a single- or double-quoted string literal, decoding its escape sequences
+    fn scan_string(&mut self) -> TokenKind {
+        let start = self.current_position();
+        let quote = self.advance();
+        let mut value = std::mem::take(&mut self.buffer);
+        value.clear();
+
+        loop {
+            match self.current_char() {
+                None | Some('\n') | Some('\r') => {
+                    self.error(1002, "Unterminated string literal.", start);
+                    break;
+                }
+                Some(ch) if Some(ch) == quote => {
+                    self.advance();
+                    break;
+                }
+                Some('\\') => {
+                    self.scan_escape_sequence(&mut value, false);
+                }
+                Some(ch) => {
+                    value.push(ch);
+                    self.advance();
+                }
+            }
+        }
+
+        let kind = TokenKind::String(intern(&value));
+        self.buffer = value;
+        kind
+    }
+
+    /// Rescan the most recent `/` or `/=` token as a regular expression literal.
+    ///
+    /// The scanner cannot tell division from a regex on its own, so the parser calls
+    /// this when it finds a slash in expression position.
+    pub fn rescan_slash_as_regex(&mut self) -> Option<Token> {
+        if !self.source[self.token_start.offset..].starts_with('/') {
+            return None;
+        }
+        let (start, leading_trivia) = self.rewind_token();
+        self.advance();
+
+        let source = self.source;
+        let pattern_start = self.position;
+        let mut pattern_end = None;
+        let mut in_class = false;
+        loop {
+            match self.current_char() {
+                None => {
+                    self.error(1161, "Unterminated regular expression literal.", start);
+                    break;
+                }
+                Some(ch) if is_line_break(ch) => {
+                    self.error(1161, "Unterminated regular expression literal.", start);
+                    break;
+                }
+                Some('/') if !in_class => {
+                    pattern_end = Some(self.position);
+                    self.advance();
+                    break;
+                }
+                Some('\\') => {
+                    self.advance();
+                    if self.current_char().is_some_and(|ch| !is_line_break(ch)) {
                         self.advance();
-                    } else {
-                        break;
                     }
                 }
+                Some(ch) => {
+                    if ch == '[' {
+                        in_class = true;
+                    } else if ch == ']' {
+                        in_class = false;
+                    }
+                    self.advance();
+                }
+            }
+        }
 
-                let kind = if is_keyword(&value) {
-                    keyword_kind(&value)
-                } else {
-                    TokenKind::Identifier(value)
-                };
+        let pattern = &source[pattern_start..pattern_end.unwrap_or(self.position)];
 
-                Some(Token {
-                    kind,
-                    span: self.make_span(start_line, start_column),
-                })
-            }
-            // Numbers
-            ch if ch.is_ascii_digit() => {
-                let mut value = String::new();
-                while let Some(ch) = self.current_char() {
-                    if ch.is_ascii_digit() || ch == '.' {
-                        value.push(ch);
+        let flags_start = self.position;
+        while let Some(ch) = self.current_char().filter(|c| is_identifier_part(*c)) {
+            let flag_start = self.current_position();
+            self.advance();
+            self.check_regex_flag(ch, &source[flags_start..flag_start.offset], flag_start);
+        }
+        let flags = &source[flags_start..self.position];
+
+        let kind = TokenKind::RegularExpression {
+            pattern: intern(pattern),
+            flags: intern(flags),
+        };
+        Some(self.finish_token(kind, start, leading_trivia, self.token_preceded_by_newline))
+    }
+
+    /// Validate a regular expression flag against the flags before it and the target
+    fn check_regex_flag(&mut self, flag: char, previous: &str, start: Position) {
+        let required_target = match flag {
+            'g' | 'i' | 'm' => ScriptTarget::ES3,
+            'u' | 'y' => ScriptTarget::ES2015,
+            's' => ScriptTarget::ES2018,
+            'd' => ScriptTarget::ES2022,
+            'v' => ScriptTarget::ES2024,
+            _ => {
+                self.error(1499, "Unknown regular expression flag.", start);
+                return;
+            }
+        };
+
+        if previous.contains(flag) {
+            self.error(1500, "Duplicate regular expression flag.", start);
+        } else if (flag == 'u' && previous.contains('v')) || (flag == 'v' && previous.contains('u')) {
+            self.error(
+                1502, "The Unicode (u) flag and the Unicode Sets (v) flag cannot be set simultaneously.",
+                start,
+            );
+        } else if self.target < required_target {
+            let message = format!(
+                "This regular expression flag is only available when targeting '{}' or later.",
+                required_target.to_string().to_lowercase()
+            );
+            self.error(1501, &message, start);
+        }
+    }
+
+    /// Rescan the most recent `>` token as the longest operator starting with it:
+    /// `>=`, `>>`, `>>=`, `>>>` or `>>>=`.
+    ///
+    /// Returns `None` if the most recent token was not a `>`.
+    pub fn rescan_greater_token(&mut self) -> Option<Token> {
+        if self.token_kind != TokenKind::Greater {
+            return None;
+        }
+        let (start, leading_trivia) = self.rewind_token();
+        let rest = &self.source[start.offset..];
+        let (kind, len) = if rest.starts_with(">>>=") {
+            (TokenKind::GreaterGreaterGreaterEqual, 4)
+        } else if rest.starts_with(">>>") {
+            (TokenKind::GreaterGreaterGreater, 3)
+        } else if rest.starts_with(">>=") {
+            (TokenKind::GreaterGreaterEqual, 3)
+        } else if rest.starts_with(">>") {
+            (TokenKind::GreaterGreater, 2)
+        } else if rest.starts_with(">=") {
+            (TokenKind::GreaterEqual, 2)
+        } else {
+            (TokenKind::Greater, 1)
+        };
+        for _ in 0..len {
+            self.advance();
+        }
+        Some(self.finish_token(kind, start, leading_trivia, self.token_preceded_by_newline))
+    }
+
+    /// Rescan the most recent `}` token as the continuation of a template literal.
+    ///
+    /// The scanner resumes templates on its own when it has seen the matching `${`;
+    /// this lets the parser force a continuation when it drives substitutions itself.
+    pub fn rescan_template_continuation(&mut self) -> Option<Token> {
+        if !self.source[self.token_start.offset..].starts_with('}') {
+            return None;
+        }
+        let (start, leading_trivia) = self.rewind_token();
+        self.advance();
+
+        let kind = self.scan_template(false, start);
+        Some(self.finish_token(kind, start, leading_trivia, self.token_preceded_by_newline))
+    }
+
+    /// Rescan the most recent template token, reporting its invalid escape sequences.
+    ///
+    /// Templates are first scanned without these errors because a tagged template
+    /// may contain any escape; the parser rescans the templates that are not tagged.
+    pub fn rescan_template_token(&mut self) -> Option<Token> {
+        let is_start = match self.token_kind {
+            TokenKind::NoSubstitutionTemplate { .. } | TokenKind::TemplateHead { .. } => true,
+            TokenKind::TemplateMiddle { .. } | TokenKind::TemplateTail { .. } => false,
+            _ => return None,
+        };
+        if matches!(self.token_kind, TokenKind::TemplateHead { .. } | TokenKind::TemplateMiddle { .. }) {
+            // Scanning the body again reopens the substitution
+            self.template_depths.pop();
+        }
+        let (start, leading_trivia) = self.rewind_token();
+        self.advance();
+
+        self.report_template_escapes = true;
+        let kind = self.scan_template(is_start, start);
+        self.report_template_escapes = false;
+        Some(self.finish_token(kind, start, leading_trivia, self.token_preceded_by_newline))
+    }
+
+    /// Scan the body of a template literal after its opening `` ` `` or resuming `}`.
+    ///
+    /// Returns a head or middle token when the body stops at a `${` substitution,
+    /// and a tail or no-substitution token when it reaches the closing backtick.
+    fn scan_template(&mut self, is_start: bool, start: Position) -> TokenKind {
+        let source = self.source;
+        let raw_start = self.position;
+        let mut cooked = std::mem::take(&mut self.buffer);
+        cooked.clear();
+        let mut valid = true;
+
+        let (opens_substitution, raw_end) = loop {
+            match self.current_char() {
+                None => {
+                    self.error(1160, "Unterminated template literal.", start);
+                    break (false, self.position);
+                }
+                Some('`') => {
+                    let raw_end = self.position;
+                    self.advance();
+                    break (false, raw_end);
+                }
+                Some('$') if self.peek_char(1) == Some('{') => {
+                    let raw_end = self.position;
+                    self.advance();
+                    self.advance();
+                    self.template_depths.push(0);
+                    break (true, raw_end);
+                }
+                Some('\\') => {
+                    // An invalid escape only leaves the cooked value undefined until
+                    // the parser knows whether the template is tagged
+                    let diagnostic_count = self.diagnostics.len();
+                    valid &= self.scan_escape_sequence(&mut cooked, true);
+                    if !self.report_template_escapes {
+                        self.diagnostics.truncate(diagnostic_count);
+                    }
+                }
+                // Line terminators in template values are normalized to `\n`
+                Some('\r') => {
+                    self.advance();
+                    if self.current_char() == Some('\n') {
                         self.advance();
-                    } else {
-                        break;
                     }
+                    cooked.push('\n');
+                }
+                Some(ch) => {
+                    self.advance();
+                    cooked.push(ch);
                 }
+            }
+        };
+        let raw = &source[raw_start..raw_end];
+        let raw = if raw.contains('\r') {
+            intern(&raw.replace("\r\n", "\n").replace('\r', "\n"))
+        } else {
+            intern(raw)
+        };
+        let cooked_symbol = valid.then(|| intern(&cooked));
+        self.buffer = cooked;
+        let cooked = cooked_symbol;
+        match (is_start, opens_substitution) {
+            (true, false) => TokenKind::NoSubstitutionTemplate { cooked, raw },
+            (true, true) => TokenKind::TemplateHead { cooked, raw },
+            (false, true) => TokenKind::TemplateMiddle { cooked, raw },
+            (false, false) => TokenKind::TemplateTail { cooked, raw },
+        }
+    }
+
+    /// Scan the next token as JSX child content, after the `>` of an opening tag or
+    /// the `}` of an expression container. Produces `JsxText`, `<`, `</` or `{`.
+    ///
+    /// Children start right where the previous token ends, so whitespace already
+    /// taken as its trailing trivia is scanned again as part of the text.
+    pub fn scan_jsx_token(&mut self) -> Option<Token> {
+        self.reset_to(self.token_end);
+        self.pending_newline = false;
 
-                let number = value.parse::<f64>().unwrap_or(0.0);
-                Some(Token {
-                    kind: TokenKind::Number(number),
-                    span: self.make_span(start_line, start_column),
-                })
+        let start = self.current_position();
+        self.token_start = start;
+        self.token_leading_trivia.clear();
+        self.token_preceded_by_newline = false;
+
+        let kind = match self.current_char() {
+            Some('<') if self.peek_char(1) == Some('/') => {
+                self.advance();
+                self.advance();
+                TokenKind::LessSlash
             }
-            // Default case - skip unknown characters
-            _ => {
+            Some('<') => {
                 self.advance();
-                self.next_token()
+                TokenKind::Less
             }
+            Some('{') => self.scan_punctuation('{'),
+            Some(_) => self.scan_jsx_text(),
+            None => self.scan_token_kind(start)?,
+        };
+        Some(self.finish_token(kind, start, Vec::new(), false))
+    }
+
+    /// Scan JSX text up to the next `{` or `<`, decoding HTML entities in its value
+    fn scan_jsx_text(&mut self) -> TokenKind {
+        let source = self.source;
+        let text_start = self.position;
+        while let Some(ch) = self.current_char() {
+            let at = self.current_position();
+            match ch {
+                '{' | '<' => break,
+                '>' => {
+                    self.advance();
+                    self.error(1382, "Unexpected token. Did you mean `{'>'}` or `&gt;`?", at);
+                }
+                '}' => {
+                    self.advance();
+                    self.error(1381, "Unexpected token. Did you mean `{'}'}` or `&rbrace;`?", at);
+                }
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+
+        let raw = &source[text_start..self.position];
+        let mut value = std::mem::take(&mut self.buffer);
+        value.clear();
+        decode_entities(raw, &mut value);
+        let kind = TokenKind::JsxText {
+            value: intern(&value),
+            raw: intern(raw),
+        };
+        self.buffer = value;
+        kind
+    }
+
+    /// Rescan the most recent identifier or keyword token as a JSX tag or attribute
+    /// name, which may contain `-` and a `namespace:` prefix.
+    ///
+    /// Returns `None` if the most recent token was not an identifier or keyword.
+    pub fn rescan_jsx_identifier(&mut self) -> Option<Token> {
+        if !self.source[self.token_start.offset..].starts_with(is_identifier_start) {
+            return None;
+        }
+        let (start, leading_trivia) = self.rewind_token();
+
+        let name = self.scan_jsx_name();
+        let kind = if self.current_char() == Some(':') && self.peek_char(1).is_some_and(is_identifier_start) {
+            self.advance();
+            TokenKind::JsxNamespacedName {
+                namespace: intern(name),
+                name: intern(self.scan_jsx_name()),
+            }
+        } else {
+            TokenKind::JsxIdentifier(intern(name))
+        };
+        Some(self.finish_token(kind, start, leading_trivia, self.token_preceded_by_newline))
+    }
+
+    /// Scan a run of identifier characters and dashes
+    fn scan_jsx_name(&mut self) -> &'a str {
+        let source = self.source;
+        let start = self.position;
+        while self.current_char().is_some_and(|ch| ch == '-' || is_identifier_part(ch)) {
+            self.advance();
+        }
+        &source[start..self.position]
+    }
+
+    /// Scan the next token as a JSX attribute value. A quoted value is taken literally,
+    /// without escape sequences, and may span lines; anything else, such as the `{`
+    /// of an expression container, is scanned as usual.
+    pub fn scan_jsx_attribute_value(&mut self) -> Option<Token> {
+        let (start, leading_trivia, preceded_by_newline) = self.start_token();
+        let kind = match self.current_char() {
+            Some(quote @ ('"' | '\'')) => self.scan_jsx_string(quote, start),
+            _ => self.scan_token_kind(start)?,
+        };
+        Some(self.finish_token(kind, start, leading_trivia, preceded_by_newline))
+    }
+
+    /// Scan a quoted JSX attribute string, decoding HTML entities in its value
+    fn scan_jsx_string(&mut self, quote: char, start: Position) -> TokenKind {
+        let source = self.source;
+        self.advance();
+        let value_start = self.position;
+        while self.current_char().is_some_and(|ch| ch != quote) {
+            self.advance();
+        }
+        let raw = &source[value_start..self.position];
+        if self.advance().is_none() {
+            self.error(1002, "Unterminated string literal.", start);
+        }
+
+        let mut value = std::mem::take(&mut self.buffer);
+        value.clear();
+        decode_entities(raw, &mut value);
+        let kind = TokenKind::String(intern(&value));
+        self.buffer = value;
+        kind
+    }
+
+    /// Scan an escape sequence starting at a backslash, appending the decoded text to `out`.
+    ///
+    /// Legacy octal escapes are rejected in strict mode and inside templates. Returns
+    /// `false` if the escape was malformed.
+    fn scan_escape_sequence(&mut self, out: &mut String, in_template: bool) -> bool {
+        let diagnostic_count = self.diagnostics.len();
+        let start = self.current_position();
+        self.advance();
+
+        let Some(ch) = self.advance() else {
+            self.error(1126, "Unexpected end of text.", start);
+            return false;
+        };
+
+        match ch {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            'b' => out.push('\u{8}'),
+            'f' => out.push('\u{c}'),
+            'v' => out.push('\u{b}'),
+            '0' if !matches!(self.current_char(), Some('0'..='9')) => out.push('\0'),
+            '0'..='7' => {
+                // Legacy octal escape: up to three digits with a value no greater than 0o377
+                let mut value = ch.to_digit(8).unwrap_or(0);
+                let max_len = if ch <= '3' { 3 } else { 2 };
+                let mut len = 1;
+                while len < max_len {
+                    match self.current_char().and_then(|c| c.to_digit(8)) {
+                        Some(digit) => {
+                            value = value * 8 + digit;
+                            self.advance();
+                            len += 1;
+                        }
+                        None => break,
+                    }
+                }
+                if self.strict_mode || in_template {
+                    let message = format!(
+                        "Octal escape sequences are not allowed. Use the syntax '\\x{:02x}'.",
+                        value
+                    );
+                    self.error(1487, &message, start);
+                }
+                out.push(char::from_u32(value).unwrap_or('\u{FFFD}'));
+            }
+            '8' | '9' => {
+                if self.strict_mode || in_template {
+                    let message = format!("Escape sequence '\\{}' is not allowed.", ch);
+                    self.error(1488, &message, start);
+                }
+                out.push(ch);
+            }
+            'x' => match self.scan_hex_digits(2, 2) {
+                Some(value) => out.push(char::from_u32(value).unwrap_or('\u{FFFD}')),
+                None => self.error(1125, "Hexadecimal digit expected.", start),
+            },
+            'u' => {
+                if let Some(value) = self.scan_unicode_escape_value(start) {
+                    self.push_code_unit(out, value);
+                }
+            }
+            // Line continuations contribute nothing to the value
+            '\r' => {
+                if self.current_char() == Some('\n') {
+                    self.advance();
+                }
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => {}
+            other => out.push(other),
+        }
+
+        self.diagnostics.len() == diagnostic_count
+    }
+
+    /// Scan the part of a `\u` escape after the `u`, in either `XXXX` or `{X...}` form
+    fn scan_unicode_escape_value(&mut self, start: Position) -> Option<u32> {
+        if self.current_char() == Some('{') {
+            self.advance();
+            let value = self.scan_hex_digits(1, usize::MAX);
+            match value {
+                None => self.error(1125, "Hexadecimal digit expected.", start),
+                Some(value) if value > 0x10FFFF => {
+                    if self.current_char() == Some('}') {
+                        self.advance();
+                    }
+                    self.error(
+                        1198, "An extended Unicode escape value must be between 0x0 and 0x10FFFF inclusive.",
+                        start,
+                    );
+                }
+                Some(_) if self.current_char() != Some('}') => {
+                    self.error(1199, "Unterminated Unicode escape sequence.", start)
+                }
+                Some(value) => {
+                    self.advance();
+                    return Some(value);
+                }
+            }
+            None
+        } else {
+            let value = self.scan_hex_digits(4, 4);
+            if value.is_none() {
+                self.error(1125, "Hexadecimal digit expected.", start);
+            }
+            value
+        }
+    }
+
+    /// Scan between `min` and `max` hex digits, returning `None` if fewer than `min` are present
+    fn scan_hex_digits(&mut self, min: usize, max: usize) -> Option<u32> {
+        let mut value: u32 = 0;
+        let mut count = 0;
+        while count < max {
+            match self.current_char().and_then(|c| c.to_digit(16)) {
+                Some(digit) => {
+                    value = value.saturating_mul(16).saturating_add(digit);
+                    self.advance();
+                    count += 1;
+                }
+                None => break,
+            }
+        }
+        (count >= min).then_some(value)
+    }
+
+    /// Append a code point or UTF-16 code unit, pairing surrogates where possible
+    fn push_code_unit(&mut self, out: &mut String, value: u32) {
+        if (0xD800..0xDC00).contains(&value) {
+            // A high surrogate followed by an escaped low surrogate forms one code point
+            if self.current_char() == Some('\\') && self.peek_char(1) == Some('u') {
+                let saved = (self.position, self.line, self.column);
+                self.advance();
+                self.advance();
+                if let Some(low @ 0xDC00..=0xDFFF) = self.scan_hex_digits(4, 4) {
+                    let combined = 0x10000 + ((value - 0xD800) << 10) + (low - 0xDC00);
+                    out.push(char::from_u32(combined).unwrap_or('\u{FFFD}'));
+                    return;
+                }
+                (self.position, self.line, self.column) = saved;
+            }
+        }
+        out.push(char::from_u32(value).unwrap_or('\u{FFFD}'));
+    }
+
+    /// Scan an operator or punctuator starting with `ch`, taking the longest match
+    fn scan_punctuation(&mut self, ch: char) -> TokenKind {
+        let next = self.peek_char(1);
+        let after = self.peek_char(2);
+        let (kind, len) = match ch {
+            '{' => {
+                if let Some(depth) = self.template_depths.last_mut() {
+                    *depth += 1;
+                }
+                (TokenKind::LeftBrace, 1)
+            }
+            '}' => {
+                if let Some(depth) = self.template_depths.last_mut() {
+                    *depth = depth.saturating_sub(1);
+                }
+                (TokenKind::RightBrace, 1)
+            }
+            '(' => (TokenKind::LeftParen, 1),
+            ')' => (TokenKind::RightParen, 1),
+            '[' => (TokenKind::LeftBracket, 1),
+            ']' => (TokenKind::RightBracket, 1),
+            ';' => (TokenKind::Semicolon, 1),
+            ',' => (TokenKind::Comma, 1),
+            ':' => (TokenKind::Colon, 1),
+            '~' => (TokenKind::Tilde, 1),
+            '@' => (TokenKind::At, 1),
+            '#' => (TokenKind::Hash, 1),
+            '.' => match (next, after) {
+                (Some('.'), Some('.')) => (TokenKind::DotDotDot, 3),
+                _ => (TokenKind::Dot, 1),
+            },
+            '+' => match next {
+                Some('+') => (TokenKind::PlusPlus, 2),
+                Some('=') => (TokenKind::PlusEqual, 2),
+                _ => (TokenKind::Plus, 1),
+            },
+            '-' => match next {
+                Some('-') => (TokenKind::MinusMinus, 2),
+                Some('=') => (TokenKind::MinusEqual, 2),
+                _ => (TokenKind::Minus, 1),
+            },
+            '*' => match (next, after) {
+                (Some('*'), Some('=')) => (TokenKind::StarStarEqual, 3),
+                (Some('*'), _) => (TokenKind::StarStar, 2),
+                (Some('='), _) => (TokenKind::StarEqual, 2),
+                _ => (TokenKind::Star, 1),
+            },
+            '/' => match next {
+                Some('=') => (TokenKind::SlashEqual, 2),
+                _ => (TokenKind::Slash, 1),
+            },
+            '%' => match next {
+                Some('=') => (TokenKind::PercentEqual, 2),
+                _ => (TokenKind::Percent, 1),
+            },
+            '=' => match (next, after) {
+                (Some('='), Some('=')) => (TokenKind::EqualEqualEqual, 3),
+                (Some('='), _) => (TokenKind::EqualEqual, 2),
+                (Some('>'), _) => (TokenKind::Arrow, 2),
+                _ => (TokenKind::Equal, 1),
+            },
+            '!' => match (next, after) {
+                (Some('='), Some('=')) => (TokenKind::BangEqualEqual, 3),
+                (Some('='), _) => (TokenKind::BangEqual, 2),
+                _ => (TokenKind::Bang, 1),
+            },
+            '<' => match (next, after) {
+                (Some('<'), Some('=')) => (TokenKind::LessLessEqual, 3),
+                (Some('<'), _) => (TokenKind::LessLess, 2),
+                (Some('='), _) => (TokenKind::LessEqual, 2),
+                _ => (TokenKind::Less, 1),
+            },
+            '>' if self.split_greater => (TokenKind::Greater, 1),
+            '>' => match (next, after, self.peek_char(3)) {
+                (Some('>'), Some('>'), Some('=')) => (TokenKind::GreaterGreaterGreaterEqual, 4),
+                (Some('>'), Some('>'), _) => (TokenKind::GreaterGreaterGreater, 3),
+                (Some('>'), Some('='), _) => (TokenKind::GreaterGreaterEqual, 3),
+                (Some('>'), _, _) => (TokenKind::GreaterGreater, 2),
+                (Some('='), _, _) => (TokenKind::GreaterEqual, 2),
+                _ => (TokenKind::Greater, 1),
+            },
+            '&' => match (next, after) {
+                (Some('&'), Some('=')) => (TokenKind::AmpersandAmpersandEqual, 3),
+                (Some('&'), _) => (TokenKind::AmpersandAmpersand, 2),
+                (Some('='), _) => (TokenKind::AmpersandEqual, 2),
+                _ => (TokenKind::Ampersand, 1),
+            },
+            '|' => match (next, after) {
+                (Some('|'), Some('=')) => (TokenKind::PipePipeEqual, 3),
+                (Some('|'), _) => (TokenKind::PipePipe, 2),
+                (Some('='), _) => (TokenKind::PipeEqual, 2),
+                _ => (TokenKind::Pipe, 1),
+            },
+            '^' => match next {
+                Some('=') => (TokenKind::CaretEqual, 2),
+                _ => (TokenKind::Caret, 1),
+            },
+            '?' => match (next, after) {
+                (Some('?'), Some('=')) => (TokenKind::QuestionQuestionEqual, 3),
+                (Some('?'), _) => (TokenKind::QuestionQuestion, 2),
+                // `a?.5:b` is a conditional with a numeric operand, not optional chaining
+                (Some('.'), Some(digit)) if digit.is_ascii_digit() => (TokenKind::Question, 1),
+                (Some('.'), _) => (TokenKind::QuestionDot, 2),
+                _ => (TokenKind::Question, 1),
+            },
+            _ => unreachable!("is_punctuation_start admitted {:?}", ch),
+        };
+
+        for _ in 0..len {
+            self.advance();
         }
+        kind
     }
-}
\ No newline at end of file
+}
+
+/// Check whether a character can start an identifier: `$`, `_` or a Unicode ID_Start character
+fn is_identifier_start(ch: char) -> bool {
+    ch == '$' || ch == '_' || unicode_id_start::is_id_start(ch)
+}
+
+/// Check whether a character can continue an identifier: `$`, `_`, ZWNJ, ZWJ or a
+/// Unicode ID_Continue character
+fn is_identifier_part(ch: char) -> bool {
+    ch == '$' || ch == '\u{200c}' || ch == '\u{200d}' || unicode_id_start::is_id_continue(ch)
+}
+
+/// Check whether a character is an ECMAScript line terminator
+fn is_line_break(ch: char) -> bool {
+    matches!(ch, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+/// Check whether a character is ECMAScript whitespace other than a line terminator
+fn is_whitespace(ch: char) -> bool {
+    matches!(
+        ch,
+        ' ' | '\t' | '\u{b}' | '\u{c}' | '\u{a0}' | '\u{feff}' | '\u{1680}' | '\u{2000}'..='\u{200a}'
+            | '\u{202f}' | '\u{205f}' | '\u{3000}'
+    )
+}
+
+/// Check whether a character can begin an operator or punctuator
+fn is_punctuation_start(ch: char) -> bool {
+    matches!(
+        ch,
+        '{' | '}' | '(' | ')' | '[' | ']' | ';' | ',' | ':' | '~' | '@' | '#' | '.'
+            | '+' | '-' | '*' | '/' | '%' | '=' | '!' | '<' | '>' | '&' | '|' | '^' | '?'
+    )
+}
+
+/// Parse decimal numeric text, ignoring `_` separators
+fn parse_decimal(text: &str) -> f64 {
+    let value = if text.contains('_') {
+        text.replace('_', "").parse::<f64>()
+    } else {
+        text.parse::<f64>()
+    };
+    value.unwrap_or(f64::NAN)
+}
+
+/// Intern numeric text with its `_` separators removed
+fn intern_without_separators(text: &str) -> Symbol {
+    if text.contains('_') {
+        intern(&text.replace('_', ""))
+    } else {
+        intern(text)
+    }
+}
+
+/// Compute the value of a digit string in the given radix, saturating to infinity
+fn parse_radix(digits: &str, radix: u32) -> f64 {
+    digits
+        .chars()
+        .filter_map(|c| c.to_digit(radix))
+        .fold(0.0, |value, digit| value * f64::from(radix) + f64::from(digit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::DiagnosticKind;
+    use crate::lexer::{ContextualKeyword, Lexer};
+    use crate::utils::intern::resolve;
+
+    /// Scan `source` to the end, returning the token kinds without the final `Eof`
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        let mut scanner = Scanner::new(source);
+        let mut kinds = Vec::new();
+        while let Some(token) = scanner.next_token() {
+            if token.kind != TokenKind::Eof {
+                kinds.push(token.kind);
+            }
+        }
+        kinds
+    }
+
+    /// Scan the rest of the source, leaving its diagnostics in `scanner`
+    fn scan_to_end(scanner: &mut Scanner) {
+        while scanner.next_token().is_some() {}
+    }
+
+    /// The code and start offset of each diagnostic reported so far
+    fn codes(scanner: &Scanner) -> Vec<(Option<u32>, usize)> {
+        scanner.diagnostics().iter().map(|d| (d.code, d.span.start.offset)).collect()
+    }
+
+    const OPERATORS: &[(&str, TokenKind)] = &[
+        ("{", TokenKind::LeftBrace),
+        ("}", TokenKind::RightBrace),
+        ("(", TokenKind::LeftParen),
+        (")", TokenKind::RightParen),
+        ("[", TokenKind::LeftBracket),
+        ("]", TokenKind::RightBracket),
+        (";", TokenKind::Semicolon),
+        (",", TokenKind::Comma),
+        (":", TokenKind::Colon),
+        ("~", TokenKind::Tilde),
+        ("@", TokenKind::At),
+        ("#", TokenKind::Hash),
+        (".", TokenKind::Dot),
+        ("...", TokenKind::DotDotDot),
+        ("+", TokenKind::Plus),
+        ("++", TokenKind::PlusPlus),
+        ("+=", TokenKind::PlusEqual),
+        ("-", TokenKind::Minus),
+        ("--", TokenKind::MinusMinus),
+        ("-=", TokenKind::MinusEqual),
+        ("*", TokenKind::Star),
+        ("**", TokenKind::StarStar),
+        ("*=", TokenKind::StarEqual),
+        ("**=", TokenKind::StarStarEqual),
+        ("/", TokenKind::Slash),
+        ("/=", TokenKind::SlashEqual),
+        ("%", TokenKind::Percent),
+        ("%=", TokenKind::PercentEqual),
+        ("=", TokenKind::Equal),
+        ("==", TokenKind::EqualEqual),
+        ("===", TokenKind::EqualEqualEqual),
+        ("=>", TokenKind::Arrow),
+        ("!", TokenKind::Bang),
+        ("!=", TokenKind::BangEqual),
+        ("!==", TokenKind::BangEqualEqual),
+        ("<", TokenKind::Less),
+        ("<=", TokenKind::LessEqual),
+        ("<<", TokenKind::LessLess),
+        ("<<=", TokenKind::LessLessEqual),
+        (">", TokenKind::Greater),
+        (">=", TokenKind::GreaterEqual),
+        (">>", TokenKind::GreaterGreater),
+        (">>=", TokenKind::GreaterGreaterEqual),
+        (">>>", TokenKind::GreaterGreaterGreater),
+        (">>>=", TokenKind::GreaterGreaterGreaterEqual),
+        ("&", TokenKind::Ampersand),
+        ("&&", TokenKind::AmpersandAmpersand),
+        ("&=", TokenKind::AmpersandEqual),
+        ("&&=", TokenKind::AmpersandAmpersandEqual),
+        ("|", TokenKind::Pipe),
+        ("||", TokenKind::PipePipe),
+        ("|=", TokenKind::PipeEqual),
+        ("||=", TokenKind::PipePipeEqual),
+        ("^", TokenKind::Caret),
+        ("^=", TokenKind::CaretEqual),
+        ("?", TokenKind::Question),
+        ("??", TokenKind::QuestionQuestion),
+        ("??=", TokenKind::QuestionQuestionEqual),
+        ("?.", TokenKind::QuestionDot),
+    ];
+
+    #[test]
+    fn scans_each_operator_as_one_token() {
+        for &(text, kind) in OPERATORS {
+            assert_eq!(kinds(text), vec![kind], "scanning {:?}", text);
+            assert_eq!(kind.text(), Some(text));
+        }
+    }
+
+    #[test]
+    fn reserved_words_scan_as_keywords_with_their_text() {
+        let words = "break case catch class const continue debugger default delete do else enum export \
+                     extends false finally for function if import in instanceof new null return super \
+                     switch this throw true try typeof var void while with implements interface let \
+                     package private protected public static yield";
+        for word in words.split_whitespace() {
+            let kind = kinds(word)[0];
+            assert!(!matches!(kind, TokenKind::Identifier(_)), "scanning {:?}", word);
+            assert_eq!(kind.text(), Some(word));
+        }
+        assert_eq!(TokenKind::Identifier(intern("of")).text(), None);
+    }
+
+    #[test]
+    fn operator_spans_cover_the_whole_operator() {
+        for &(text, _) in OPERATORS {
+            let source = format!("a {} b", text);
+            let mut scanner = Scanner::new(&source);
+            scanner.next_token();
+            let token = scanner.next_token().unwrap();
+            assert_eq!(token.span.start.offset, 2, "scanning {:?}", text);
+            assert_eq!(token.span.end.offset, 2 + text.len(), "scanning {:?}", text);
+        }
+    }
+
+    #[test]
+    fn takes_the_longest_match_then_continues() {
+        assert_eq!(kinds("...."), vec![TokenKind::DotDotDot, TokenKind::Dot]);
+        assert_eq!(kinds(".."), vec![TokenKind::Dot, TokenKind::Dot]);
+        assert_eq!(kinds("****"), vec![TokenKind::StarStar, TokenKind::StarStar]);
+        assert_eq!(kinds("+++"), vec![TokenKind::PlusPlus, TokenKind::Plus]);
+        assert_eq!(kinds("===="), vec![TokenKind::EqualEqualEqual, TokenKind::Equal]);
+        assert_eq!(kinds("!==="), vec![TokenKind::BangEqualEqual, TokenKind::Equal]);
+        assert_eq!(kinds("<<<"), vec![TokenKind::LessLess, TokenKind::Less]);
+        assert_eq!(kinds(">>>>="), vec![TokenKind::GreaterGreaterGreater, TokenKind::GreaterEqual]);
+        assert_eq!(kinds("&&&"), vec![TokenKind::AmpersandAmpersand, TokenKind::Ampersand]);
+        assert_eq!(kinds("|||="), vec![TokenKind::PipePipe, TokenKind::PipeEqual]);
+        assert_eq!(kinds("???"), vec![TokenKind::QuestionQuestion, TokenKind::Question]);
+        assert_eq!(kinds("=>="), vec![TokenKind::Arrow, TokenKind::Equal]);
+    }
+
+    #[test]
+    fn question_dot_before_a_digit_is_a_conditional() {
+        assert_eq!(kinds("?.5"), vec![TokenKind::Question, TokenKind::Number(0.5)]);
+        let optional_chain = kinds("a?.b");
+        assert_eq!(optional_chain.len(), 3);
+        assert_eq!(optional_chain[1], TokenKind::QuestionDot);
+        assert_eq!(kinds("?.["), vec![TokenKind::QuestionDot, TokenKind::LeftBracket]);
+    }
+
+    #[test]
+    fn split_greater_scans_greater_alone_until_rescanned() {
+        let mut scanner = Scanner::new(">>>= >");
+        scanner.set_split_greater(true);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Greater);
+        let token = scanner.rescan_greater_token().unwrap();
+        assert_eq!(token.kind, TokenKind::GreaterGreaterGreaterEqual);
+        assert_eq!(token.span.end.offset, 4);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Greater);
+        assert_eq!(scanner.rescan_greater_token().unwrap().kind, TokenKind::Greater);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Eof);
+    }
+
+    /// Decoded value and raw text of a `JsxText` token
+    fn jsx_text(token: &Token) -> (String, String) {
+        match token.kind {
+            TokenKind::JsxText { value, raw } => (resolve(value).unwrap(), resolve(raw).unwrap()),
+            kind => panic!("expected JSX text, found {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn jsx_children_scan_as_text_up_to_a_tag_or_brace() {
+        let source = "<a> x &amp; &#65;&#x42; &nbsp;{y}</a>";
+        let mut scanner = Scanner::new(source);
+        scanner.next_token(); // <
+        scanner.next_token(); // a
+        scanner.next_token(); // >
+        let text = scanner.scan_jsx_token().unwrap();
+        // The space after `>` belongs to the text, not to trivia
+        assert_eq!(text.span.start.offset, 3);
+        assert_eq!(jsx_text(&text), (" x & AB \u{a0}".to_string(), " x &amp; &#65;&#x42; &nbsp;".to_string()));
+        assert_eq!(scanner.scan_jsx_token().unwrap().kind, TokenKind::LeftBrace);
+        scanner.next_token(); // y
+        scanner.next_token(); // }
+        let closing = scanner.scan_jsx_token().unwrap();
+        assert_eq!(closing.kind, TokenKind::LessSlash);
+        assert_eq!(closing.span.end.offset, source.len() - 2);
+        assert!(scanner.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn jsx_text_reports_greater_and_right_brace() {
+        let mut scanner = Scanner::new("<a>1 > 0 }</a>");
+        for _ in 0..3 {
+            scanner.next_token();
+        }
+        let text = scanner.scan_jsx_token().unwrap();
+        assert_eq!(jsx_text(&text).0, "1 > 0 }");
+        let codes: Vec<_> = scanner.diagnostics().iter().map(|d| (d.code, d.span.start.offset)).collect();
+        assert_eq!(codes, [(Some(1382), 5), (Some(1381), 9)]);
+        assert_eq!(scanner.scan_jsx_token().unwrap().kind, TokenKind::LessSlash);
+    }
+
+    #[test]
+    fn jsx_identifiers_take_dashes_and_a_namespace() {
+        let mut scanner = Scanner::new("data-foo-bar xlink:href class");
+        scanner.next_token();
+        let token = scanner.rescan_jsx_identifier().unwrap();
+        assert_eq!(token.kind, TokenKind::JsxIdentifier(intern("data-foo-bar")));
+        assert_eq!(token.span.end.offset, 12);
+        scanner.next_token();
+        let token = scanner.rescan_jsx_identifier().unwrap();
+        assert_eq!(
+            token.kind,
+            TokenKind::JsxNamespacedName {
+                namespace: intern("xlink"),
+                name: intern("href")
+            }
+        );
+        assert_eq!((token.span.start.offset, token.span.end.offset), (13, 23));
+        // Keywords are valid JSX names too
+        scanner.next_token();
+        assert_eq!(scanner.rescan_jsx_identifier().unwrap().kind, TokenKind::JsxIdentifier(intern("class")));
+
+        let mut scanner = Scanner::new("{");
+        scanner.next_token();
+        assert!(scanner.rescan_jsx_identifier().is_none());
+    }
+
+    #[test]
+    fn jsx_attribute_strings_decode_entities_but_not_escapes() {
+        let mut scanner = Scanner::new("a='\\n &quot;\nx' b={c}");
+        scanner.next_token(); // a
+        scanner.next_token(); // =
+        let value = scanner.scan_jsx_attribute_value().unwrap();
+        assert_eq!(value.kind, TokenKind::String(intern("\\n \"\nx")));
+        assert_eq!(value.span.end.offset, 15);
+        scanner.next_token(); // b
+        scanner.next_token(); // =
+        assert_eq!(scanner.scan_jsx_attribute_value().unwrap().kind, TokenKind::LeftBrace);
+        assert!(scanner.diagnostics().is_empty());
+
+        let mut scanner = Scanner::new("a=\"open");
+        scanner.next_token();
+        scanner.next_token();
+        scanner.scan_jsx_attribute_value();
+        assert_eq!(scanner.diagnostics()[0].code, Some(1002));
+    }
+
+    #[test]
+    fn invalid_template_escapes_are_reported_only_on_rescan() {
+        let mut scanner = Scanner::new("`a\\u{` + `${x}\\xg`");
+        let token = scanner.next_token().unwrap();
+        assert_eq!(
+            token.kind,
+            TokenKind::NoSubstitutionTemplate {
+                cooked: None,
+                raw: intern("a\\u{")
+            }
+        );
+        assert!(scanner.diagnostics().is_empty());
+        let rescanned = scanner.rescan_template_token().unwrap();
+        assert_eq!((rescanned.kind, rescanned.span), (token.kind, token.span));
+        assert_eq!(scanner.diagnostics().len(), 1);
+
+        scanner.next_token(); // +
+        scanner.next_token(); // `${
+        scanner.next_token(); // x
+        let tail = scanner.next_token().unwrap();
+        assert!(matches!(tail.kind, TokenKind::TemplateTail { cooked: None, .. }));
+        assert_eq!(scanner.diagnostics().len(), 1);
+        scanner.rescan_template_token();
+        let codes: Vec<_> = scanner.diagnostics().iter().map(|d| (d.code, d.span.start.offset)).collect();
+        assert_eq!(codes[1], (Some(1125), 14));
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Eof);
+
+        // Rescanning a head reopens its substitution only once
+        let mut scanner = Scanner::new("`\\u${a}`");
+        scanner.next_token();
+        scanner.rescan_template_token();
+        scanner.next_token(); // a
+        assert!(matches!(scanner.next_token().unwrap().kind, TokenKind::TemplateTail { .. }));
+        assert!(scanner.rescan_template_token().is_some());
+
+        let mut scanner = Scanner::new("a");
+        scanner.next_token();
+        assert!(scanner.rescan_template_token().is_none());
+    }
+
+    #[test]
+    fn string_escapes_are_decoded() {
+        let source = r#"'a\n\t\x41B\u{1F600}\u{43}' "q\"\'\\" 'line\
+next' '\0'"#;
+        assert_eq!(
+            kinds(source),
+            vec![
+                TokenKind::String(intern("a\n\tAB\u{1F600}C")),
+                TokenKind::String(intern("q\"'\\")),
+                TokenKind::String(intern("linenext")),
+                TokenKind::String(intern("\0")),
+            ]
+        );
+        assert_eq!(kinds(r#"'\c\''"#), vec![TokenKind::String(intern("c'"))]);
+    }
+
+    #[test]
+    fn octal_escapes_are_reported_only_in_strict_mode() {
+        let mut scanner = Scanner::new(r"'\101\08'");
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::String(intern("A\08")));
+        assert!(scanner.diagnostics().is_empty());
+
+        let mut scanner = Scanner::new(r"x = '\101 \8'");
+        scanner.set_strict_mode(true);
+        scan_to_end(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1487), 5), (Some(1488), 10)]);
+    }
+
+    #[test]
+    fn malformed_escapes_are_reported() {
+        let mut scanner = Scanner::new(r"'\xG' '\u12' '\u{110000}' '\u{41'");
+        scan_to_end(&mut scanner);
+        let codes: Vec<_> = codes(&scanner).into_iter().map(|(code, _)| code).collect();
+        assert_eq!(codes, [Some(1125), Some(1125), Some(1198), Some(1199)]);
+    }
+
+    #[test]
+    fn unterminated_strings_stop_at_the_end_of_the_line() {
+        let source = "let s = 'abc\nx;";
+        assert_eq!(
+            kinds(source),
+            [
+                TokenKind::Let,
+                TokenKind::Identifier(intern("s")),
+                TokenKind::Equal,
+                TokenKind::String(intern("abc")),
+                TokenKind::Identifier(intern("x")),
+                TokenKind::Semicolon,
+            ]
+        );
+        let mut scanner = Scanner::new(source);
+        scan_to_end(&mut scanner);
+        let diagnostic = &scanner.diagnostics()[0];
+        assert_eq!(diagnostic.code, Some(1002));
+        assert_eq!((diagnostic.span.start.offset, diagnostic.span.end.offset), (8, 12));
+
+        let mut scanner = Scanner::new("\"abc");
+        scanner.next_token();
+        assert_eq!(codes(&scanner), [(Some(1002), 0)]);
+    }
+
+    #[test]
+    fn numeric_literals_in_every_radix() {
+        assert_eq!(
+            kinds("0xFF 0o17 0b101 1e3 2.5E-1 .5 5. 1.2.3"),
+            vec![
+                TokenKind::Number(255.0),
+                TokenKind::Number(15.0),
+                TokenKind::Number(5.0),
+                TokenKind::Number(1000.0),
+                TokenKind::Number(0.25),
+                TokenKind::Number(0.5),
+                TokenKind::Number(5.0),
+                TokenKind::Number(1.2),
+                TokenKind::Number(0.3),
+            ]
+        );
+        let mut scanner = Scanner::new("0x 1e+");
+        scan_to_end(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1125), 0), (Some(1124), 4)]);
+    }
+
+    #[test]
+    fn numeric_separators_are_validated() {
+        assert_eq!(kinds("1_000_000 0xFF_FF"), vec![TokenKind::Number(1e6), TokenKind::Number(65535.0)]);
+        let mut scanner = Scanner::new("1__0 2_ 0x_1 3._5");
+        scan_to_end(&mut scanner);
+        assert_eq!(
+            codes(&scanner),
+            [(Some(6189), 2), (Some(6188), 6), (Some(6188), 10), (Some(6188), 15)]
+        );
+    }
+
+    #[test]
+    fn bigint_literals_keep_their_digits() {
+        assert_eq!(
+            kinds("123n 0xFF_FFn 0b1n"),
+            vec![
+                TokenKind::BigInt(intern("123")),
+                TokenKind::BigInt(intern("0xFFFF")),
+                TokenKind::BigInt(intern("0b1")),
+            ]
+        );
+        let mut scanner = Scanner::new("1.5n 1e3n");
+        scan_to_end(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1353), 0), (Some(1352), 5)]);
+    }
+
+    #[test]
+    fn legacy_octal_and_leading_zero_literals_are_reported() {
+        assert_eq!(
+            kinds("017 089 09.5"),
+            vec![TokenKind::Number(15.0), TokenKind::Number(89.0), TokenKind::Number(9.5)]
+        );
+        let mut scanner = Scanner::new("017 089");
+        scan_to_end(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1121), 0), (Some(1489), 4)]);
+        assert_eq!(scanner.diagnostics()[0].message, "Octal literals are not allowed. Use the syntax '0o17'.");
+    }
+
+    #[test]
+    fn identifier_right_after_a_number_is_reported() {
+        let mut scanner = Scanner::new("3in x");
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Number(3.0));
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Identifier(intern("x")));
+        assert_eq!(codes(&scanner), [(Some(1351), 0)]);
+    }
+
+    /// Scan the slash that starts the source as a regular expression literal
+    fn scan_regex(scanner: &mut Scanner) -> TokenKind {
+        scanner.next_token();
+        scanner.rescan_slash_as_regex().unwrap().kind
+    }
+
+    #[test]
+    fn slash_rescans_as_a_regular_expression() {
+        let mut scanner = Scanner::new("/[/]\\/a/gi.x");
+        assert_eq!(
+            scan_regex(&mut scanner),
+            TokenKind::RegularExpression {
+                pattern: intern("[/]\\/a"),
+                flags: intern("gi")
+            }
+        );
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Dot);
+        assert!(scanner.diagnostics().is_empty());
+
+        // `/=` starts a regex too
+        let mut scanner = Scanner::new("/=a/");
+        assert_eq!(
+            scan_regex(&mut scanner),
+            TokenKind::RegularExpression {
+                pattern: intern("=a"),
+                flags: intern("")
+            }
+        );
+
+        let mut scanner = Scanner::new("a");
+        scanner.next_token();
+        assert!(scanner.rescan_slash_as_regex().is_none());
+    }
+
+    #[test]
+    fn regular_expression_flags_are_validated() {
+        let mut scanner = Scanner::new("/a/gxgu");
+        scan_regex(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1499), 4), (Some(1500), 5)]);
+
+        let mut scanner = Scanner::new("/a/uv");
+        scan_regex(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1502), 4)]);
+
+        let mut scanner = Scanner::new("/a/gsd");
+        scanner.set_target(ScriptTarget::ES2018);
+        scan_regex(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1501), 5)]);
+        assert_eq!(
+            scanner.diagnostics()[0].message,
+            "This regular expression flag is only available when targeting 'es2022' or later."
+        );
+    }
+
+    #[test]
+    fn unterminated_regular_expression_stops_at_the_end_of_the_line() {
+        let mut scanner = Scanner::new("/ab[/]\nx");
+        assert_eq!(
+            scan_regex(&mut scanner),
+            TokenKind::RegularExpression {
+                pattern: intern("ab[/]"),
+                flags: intern("")
+            }
+        );
+        assert_eq!(codes(&scanner), [(Some(1161), 0)]);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Identifier(intern("x")));
+    }
+
+    /// Scan `source` with trivia preserved and join the text of every token and its trivia
+    fn round_trip(source: &str) -> String {
+        let mut scanner = Scanner::new(source);
+        scanner.set_preserve_trivia(true);
+        let text = |span: Span| &source[span.start.offset..span.end.offset];
+        let mut output = String::new();
+        while let Some(token) = scanner.next_token() {
+            for trivia in &token.leading_trivia {
+                output.push_str(text(trivia.span));
+            }
+            output.push_str(text(token.span));
+            for trivia in &token.trailing_trivia {
+                output.push_str(text(trivia.span));
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn preserved_trivia_reproduces_the_source() {
+        let sources = [
+            "\u{feff}#!/usr/bin/env node\r\n// @target: es5\r\nlet a = 1; /* x */ // y\r\n",
+            "/** doc */\nfunction f() {\n\treturn `a${ `b${c}` }d`;\u{2028}}\n",
+            "x = 'unterminated\n  y /* open",
+            "",
+        ];
+        for source in sources {
+            assert_eq!(round_trip(source), source);
+        }
+    }
+
+    #[test]
+    fn trivia_is_split_at_the_first_line_break() {
+        let mut scanner = Scanner::new("a /* x */ // y\n  /* z */ b");
+        scanner.set_preserve_trivia(true);
+        let a = scanner.next_token().unwrap();
+        let trailing: Vec<_> = a.trailing_trivia.iter().map(|trivia| trivia.kind).collect();
+        assert_eq!(
+            trailing,
+            [
+                TriviaKind::Whitespace,
+                TriviaKind::MultiLineComment,
+                TriviaKind::Whitespace,
+                TriviaKind::SingleLineComment,
+            ]
+        );
+        assert!(!a.preceded_by_newline);
+        let b = scanner.next_token().unwrap();
+        let leading: Vec<_> = b.leading_trivia.iter().map(|trivia| trivia.kind).collect();
+        assert_eq!(
+            leading,
+            [
+                TriviaKind::Newline,
+                TriviaKind::Whitespace,
+                TriviaKind::MultiLineComment,
+                TriviaKind::Whitespace,
+            ]
+        );
+        assert!(b.preceded_by_newline);
+    }
+
+    #[test]
+    fn newline_is_recorded_without_preserving_trivia() {
+        let mut scanner = Scanner::new("a /*\n*/ b c");
+        assert!(!scanner.next_token().unwrap().preceded_by_newline);
+        let b = scanner.next_token().unwrap();
+        assert!(b.preceded_by_newline);
+        assert!(b.leading_trivia.is_empty());
+        assert!(!scanner.next_token().unwrap().preceded_by_newline);
+    }
+
+    #[test]
+    fn identifiers_follow_the_unicode_id_tables() {
+        assert_eq!(
+            kinds("café π_1 $x _ a\u{200D}b ℘"),
+            vec![
+                TokenKind::Identifier(intern("café")),
+                TokenKind::Identifier(intern("π_1")),
+                TokenKind::Identifier(intern("$x")),
+                TokenKind::Identifier(intern("_")),
+                TokenKind::Identifier(intern("a\u{200D}b")),
+                TokenKind::Identifier(intern("℘")),
+            ]
+        );
+        // A digit or ZWJ cannot start an identifier, and symbols are not identifiers
+        let mut scanner = Scanner::new("\u{200D}a €");
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Unknown);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Identifier(intern("a")));
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Unknown);
+        assert_eq!(codes(&scanner), [(Some(1127), 0), (Some(1127), 5)]);
+    }
+
+    #[test]
+    fn escaped_identifiers_are_decoded() {
+        assert_eq!(
+            kinds(r"\u0061bc a\u{62}c \u{1D49C}"),
+            vec![
+                TokenKind::Identifier(intern("abc")),
+                TokenKind::Identifier(intern("abc")),
+                TokenKind::Identifier(intern("\u{1D49C}")),
+            ]
+        );
+        // An escape must still spell an identifier character
+        let mut scanner = Scanner::new(r"a\u0020b \x");
+        scan_to_end(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1127), 1), (Some(1127), 9)]);
+    }
+
+    #[test]
+    fn escaped_keywords_are_identifiers_and_reported() {
+        let mut scanner = Scanner::new(r"\u0069f if");
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Identifier(intern("if")));
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::If);
+        assert_eq!(codes(&scanner), [(Some(1260), 0)]);
+    }
+
+    #[test]
+    fn only_reserved_words_scan_as_keywords() {
+        assert_eq!(
+            kinds("class let yield type from as of"),
+            vec![
+                TokenKind::Class,
+                TokenKind::Let,
+                TokenKind::Yield,
+                TokenKind::Identifier(intern("type")),
+                TokenKind::Identifier(intern("from")),
+                TokenKind::Identifier(intern("as")),
+                TokenKind::Identifier(intern("of")),
+            ]
+        );
+        for keyword in ContextualKeyword::ALL {
+            let mut scanner = Scanner::new(keyword.as_str());
+            let token = scanner.next_token().unwrap();
+            assert_eq!(token.kind, TokenKind::Identifier(keyword.symbol()));
+            assert_eq!(token.contextual_keyword(), Some(keyword));
+            assert!(token.is_contextual_keyword(keyword));
+        }
+        let mut scanner = Scanner::new("types");
+        assert_eq!(scanner.next_token().unwrap().contextual_keyword(), None);
+    }
+
+    #[test]
+    fn equal_token_text_interns_to_the_same_symbol() {
+        let mut scanner = Scanner::new("name 'name' `name` na\\u{6D}e");
+        let tokens: Vec<_> = std::iter::from_fn(|| scanner.next_token()).map(|token| token.kind).collect();
+        let symbol = intern("name");
+        assert_eq!(tokens[0], TokenKind::Identifier(symbol));
+        assert_eq!(tokens[1], TokenKind::String(symbol));
+        assert_eq!(
+            tokens[2],
+            TokenKind::NoSubstitutionTemplate {
+                cooked: Some(symbol),
+                raw: symbol
+            }
+        );
+        assert_eq!(tokens[3], TokenKind::Identifier(symbol));
+        assert_eq!(resolve(symbol).as_deref(), Some("name"));
+        assert!(scanner.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn invalid_characters_become_unknown_tokens_with_a_diagnostic() {
+        let mut scanner = Scanner::new("a € \u{1}b");
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Identifier(intern("a")));
+        let unknown = scanner.next_token().unwrap();
+        assert_eq!(unknown.kind, TokenKind::Unknown);
+        assert_eq!((unknown.span.start.offset, unknown.span.end.offset), (2, 5));
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Unknown);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Identifier(intern("b")));
+
+        let diagnostic = &scanner.diagnostics()[0];
+        assert_eq!(diagnostic.kind, DiagnosticKind::SyntaxError);
+        assert_eq!(diagnostic.code, Some(1127));
+        assert_eq!(diagnostic.message, "Invalid character.");
+        assert_eq!(diagnostic.span, unknown.span);
+        assert_eq!(codes(&scanner)[1], (Some(1127), 6));
+    }
+
+    #[test]
+    fn a_long_run_of_invalid_characters_does_not_overflow() {
+        let source = "\u{1}".repeat(100_000);
+        let (tokens, diagnostics) = Lexer::new(&source).tokenize();
+        assert_eq!(tokens.len(), 100_001);
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::Eof);
+        assert_eq!(diagnostics.len(), 100_000);
+    }
+
+    #[test]
+    fn unterminated_constructs_are_reported_at_their_start() {
+        let mut scanner = Scanner::new("a /* open");
+        scan_to_end(&mut scanner);
+        let diagnostic = &scanner.diagnostics()[0];
+        assert_eq!(diagnostic.code, Some(1010));
+        assert_eq!(diagnostic.span.start.offset, 2);
+
+        let mut scanner = Scanner::new("x = `a${b}c");
+        scan_to_end(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1160), 9)]);
+    }
+
+    #[test]
+    fn option_comments_are_collected_as_directives() {
+        let source = "\u{feff}#!/usr/bin/env node\n// @target: es5\n//@strict:true\n\
+                      let a; // @noLib: true\n  // @Filename:  b.ts \n";
+        let mut scanner = Scanner::new(source);
+        scan_to_end(&mut scanner);
+        let directives: Vec<_> = scanner
+            .directives()
+            .iter()
+            .map(|d| (d.key.as_str(), d.value.as_str(), &source[d.span.start.offset..d.span.end.offset]))
+            .collect();
+        assert_eq!(
+            directives,
+            [
+                ("target", "es5", "// @target: es5"),
+                ("strict", "true", "//@strict:true"),
+                ("Filename", "b.ts", "// @Filename:  b.ts "),
+            ]
+        );
+        assert!(scanner.diagnostics().is_empty());
+
+        // Not directives: no colon, no key, or inside a block comment
+        let mut scanner = Scanner::new("// @target es5\n// @: x\n/* // @a: b */\n// @ts-ignore\n");
+        scan_to_end(&mut scanner);
+        assert!(scanner.directives().is_empty());
+    }
+
+    #[test]
+    fn reference_directives_are_collected_from_the_file_header() {
+        let source = "/// <reference path=\"a.ts\" />\n///<reference types='node' lib=\"es2015\"/>\n\
+                      /// <reference path=\"bad\" \nlet x;\n/// <reference path=\"late.ts\" />\n\
+                      // @filename: b.ts\n/// <reference path=\"b.d.ts\" />\n";
+        let mut scanner = Scanner::new(source);
+        scan_to_end(&mut scanner);
+        let references: Vec<_> = scanner.references().iter().map(|r| r.attributes.clone()).collect();
+        let attribute = |name: &str, value: &str| (name.to_string(), value.to_string());
+        assert_eq!(
+            references,
+            [
+                vec![attribute("path", "a.ts")],
+                vec![attribute("types", "node"), attribute("lib", "es2015")],
+                vec![attribute("path", "b.d.ts")],
+            ]
+        );
+        assert_eq!(scanner.references()[1].attribute("lib"), Some("es2015"));
+        assert_eq!(scanner.references()[0].span.end.offset, 29);
+    }
+
+    /// Scan `source` to the end, returning the source text of each token before `Eof`
+    fn token_texts(source: &str) -> Vec<&str> {
+        let mut scanner = Scanner::new(source);
+        let mut texts = Vec::new();
+        while let Some(token) = scanner.next_token() {
+            if token.kind != TokenKind::Eof {
+                texts.push(&source[token.span.start.offset..token.span.end.offset]);
+            }
+        }
+        texts
+    }
+
+    #[test]
+    fn braces_inside_substitutions_do_not_end_the_template() {
+        let source = "`a${ `b${ { c: 1 }.c }` }d` + x";
+        assert_eq!(
+            token_texts(source),
+            ["`a${", "`b${", "{", "c", ":", "1", "}", ".", "c", "}`", "}d`", "+", "x"]
+        );
+        let template = |kind: &TokenKind| match *kind {
+            TokenKind::TemplateHead { raw, .. } => format!("head {}", resolve(raw).unwrap()),
+            TokenKind::TemplateTail { raw, .. } => format!("tail {}", resolve(raw).unwrap()),
+            _ => String::new(),
+        };
+        let templates: Vec<_> = kinds(source).iter().map(template).filter(|text| !text.is_empty()).collect();
+        assert_eq!(templates, ["head a", "head b", "tail ", "tail d"]);
+
+        // A `${` inside a string or a nested template does not open the outer template
+        assert_eq!(token_texts("`${'}'}${`}`}`"), ["`${", "'}'", "}${", "`}`", "}`"]);
+    }
+
+    #[test]
+    fn unterminated_templates_are_reported() {
+        let mut scanner = Scanner::new("`abc");
+        let token = scanner.next_token().unwrap();
+        assert_eq!(
+            token.kind,
+            TokenKind::NoSubstitutionTemplate {
+                cooked: Some(intern("abc")),
+                raw: intern("abc")
+            }
+        );
+        assert_eq!(codes(&scanner), [(Some(1160), 0)]);
+        assert_eq!(scanner.next_token().unwrap().kind, TokenKind::Eof);
+
+        // The error is reported at the part of the template that runs to the end
+        let mut scanner = Scanner::new("`a${b}\nc${d}");
+        scan_to_end(&mut scanner);
+        assert_eq!(codes(&scanner), [(Some(1160), 11)]);
+
+        // An open substitution is left for the parser to report
+        let mut scanner = Scanner::new("`a${b");
+        scan_to_end(&mut scanner);
+        assert!(scanner.diagnostics().is_empty());
+    }
+}
@@ -0,0 +1,297 @@
+//! Main lexer implementation
+
+use super::{Checkpoint, CommentDirective, ReferenceDirective, Token, Scanner, ScriptTarget};
+use crate::diagnostics::Diagnostic;
+use std::collections::VecDeque;
+
+/// Main lexer for TypeScript source code
+#[derive(Debug)]
+pub struct Lexer<'a> {
+    scanner: Scanner<'a>,
+    /// Tokens scanned ahead by `peek` but not yet returned, each with the scanner
+    /// state after it
+    lookahead: VecDeque<(Token, Checkpoint)>,
+    /// The scanner state after the last token returned, while the scanner itself
+    /// has moved on to the end of `lookahead`
+    consumed: Option<Checkpoint>,
+}
+
+impl<'a> Lexer<'a> {
+    /// Create a new lexer for the given source code
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            scanner: Scanner::new(source),
+            lookahead: VecDeque::new(),
+            consumed: None,
+        }
+    }
+
+    /// Enable or disable strict-mode scanning rules
+    pub fn set_strict_mode(&mut self, strict: bool) {
+        self.discard_lookahead();
+        self.scanner.set_strict_mode(strict);
+    }
+
+    /// Enable or disable lossless mode, in which tokens carry their leading and
+    /// trailing trivia so that concatenating them reproduces the source
+    pub fn set_preserve_trivia(&mut self, preserve: bool) {
+        self.discard_lookahead();
+        self.scanner.set_preserve_trivia(preserve);
+    }
+
+    /// Enable or disable scanning `>` on its own. By default `>` is scanned as the
+    /// longest operator starting with it, such as `>>>=`; the parser turns this on
+    /// to close nested type argument lists and rescans `>` where an operator may
+    /// appear with `rescan_greater_token`.
+    pub fn set_split_greater(&mut self, split: bool) {
+        self.discard_lookahead();
+        self.scanner.set_split_greater(split);
+    }
+
+    /// Set the target used to validate version-dependent syntax
+    pub fn set_target(&mut self, target: ScriptTarget) {
+        self.discard_lookahead();
+        self.scanner.set_target(target);
+    }
+
+    /// The source text being tokenized
+    pub fn source(&self) -> &'a str {
+        self.scanner.source()
+    }
+
+    /// Diagnostics reported while scanning so far
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        let diagnostics = self.scanner.diagnostics();
+        match &self.consumed {
+            Some(checkpoint) => &diagnostics[..checkpoint.diagnostic_count],
+            None => diagnostics,
+        }
+    }
+
+    /// `// @key: value` directive comments seen so far, in source order
+    pub fn directives(&self) -> &[CommentDirective] {
+        let directives = self.scanner.directives();
+        match &self.consumed {
+            Some(checkpoint) => &directives[..checkpoint.directive_count],
+            None => directives,
+        }
+    }
+
+    /// `/// <reference ... />` directives from the file header seen so far
+    pub fn references(&self) -> &[ReferenceDirective] {
+        let references = self.scanner.references();
+        match &self.consumed {
+            Some(checkpoint) => &references[..checkpoint.reference_count],
+            None => references,
+        }
+    }
+
+    /// Get the next token from the source. The `Eof` token is returned once, after
+    /// which this returns `None`.
+    pub fn next_token(&mut self) -> Option<Token> {
+        match self.lookahead.pop_front() {
+            Some((token, checkpoint)) => {
+                // Once the buffer is drained the scanner is back in step
+                self.consumed = (!self.lookahead.is_empty()).then_some(checkpoint);
+                Some(token)
+            }
+            None => self.scanner.next_token(),
+        }
+    }
+
+    /// Look at the token `n` positions ahead without consuming anything, so that
+    /// `peek(0)` is the token the next call to `next_token` returns.
+    ///
+    /// Tokens scanned ahead are kept until they are returned, so looking at the
+    /// same tokens again costs nothing.
+    pub fn peek(&mut self, n: usize) -> Option<Token> {
+        while self.lookahead.len() <= n {
+            let before = self.lookahead.is_empty().then(|| self.scanner.checkpoint());
+            let token = self.scanner.next_token()?;
+            if before.is_some() {
+                self.consumed = before;
+            }
+            self.lookahead.push_back((token, self.scanner.checkpoint()));
+        }
+        self.lookahead.get(n).map(|(token, _)| token.clone())
+    }
+
+    /// Save the current state so that scanning can resume from here, as when the
+    /// parser tries one interpretation of ambiguous syntax and may need to back out
+    pub fn checkpoint(&self) -> Checkpoint {
+        match &self.consumed {
+            Some(checkpoint) => checkpoint.clone(),
+            None => self.scanner.checkpoint(),
+        }
+    }
+
+    /// Return to a saved state, discarding tokens and diagnostics produced since
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.lookahead.clear();
+        self.consumed = None;
+        self.scanner.rewind(checkpoint);
+    }
+
+    /// Drop the tokens scanned ahead and return the scanner to the last token
+    /// returned, before it is rescanned or the scanning rules change
+    fn discard_lookahead(&mut self) {
+        self.lookahead.clear();
+        if let Some(checkpoint) = self.consumed.take() {
+            self.scanner.rewind(checkpoint);
+        }
+    }
+
+    /// Rescan the `/` or `/=` token just returned as a regular expression literal.
+    ///
+    /// Returns `None` if the most recent token was not a slash.
+    pub fn rescan_slash_as_regex(&mut self) -> Option<Token> {
+        self.discard_lookahead();
+        self.scanner.rescan_slash_as_regex()
+    }
+
+    /// Rescan the `>` token just returned as part of a longer operator such as `>>=`.
+    ///
+    /// Returns `None` if the most recent token was not a `>`.
+    pub fn rescan_greater_token(&mut self) -> Option<Token> {
+        self.discard_lookahead();
+        self.scanner.rescan_greater_token()
+    }
+
+    /// Rescan the `}` token just returned as the continuation of a template literal.
+    ///
+    /// Returns `None` if the most recent token was not a `}`.
+    pub fn rescan_template_continuation(&mut self) -> Option<Token> {
+        self.discard_lookahead();
+        self.scanner.rescan_template_continuation()
+    }
+
+    /// Rescan the template token just returned, reporting invalid escape sequences
+    /// that are only allowed in tagged templates.
+    ///
+    /// Returns `None` if the most recent token was not part of a template.
+    pub fn rescan_template_token(&mut self) -> Option<Token> {
+        self.discard_lookahead();
+        self.scanner.rescan_template_token()
+    }
+
+    /// Scan the next token as JSX child content: `JsxText`, `<`, `</` or `{`.
+    ///
+    /// Called after the `>` of an opening tag or the `}` of an expression container.
+    pub fn scan_jsx_token(&mut self) -> Option<Token> {
+        self.discard_lookahead();
+        self.scanner.scan_jsx_token()
+    }
+
+    /// Rescan the identifier or keyword just returned as a JSX name such as `data-id`
+    /// or `xlink:href`.
+    ///
+    /// Returns `None` if the most recent token was not an identifier or keyword.
+    pub fn rescan_jsx_identifier(&mut self) -> Option<Token> {
+        self.discard_lookahead();
+        self.scanner.rescan_jsx_identifier()
+    }
+
+    /// Scan the next token as a JSX attribute value, where quoted strings have no escapes
+    pub fn scan_jsx_attribute_value(&mut self) -> Option<Token> {
+        self.discard_lookahead();
+        self.scanner.scan_jsx_attribute_value()
+    }
+
+    /// Tokenize the entire source, returning the tokens (ending with `Eof`) along
+    /// with the diagnostics reported while scanning them
+    pub fn tokenize(&mut self) -> (Vec<Token>, Vec<Diagnostic>) {
+        let tokens = self.by_ref().collect();
+        self.discard_lookahead();
+        (tokens, self.scanner.take_diagnostics())
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        self.next_token()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::TokenKind;
+    use crate::utils::intern::intern;
+
+    #[test]
+    fn iteration_ends_after_eof() {
+        let mut lexer = Lexer::new("a");
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Identifier(intern("a")));
+        assert_eq!(lexer.next().unwrap().kind, TokenKind::Eof);
+        assert!(lexer.next().is_none());
+        assert!(lexer.next().is_none());
+        assert!(lexer.peek(0).is_none());
+    }
+
+    #[test]
+    fn peek_looks_ahead_without_consuming() {
+        let mut lexer = Lexer::new("a + b");
+        assert_eq!(lexer.peek(2).unwrap().kind, TokenKind::Identifier(intern("b")));
+        assert_eq!(lexer.peek(3).unwrap().kind, TokenKind::Eof);
+        assert!(lexer.peek(4).is_none());
+        let peeked = lexer.peek(0).unwrap();
+        assert_eq!(lexer.next_token().unwrap(), peeked);
+        assert_eq!(lexer.peek(0).unwrap().kind, TokenKind::Plus);
+        assert_eq!(lexer.next_token().unwrap().kind, TokenKind::Plus);
+    }
+
+    #[test]
+    fn rewind_returns_to_a_checkpoint() {
+        let mut lexer = Lexer::new("a € b");
+        lexer.next_token();
+        let checkpoint = lexer.checkpoint();
+        let ahead: Vec<_> = lexer.by_ref().map(|token| token.kind).collect();
+        assert_eq!(lexer.diagnostics().len(), 1);
+
+        lexer.rewind(checkpoint);
+        assert!(lexer.diagnostics().is_empty());
+        let again: Vec<_> = lexer.by_ref().map(|token| token.kind).collect();
+        assert_eq!(again, ahead);
+        assert_eq!(lexer.diagnostics().len(), 1);
+    }
+
+    #[test]
+    fn rescanning_drops_the_tokens_looked_ahead() {
+        let mut lexer = Lexer::new("x = /a/g.b €");
+        lexer.next_token(); // x
+        lexer.next_token(); // =
+        assert_eq!(lexer.next_token().unwrap().kind, TokenKind::Slash);
+        assert_eq!(lexer.peek(4).unwrap().kind, TokenKind::Identifier(intern("b")));
+        assert_eq!(lexer.peek(0).unwrap().kind, TokenKind::Identifier(intern("a")));
+        let regex = lexer.rescan_slash_as_regex().unwrap();
+        assert_eq!(
+            regex.kind,
+            TokenKind::RegularExpression {
+                pattern: intern("a"),
+                flags: intern("g")
+            }
+        );
+        assert_eq!(lexer.peek(0).unwrap().kind, TokenKind::Dot);
+        let checkpoint = lexer.checkpoint();
+        assert_eq!(lexer.next_token().unwrap().kind, TokenKind::Dot);
+        lexer.rewind(checkpoint);
+        let rest: Vec<_> = lexer.by_ref().map(|token| token.kind).collect();
+        assert_eq!(rest, [TokenKind::Dot, TokenKind::Identifier(intern("b")), TokenKind::Unknown, TokenKind::Eof]);
+        assert_eq!(lexer.diagnostics().len(), 1);
+    }
+
+    #[test]
+    fn peek_keeps_diagnostics_for_later() {
+        let mut lexer = Lexer::new("a €");
+        assert!(lexer.peek(3).is_none());
+        assert!(lexer.diagnostics().is_empty());
+        lexer.next_token();
+        assert!(lexer.diagnostics().is_empty());
+        lexer.next_token();
+        assert_eq!(lexer.diagnostics().len(), 1);
+        assert_eq!(lexer.next_token().unwrap().kind, TokenKind::Eof);
+        assert!(lexer.next_token().is_none());
+        assert_eq!(lexer.diagnostics().len(), 1);
+    }
+}
@@ -1,5 +1,7 @@
 //! Token definitions for the TypeScript lexer
 
+use super::keywords::{contextual_keyword_for_symbol, keyword_text, ContextualKeyword};
+use crate::utils::intern::{serde_option_symbol, serde_symbol, Symbol};
 use crate::utils::span::Span;
 use serde::{Deserialize, Serialize};
 
@@ -10,64 +12,143 @@ pub struct Token {
     pub kind: TokenKind,
     /// Source location information
     pub span: Span,
+    /// Trivia between the previous token's trailing trivia and this token.
+    /// Only populated when the lexer preserves trivia.
+    pub leading_trivia: Vec<Trivia>,
+    /// Trivia after this token up to, but not including, the next line break.
+    /// Only populated when the lexer preserves trivia.
+    pub trailing_trivia: Vec<Trivia>,
+    /// Whether a line break occurs between the previous token and this one
+    pub preceded_by_newline: bool,
 }
 
-/// Different kinds of tokens in TypeScript
+/// A piece of trivia: source text that carries no meaning for the parser
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trivia {
+    /// The kind of trivia
+    pub kind: TriviaKind,
+    /// Source location information
+    pub span: Span,
+}
+
+/// Different kinds of trivia
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TriviaKind {
+    /// A run of spaces, tabs and other non-line-break whitespace
+    Whitespace,
+    /// A single line break (`\n`, `\r\n`, `\r`, U+2028 or U+2029)
+    Newline,
+    /// A `//` comment, excluding the line break that ends it
+    SingleLineComment,
+    /// A `/* */` comment
+    MultiLineComment,
+    /// A `#!` line at the very start of the file, after any byte order mark
+    Shebang,
+    /// A U+FEFF byte order mark at the very start of the file
+    ByteOrderMark,
+}
+
+/// Different kinds of tokens in TypeScript.
+///
+/// Token text is interned rather than owned, so a `TokenKind` is `Copy` and
+/// scanning does not allocate per token.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TokenKind {
     // Literals
     Number(f64),
-    String(String),
+    /// A BigInt literal such as `123n` or `0xFFn`. Holds the literal's digits,
+    /// including any radix prefix, with separators and the `n` suffix removed.
+    BigInt(#[serde(with = "serde_symbol")] Symbol),
+    /// A string literal, holding its decoded value
+    String(#[serde(with = "serde_symbol")] Symbol),
+    /// A regular expression literal such as `/ab+c/gi`, produced by rescanning a slash
+    RegularExpression {
+        #[serde(with = "serde_symbol")]
+        pattern: Symbol,
+        #[serde(with = "serde_symbol")]
+        flags: Symbol,
+    },
+    /// A template literal without substitutions, such as `` `text` ``.
+    /// `cooked` is `None` when the template contains an invalid escape sequence.
+    NoSubstitutionTemplate {
+        #[serde(with = "serde_option_symbol")]
+        cooked: Option<Symbol>,
+        #[serde(with = "serde_symbol")]
+        raw: Symbol,
+    },
+    /// The opening part of a template literal up to the first `${`
+    TemplateHead {
+        #[serde(with = "serde_option_symbol")]
+        cooked: Option<Symbol>,
+        #[serde(with = "serde_symbol")]
+        raw: Symbol,
+    },
+    /// The part of a template literal between two substitutions
+    TemplateMiddle {
+        #[serde(with = "serde_option_symbol")]
+        cooked: Option<Symbol>,
+        #[serde(with = "serde_symbol")]
+        raw: Symbol,
+    },
+    /// The closing part of a template literal after the last substitution
+    TemplateTail {
+        #[serde(with = "serde_option_symbol")]
+        cooked: Option<Symbol>,
+        #[serde(with = "serde_symbol")]
+        raw: Symbol,
+    },
     Boolean(bool),
     Null,
-    Undefined,
     
     // Identifiers and keywords
-    Identifier(String),
+    Identifier(#[serde(with = "serde_symbol")] Symbol),
     
-    // Keywords
-    Let,
-    Const,
-    Var,
-    Function,
+    // Reserved words
+    Break,
+    Case,
+    Catch,
     Class,
-    Interface,
-    Type,
-    Enum,
-    Namespace,
-    Import,
-    Export,
-    From,
-    As,
+    Const,
+    Continue,
+    Debugger,
     Default,
-    If,
-    Else,
-    For,
-    While,
+    Delete,
     Do,
-    Switch,
-    Case,
-    Break,
-    Continue,
-    Return,
-    Try,
-    Catch,
+    Else,
+    Enum,
+    Export,
+    Extends,
     Finally,
-    Throw,
+    For,
+    Function,
+    If,
+    Import,
+    In,
+    Instanceof,
     New,
-    This,
+    Return,
     Super,
-    Extends,
+    Switch,
+    This,
+    Throw,
+    Try,
+    Typeof,
+    Var,
+    Void,
+    While,
+    With,
+
+    // Reserved words in strict mode code
     Implements,
-    Public,
+    Interface,
+    Let,
+    Package,
     Private,
     Protected,
+    Public,
     Static,
-    Abstract,
-    Readonly,
-    Async,
-    Await,
-    
+    Yield,
+
     // Operators
     Plus,
     Minus,
@@ -81,6 +162,18 @@ pub enum TokenKind {
     StarEqual,
     SlashEqual,
     PercentEqual,
+    StarStarEqual,
+    AmpersandEqual,
+    PipeEqual,
+    CaretEqual,
+    LessLessEqual,
+    GreaterGreaterEqual,
+    GreaterGreaterGreaterEqual,
+    AmpersandAmpersandEqual,
+    PipePipeEqual,
+    QuestionQuestionEqual,
+    PlusPlus,
+    MinusMinus,
     EqualEqual,
     EqualEqualEqual,
     BangEqual,
@@ -115,37 +208,161 @@ pub enum TokenKind {
     Dot,
     Colon,
     Arrow,
+    DotDotDot,
+    At,
+    Hash,
     
+    // JSX
+    /// Text between JSX tags, with HTML entities decoded in `value`
+    JsxText {
+        #[serde(with = "serde_symbol")]
+        value: Symbol,
+        #[serde(with = "serde_symbol")]
+        raw: Symbol,
+    },
+    /// A JSX tag or attribute name, which may contain `-` as in `data-id`
+    JsxIdentifier(#[serde(with = "serde_symbol")] Symbol),
+    /// A namespaced JSX tag or attribute name such as `xlink:href`
+    JsxNamespacedName {
+        #[serde(with = "serde_symbol")]
+        namespace: Symbol,
+        #[serde(with = "serde_symbol")]
+        name: Symbol,
+    },
+    /// `</`, which starts a JSX closing tag
+    LessSlash,
+
     // Special
+    /// A character that does not begin any valid token
+    Unknown,
     Eof,
-    Newline,
-    Whitespace,
-    Comment(String),
 }
 
 impl Token {
     /// Create a new token
     pub fn new(kind: TokenKind, span: Span) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            leading_trivia: Vec::new(),
+            trailing_trivia: Vec::new(),
+            preceded_by_newline: false,
+        }
     }
     
-    /// Check if this token is a keyword
+    /// Check if this token is a keyword, i.e. a reserved word in strict mode code
     pub fn is_keyword(&self) -> bool {
-        matches!(self.kind, 
-            TokenKind::Let | TokenKind::Const | TokenKind::Var |
-            TokenKind::Function | TokenKind::Class | TokenKind::Interface |
-            TokenKind::Type | TokenKind::Enum | TokenKind::Namespace |
-            TokenKind::Import | TokenKind::Export | TokenKind::From |
-            TokenKind::As | TokenKind::Default | TokenKind::If |
-            TokenKind::Else | TokenKind::For | TokenKind::While |
-            TokenKind::Do | TokenKind::Switch | TokenKind::Case |
-            TokenKind::Break | TokenKind::Continue | TokenKind::Return |
-            TokenKind::Try | TokenKind::Catch | TokenKind::Finally |
-            TokenKind::Throw | TokenKind::New | TokenKind::This |
-            TokenKind::Super | TokenKind::Extends | TokenKind::Implements |
-            TokenKind::Public | TokenKind::Private | TokenKind::Protected |
-            TokenKind::Static | TokenKind::Abstract | TokenKind::Readonly |
-            TokenKind::Async | TokenKind::Await
+        self.is_reserved_word() || self.is_strict_mode_reserved_word()
+    }
+
+    /// Check if this token is a word reserved in all code
+    pub fn is_reserved_word(&self) -> bool {
+        matches!(self.kind,
+            TokenKind::Break | TokenKind::Case | TokenKind::Catch |
+            TokenKind::Class | TokenKind::Const | TokenKind::Continue |
+            TokenKind::Debugger | TokenKind::Default | TokenKind::Delete |
+            TokenKind::Do | TokenKind::Else | TokenKind::Enum |
+            TokenKind::Export | TokenKind::Extends | TokenKind::Boolean(_) |
+            TokenKind::Finally | TokenKind::For | TokenKind::Function |
+            TokenKind::If | TokenKind::Import | TokenKind::In |
+            TokenKind::Instanceof | TokenKind::New | TokenKind::Null |
+            TokenKind::Return | TokenKind::Super | TokenKind::Switch |
+            TokenKind::This | TokenKind::Throw | TokenKind::Try |
+            TokenKind::Typeof | TokenKind::Var | TokenKind::Void |
+            TokenKind::While | TokenKind::With
+        )
+    }
+
+    /// Check if this token is a word reserved only in strict mode code, which
+    /// sloppy-mode code may still use as an identifier
+    pub fn is_strict_mode_reserved_word(&self) -> bool {
+        matches!(self.kind,
+            TokenKind::Implements | TokenKind::Interface | TokenKind::Let |
+            TokenKind::Package | TokenKind::Private | TokenKind::Protected |
+            TokenKind::Public | TokenKind::Static | TokenKind::Yield
         )
     }
-}
\ No newline at end of file
+
+    /// Get the contextual keyword this identifier token spells, if any
+    pub fn contextual_keyword(&self) -> Option<ContextualKeyword> {
+        match self.kind {
+            TokenKind::Identifier(name) => contextual_keyword_for_symbol(name),
+            _ => None,
+        }
+    }
+
+    /// Check if this token is an identifier spelling the given contextual keyword
+    pub fn is_contextual_keyword(&self, keyword: ContextualKeyword) -> bool {
+        matches!(self.kind, TokenKind::Identifier(name) if name == keyword.symbol())
+    }
+}
+
+impl TokenKind {
+    /// Get the fixed source text of a keyword, operator or punctuator token, or
+    /// `None` for tokens whose text varies, such as identifiers and literals
+    pub fn text(&self) -> Option<&'static str> {
+        match self {
+            TokenKind::Plus => Some("+"),
+            TokenKind::Minus => Some("-"),
+            TokenKind::Star => Some("*"),
+            TokenKind::Slash => Some("/"),
+            TokenKind::Percent => Some("%"),
+            TokenKind::StarStar => Some("**"),
+            TokenKind::Equal => Some("="),
+            TokenKind::PlusEqual => Some("+="),
+            TokenKind::MinusEqual => Some("-="),
+            TokenKind::StarEqual => Some("*="),
+            TokenKind::SlashEqual => Some("/="),
+            TokenKind::PercentEqual => Some("%="),
+            TokenKind::StarStarEqual => Some("**="),
+            TokenKind::AmpersandEqual => Some("&="),
+            TokenKind::PipeEqual => Some("|="),
+            TokenKind::CaretEqual => Some("^="),
+            TokenKind::LessLessEqual => Some("<<="),
+            TokenKind::GreaterGreaterEqual => Some(">>="),
+            TokenKind::GreaterGreaterGreaterEqual => Some(">>>="),
+            TokenKind::AmpersandAmpersandEqual => Some("&&="),
+            TokenKind::PipePipeEqual => Some("||="),
+            TokenKind::QuestionQuestionEqual => Some("??="),
+            TokenKind::PlusPlus => Some("++"),
+            TokenKind::MinusMinus => Some("--"),
+            TokenKind::EqualEqual => Some("=="),
+            TokenKind::EqualEqualEqual => Some("==="),
+            TokenKind::BangEqual => Some("!="),
+            TokenKind::BangEqualEqual => Some("!=="),
+            TokenKind::Less => Some("<"),
+            TokenKind::LessEqual => Some("<="),
+            TokenKind::Greater => Some(">"),
+            TokenKind::GreaterEqual => Some(">="),
+            TokenKind::LessLess => Some("<<"),
+            TokenKind::GreaterGreater => Some(">>"),
+            TokenKind::GreaterGreaterGreater => Some(">>>"),
+            TokenKind::Ampersand => Some("&"),
+            TokenKind::AmpersandAmpersand => Some("&&"),
+            TokenKind::Pipe => Some("|"),
+            TokenKind::PipePipe => Some("||"),
+            TokenKind::Caret => Some("^"),
+            TokenKind::Tilde => Some("~"),
+            TokenKind::Bang => Some("!"),
+            TokenKind::Question => Some("?"),
+            TokenKind::QuestionQuestion => Some("??"),
+            TokenKind::QuestionDot => Some("?."),
+            TokenKind::LeftParen => Some("("),
+            TokenKind::RightParen => Some(")"),
+            TokenKind::LeftBrace => Some("{"),
+            TokenKind::RightBrace => Some("}"),
+            TokenKind::LeftBracket => Some("["),
+            TokenKind::RightBracket => Some("]"),
+            TokenKind::Semicolon => Some(";"),
+            TokenKind::Comma => Some(","),
+            TokenKind::Dot => Some("."),
+            TokenKind::Colon => Some(":"),
+            TokenKind::Arrow => Some("=>"),
+            TokenKind::DotDotDot => Some("..."),
+            TokenKind::At => Some("@"),
+            TokenKind::Hash => Some("#"),
+            TokenKind::LessSlash => Some("</"),
+            _ => keyword_text(*self),
+        }
+    }
+}
@@ -21,7 +21,7 @@ pub use symbols::{Symbol, SymbolTable, SymbolKind};
 pub use codegen::CodeGenerator;
 pub use diagnostics::Diagnostic;
 pub use utils::span::{Span, Position};
-pub use baseline_test::{BaselineTestRunner, BaselineTestResult};
+pub use baseline_test::{BaselineTestRunner, BaselineTestResult, ParseReport};
 
 use serde::{Deserialize, Serialize};
 
@@ -36,6 +36,8 @@ pub struct CompileOptions {
     pub source_map: bool,
     /// Source file name
     pub file_name: String,
+    /// Use the legacy `experimentalDecorators` semantics instead of standard decorators
+    pub experimental_decorators: bool,
 }
 
 impl Default for CompileOptions {
@@ -45,6 +47,7 @@ impl Default for CompileOptions {
             module: "commonjs".to_string(),
             source_map: false,
             file_name: "input.ts".to_string(),
+            experimental_decorators: false,
         }
     }
 }
@@ -64,15 +67,27 @@ pub struct CompileResult {
 pub fn compile(input: &Path, options: &CompileOptions) -> Result<CompileResult, Vec<Diagnostic>> {
     let source = std::fs::read_to_string(input)
         .map_err(|e| vec![Diagnostic::error(format!("Failed to read file: {}", e), Span::default())])?;
-    
-    let mut lexer = Lexer::new(&source);
+    compile_source(&source, options)
+}
+
+/// Compile TypeScript source code already read from `options.file_name`
+pub fn compile_source(source: &str, options: &CompileOptions) -> Result<CompileResult, Vec<Diagnostic>> {
+    let lexer = Lexer::new(source);
     let mut parser = Parser::new(lexer);
+    parser.set_jsx(Path::new(&options.file_name).extension().is_some_and(|ext| ext == "tsx"));
+    // An `@experimentalDecorators` option comment in the source also turns it on
+    if options.experimental_decorators {
+        parser.set_experimental_decorators(true);
+    }
     
-    let ast = parser.parse()
-        .map_err(|e| vec![Diagnostic::error(format!("Parse error: {:?}", e), Span::default())])?;
+    let (program, diagnostics) = parser.parse();
+    if !diagnostics.is_empty() {
+        return Err(diagnostics);
+    }
+    let nodes: Vec<AstNode> = program.statements.into_iter().map(AstNode::Statement).collect();
     
     let mut codegen = CodeGenerator::new();
-    let js_code = codegen.generate(&[ast])
+    let js_code = codegen.generate(&nodes)
         .map_err(|e| vec![Diagnostic::error(format!("Codegen error: {:?}", e), Span::default())])?;
     
     Ok(CompileResult {
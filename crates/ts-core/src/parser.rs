@@ -1,41 +0,0 @@
-//! Parser implementation for TypeScript AST
-
-use crate::ast::{AstNode, Statement};
-use crate::lexer::{Lexer, Token, TokenKind};
-use crate::diagnostics::Diagnostic;
-use crate::utils::span::{Span, Position};
-
-/// Parser for TypeScript source code
-pub struct Parser {
-    lexer: Lexer,
-    current_token: Option<Token>,
-    diagnostics: Vec<Diagnostic>,
-}
-
-impl Parser {
-    pub fn new(lexer: Lexer) -> Self {
-        Self {
-            lexer,
-            current_token: Some(Token::new(TokenKind::Eof, Span::default())),
-            diagnostics: Vec::new(),
-        }
-    }
-
-    /// Create a new parser from tokens
-    pub fn from_tokens(tokens: Vec<Token>) -> Self {
-        // Create a dummy lexer and manually set tokens
-        let lexer = Lexer::new("");
-        let current_token = tokens.first().cloned();
-        Self {
-            lexer,
-            current_token,
-            diagnostics: Vec::new(),
-        }
-    }
-
-    /// Parse the source code into an AST
-    pub fn parse(&mut self) -> Result<AstNode, String> {
-        // Simple implementation - just return an empty statement for now
-        Ok(AstNode::Statement(Statement::Empty))
-    }
-}
\ No newline at end of file
@@ -0,0 +1,1242 @@
+//! Declaration parsing: functions, classes, interfaces, type aliases, enums,
+//! namespaces, imports and exports
+
+use super::{ParseResult, Parser};
+use crate::ast::{
+    Accessibility, BindingPattern, BlockStatement, ClassDeclaration, ClassMember,
+    ComputedPropertyName, ConstructorDefinition, Declaration, Decorator, EnumDeclaration, EnumMember,
+    ExportAssignment, ExportDeclaration, ExportDefault, ExportDefaultDeclaration, ExportSpecifier,
+    Expression, FunctionDeclaration, Identifier, ImportAttribute, ImportAttributes, ImportDeclaration,
+    ImportEqualsDeclaration, ImportSpecifier, IndexSignature, InterfaceDeclaration, InterfaceMember,
+    InterfaceMethod, InterfaceProperty, KeywordType, KeywordTypeKind, Literal, LiteralExpression,
+    MethodDefinition, MethodKind, Modifiers, ModuleReference, NamedExportSpecifier,
+    NamedImportSpecifier, NamespaceDeclaration, NamespaceExportDeclaration, Parameter, PropertyDefinition, PropertyName,
+    SignatureDeclaration, Statement, TypeDeclaration, TypeNode, TypeParameter, VariableStatement,
+};
+use crate::lexer::{ContextualKeyword, TokenKind};
+use crate::utils::intern::resolve;
+use crate::utils::span::{Position, Span};
+
+impl<'a> Parser<'a> {
+    /// Check whether the current token starts a declaration. Contextual keywords
+    /// such as `type` or `namespace` only do so when followed by a name on the
+    /// same line; otherwise they are ordinary identifiers.
+    pub(super) fn is_start_of_declaration(&mut self) -> bool {
+        match self.kind() {
+            TokenKind::Function | TokenKind::Class | TokenKind::Enum | TokenKind::Export | TokenKind::At => true,
+            TokenKind::Const => self.peek_kind(0) == TokenKind::Enum,
+            TokenKind::Import => !matches!(self.peek_kind(0), TokenKind::LeftParen | TokenKind::Dot),
+            TokenKind::Interface => self.next_is_name_on_same_line(),
+            TokenKind::Identifier(_) => match self.current_token.contextual_keyword() {
+                Some(ContextualKeyword::Type) => self.next_is_name_on_same_line(),
+                Some(ContextualKeyword::Namespace | ContextualKeyword::Module) => {
+                    self.next_is_name_on_same_line()
+                        || matches!(self.peek_kind(0), TokenKind::String(_))
+                }
+                Some(ContextualKeyword::Global) => self.peek_kind(0) == TokenKind::LeftBrace,
+                Some(ContextualKeyword::Declare) => {
+                    !self.next_starts_line() && self.peek_token().is_some_and(|token| {
+                        token.kind.text().is_some_and(|text| text.starts_with(char::is_alphabetic))
+                            || matches!(token.kind, TokenKind::Identifier(_))
+                    })
+                }
+                Some(ContextualKeyword::Abstract) => {
+                    !self.next_starts_line() && self.peek_kind(0) == TokenKind::Class
+                }
+                Some(ContextualKeyword::Async) => self.at_async_function(),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Check whether the token after the current one starts a new line
+    pub(super) fn next_starts_line(&mut self) -> bool {
+        self.peek_token().is_some_and(|token| token.preceded_by_newline)
+    }
+
+    /// Check whether the current token is `async` modifying a function. A line
+    /// break after `async` ends it as an identifier instead.
+    pub(super) fn at_async_function(&mut self) -> bool {
+        self.at_contextual(ContextualKeyword::Async)
+            && !self.next_starts_line()
+            && self.peek_kind(0) == TokenKind::Function
+    }
+
+    /// Check whether the token after the current one is an identifier on the same line
+    fn next_is_name_on_same_line(&mut self) -> bool {
+        !self.next_starts_line()
+            && self.peek_token().is_some_and(|token| {
+                matches!(token.kind, TokenKind::Identifier(_)) || token.is_strict_mode_reserved_word()
+            })
+    }
+
+    /// Parse a declaration, having checked [`Self::is_start_of_declaration`]
+    pub(super) fn parse_declaration(&mut self) -> ParseResult<Declaration> {
+        match self.kind() {
+            TokenKind::Function => Ok(Declaration::Function(Box::new(self.parse_function_declaration(true)?))),
+            TokenKind::Class => self.parse_class_declaration(Vec::new(), false, true),
+            TokenKind::At => self.parse_decorated_declaration(),
+            TokenKind::Enum => Ok(Declaration::Enum(self.parse_enum_declaration(false)?)),
+            TokenKind::Const if self.peek_kind(0) == TokenKind::Enum => {
+                self.advance();
+                Ok(Declaration::Enum(self.parse_enum_declaration(true)?))
+            }
+            // `export using` and `declare using` are left for the checker to reject
+            kind if matches!(kind, TokenKind::Var | TokenKind::Let | TokenKind::Const)
+                || self.is_using_declaration(false) =>
+            {
+                let start = self.start();
+                let statement = self.parse_variable_declaration_list()?;
+                self.consume_semicolon()?;
+                Ok(Declaration::Variable(VariableStatement {
+                    span: self.span_from(start),
+                    ..statement
+                }))
+            }
+            TokenKind::Interface => Ok(Declaration::Interface(Box::new(self.parse_interface_declaration()?))),
+            TokenKind::Import => self.parse_import_declaration(),
+            _ if self.at_async_function() => Ok(Declaration::Function(Box::new(self.parse_function_declaration(true)?))),
+            TokenKind::Export => self.parse_export_declaration(),
+            _ => match self.current_token.contextual_keyword() {
+                Some(ContextualKeyword::Type) => Ok(Declaration::Type(Box::new(self.parse_type_alias_declaration()?))),
+                Some(ContextualKeyword::Namespace | ContextualKeyword::Module | ContextualKeyword::Global) => {
+                    Ok(Declaration::Namespace(self.parse_namespace_declaration()?))
+                }
+                Some(ContextualKeyword::Abstract) => {
+                    self.advance();
+                    self.parse_class_declaration(Vec::new(), true, true)
+                }
+                Some(ContextualKeyword::Declare) => {
+                    let start = self.start();
+                    self.advance();
+                    let mut declaration = self.parse_declaration()?;
+                    mark_declare(&mut declaration, self.span_from(start));
+                    Ok(declaration)
+                }
+                _ => Err(self.error(1128, "Declaration or statement expected.")),
+            },
+        }
+    }
+
+    /// Parse decorators at the start of a statement and the class they decorate,
+    /// which may also follow `export` or `export default`
+    fn parse_decorated_declaration(&mut self) -> ParseResult<Declaration> {
+        let start = self.start();
+        let decorators = self.parse_decorators()?;
+        if !self.is_start_of_declaration() {
+            return Err(self.error(1146, "Declaration expected."));
+        }
+        let mut declaration = self.parse_declaration()?;
+        let span = self.span_from(start);
+        let class = match &mut declaration {
+            Declaration::Class(class) => {
+                class.span = span;
+                Some(class)
+            }
+            Declaration::Export(export) => {
+                export.span = span;
+                match export.declaration.as_deref_mut() {
+                    Some(Declaration::Class(class)) => Some(class),
+                    _ => None,
+                }
+            }
+            Declaration::ExportDefault(export) => {
+                export.span = span;
+                match &mut export.value {
+                    ExportDefault::Declaration(Declaration::Class(class)) => Some(class),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match class {
+            Some(class) => {
+                if let Some(decorator) = class.decorators.first() {
+                    self.report(
+                        8038,
+                        "Decorators may not appear after 'export' or 'export default' if they also appear before 'export'.",
+                        decorator.span,
+                    );
+                }
+                class.decorators.splice(0..0, decorators);
+            }
+            None => self.report_invalid_decorators(&decorators),
+        }
+        Ok(declaration)
+    }
+
+    /// Parse `@expression` decorators, if any, before a class, class member or parameter
+    pub(super) fn parse_decorators(&mut self) -> ParseResult<Vec<Decorator>> {
+        let mut decorators = Vec::new();
+        while self.at(TokenKind::At) {
+            let start = self.start();
+            self.advance();
+            let expression = self.parse_decorator_expression()?;
+            decorators.push(Decorator {
+                expression,
+                span: self.span_from(start),
+            });
+        }
+        Ok(decorators)
+    }
+
+    /// Report the first of some decorators written where none are allowed
+    pub(super) fn report_invalid_decorators(&mut self, decorators: &[Decorator]) {
+        if let Some(decorator) = decorators.first() {
+            self.report(1206, "Decorators are not valid here.", decorator.span);
+        }
+    }
+
+    /// Parse `function name(params): T { ... }` or `function*`, or an overload
+    /// signature ending in `;`, after any `async` modifier. The name may only be
+    /// left out after `export default`.
+    fn parse_function_declaration(&mut self, name_required: bool) -> ParseResult<FunctionDeclaration> {
+        let start = self.start();
+        let is_async = self.eat_contextual(ContextualKeyword::Async);
+        self.expect(TokenKind::Function)?;
+        let is_generator = self.eat(TokenKind::Star);
+        let name = if self.is_identifier() {
+            Some(self.parse_identifier()?)
+        } else {
+            if name_required {
+                self.recover_from(self.error(1003, "Identifier expected."))?;
+            }
+            None
+        };
+        let type_parameters = self.parse_type_parameters()?;
+        let parameters = self.with_function_context(is_async, is_generator, Self::parse_parameter_list)?;
+        let return_type = self.parse_optional_return_type()?;
+        let body = self.with_function_context(is_async, is_generator, Self::parse_optional_body)?;
+        Ok(FunctionDeclaration {
+            name,
+            is_async,
+            is_generator,
+            type_parameters,
+            parameters,
+            return_type,
+            body,
+            is_declare: false,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a function body, or the `;` ending a signature without one
+    fn parse_optional_body(&mut self) -> ParseResult<Option<BlockStatement>> {
+        if self.at(TokenKind::LeftBrace) {
+            Ok(Some(self.parse_block()?))
+        } else {
+            self.consume_semicolon()?;
+            Ok(None)
+        }
+    }
+
+    /// Parse a class declaration after any `abstract` modifier, given the
+    /// decorators before it
+    fn parse_class_declaration(
+        &mut self,
+        decorators: Vec<Decorator>,
+        is_abstract: bool,
+        name_required: bool,
+    ) -> ParseResult<Declaration> {
+        let mut class = self.parse_class(is_abstract, name_required)?;
+        class.decorators = decorators;
+        self.check_class_decorators(&class, false);
+        Ok(Declaration::Class(Box::new(class)))
+    }
+
+    /// Parse a class expression and any decorators before it
+    pub(super) fn parse_class_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let decorators = self.parse_decorators()?;
+        if !self.at(TokenKind::Class) {
+            return Err(self.error(1109, "Expression expected."));
+        }
+        let mut class = self.parse_class(false, false)?;
+        class.decorators = decorators;
+        class.span = self.span_from(start);
+        self.check_class_decorators(&class, true);
+        Ok(Expression::Class(Box::new(class)))
+    }
+
+    /// Report decorators that the chosen semantics do not allow on a class or
+    /// its members, like tsc's `nodeCanBeDecorated`. Legacy decorators only
+    /// apply to class declarations and may decorate parameters; standard ones
+    /// cannot decorate abstract or ambient fields. Neither can decorate an
+    /// overload signature.
+    fn check_class_decorators(&mut self, class: &ClassDeclaration, is_expression: bool) {
+        let legacy_in_expression = is_expression && self.experimental_decorators;
+        if legacy_in_expression {
+            self.report_invalid_decorators(&class.decorators);
+        }
+        for member in &class.body {
+            let (parameters, has_body) = match member {
+                ClassMember::Method(method) => {
+                    match method.decorators.first() {
+                        Some(decorator) if method.body.is_none() => self.report(
+                            1249,
+                            "A decorator can only decorate a method implementation, not an overload.",
+                            decorator.span,
+                        ),
+                        _ if legacy_in_expression => self.report_invalid_decorators(&method.decorators),
+                        _ => {}
+                    }
+                    (&method.parameters, method.body.is_some())
+                }
+                ClassMember::Constructor(constructor) => (&constructor.parameters, constructor.body.is_some()),
+                ClassMember::Property(property) => {
+                    let modifiers = property.modifiers;
+                    if legacy_in_expression
+                        || (!self.experimental_decorators && (modifiers.is_abstract || modifiers.is_declare))
+                    {
+                        self.report_invalid_decorators(&property.decorators);
+                    }
+                    continue;
+                }
+                ClassMember::IndexSignature(_) | ClassMember::StaticBlock(_) => continue,
+            };
+            // Without `experimentalDecorators` parameter decorators were
+            // already reported while parsing
+            if self.experimental_decorators && (legacy_in_expression || !has_body) {
+                for parameter in parameters {
+                    self.report_invalid_decorators(&parameter.decorators);
+                }
+            }
+        }
+    }
+
+    /// Parse a class declaration or expression after any `abstract` modifier.
+    /// The name may be left out of class expressions and `export default class`.
+    pub(super) fn parse_class(&mut self, is_abstract: bool, name_required: bool) -> ParseResult<ClassDeclaration> {
+        let start = self.start();
+        let keyword = self.expect(TokenKind::Class)?;
+        // `implements` is the class name unless a heritage type follows it
+        let is_implements_clause = self.at(TokenKind::Implements) && self.peek_token().is_some_and(|token| {
+            matches!(token.kind, TokenKind::Identifier(_))
+                || token.kind.text().is_some_and(|text| text.starts_with(char::is_alphabetic))
+        });
+        let name = if self.is_identifier() && !is_implements_clause {
+            Some(self.parse_identifier()?)
+        } else {
+            if name_required {
+                self.report(1211, "A class declaration without the 'default' modifier must have a name.", keyword.span);
+            }
+            None
+        };
+        let type_parameters = self.parse_type_parameters()?;
+        let mut super_type_arguments = Vec::new();
+        let super_class = if self.eat(TokenKind::Extends) {
+            match self.parse_left_hand_side_expression()? {
+                // `extends Base<T>` reads as an instantiation expression
+                Expression::Instantiation(instantiation) => {
+                    super_type_arguments = instantiation.type_arguments;
+                    Some(*instantiation.expression)
+                }
+                expression => {
+                    if self.at(TokenKind::Less) {
+                        // Type arguments not taken as an instantiation, as before `implements`
+                        super_type_arguments = self.parse_type_arguments()?;
+                    }
+                    Some(expression)
+                }
+            }
+        } else {
+            None
+        };
+        let implements = if self.eat(TokenKind::Implements) {
+            self.parse_type_list()?
+        } else {
+            Vec::new()
+        };
+
+        self.expect(TokenKind::LeftBrace)?;
+        let mut body = Vec::new();
+        while !self.at(TokenKind::RightBrace) && !self.at(TokenKind::Eof) {
+            if self.eat(TokenKind::Semicolon) {
+                continue;
+            }
+            self.parse_list_element(&mut body, Self::parse_class_member)?;
+        }
+        self.expect(TokenKind::RightBrace)?;
+
+        Ok(ClassDeclaration {
+            decorators: Vec::new(),
+            name,
+            type_parameters,
+            super_class,
+            super_type_arguments,
+            implements,
+            body,
+            is_abstract,
+            is_declare: false,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a comma-separated list of types, as after `implements`
+    fn parse_type_list(&mut self) -> ParseResult<Vec<TypeNode>> {
+        let mut types = vec![self.parse_type()?];
+        while self.eat(TokenKind::Comma) {
+            types.push(self.parse_type()?);
+        }
+        Ok(types)
+    }
+
+    /// Check whether the current word is a modifier rather than a member name,
+    /// which is the case when another name, or a misplaced decorator, follows it
+    pub(super) fn at_member_modifier(&mut self) -> bool {
+        let is_modifier_word = matches!(
+            self.kind(),
+            TokenKind::Public | TokenKind::Private | TokenKind::Protected | TokenKind::Static
+        ) || matches!(
+            self.current_token.contextual_keyword(),
+            Some(
+                ContextualKeyword::Readonly
+                    | ContextualKeyword::Abstract
+                    | ContextualKeyword::Override
+                    | ContextualKeyword::Declare
+                    | ContextualKeyword::Accessor
+            )
+        );
+        is_modifier_word && (self.next_starts_member_name() || self.peek_kind(0) == TokenKind::At)
+    }
+
+    /// Parse the modifiers before a class member or parameter property
+    pub(super) fn parse_modifiers(&mut self) -> Modifiers {
+        let mut modifiers = Modifiers::default();
+        while self.at_member_modifier() {
+            let token = self.advance();
+            let seen = match token.kind {
+                TokenKind::Public | TokenKind::Private | TokenKind::Protected => {
+                    let seen = modifiers.accessibility.is_some();
+                    modifiers.accessibility = Some(match token.kind {
+                        TokenKind::Public => Accessibility::Public,
+                        TokenKind::Private => Accessibility::Private,
+                        _ => Accessibility::Protected,
+                    });
+                    if seen {
+                        self.report(1028, "Accessibility modifier already seen.", token.span);
+                    }
+                    continue;
+                }
+                TokenKind::Static => std::mem::replace(&mut modifiers.is_static, true),
+                _ => match token.contextual_keyword() {
+                    Some(ContextualKeyword::Readonly) => std::mem::replace(&mut modifiers.is_readonly, true),
+                    Some(ContextualKeyword::Abstract) => std::mem::replace(&mut modifiers.is_abstract, true),
+                    Some(ContextualKeyword::Override) => std::mem::replace(&mut modifiers.is_override, true),
+                    Some(ContextualKeyword::Declare) => std::mem::replace(&mut modifiers.is_declare, true),
+                    _ => std::mem::replace(&mut modifiers.is_accessor, true),
+                },
+            };
+            if seen {
+                let message = format!("'{}' modifier already seen.", self.token_text(&token));
+                self.report(1030, &message, token.span);
+            }
+        }
+        modifiers
+    }
+
+    /// Check whether the token after the current one can start a member name
+    pub(super) fn next_starts_member_name(&mut self) -> bool {
+        match self.peek_token() {
+            Some(token) if token.preceded_by_newline => false,
+            Some(token) => {
+                matches!(
+                    token.kind,
+                    TokenKind::Identifier(_) | TokenKind::String(_) | TokenKind::Number(_)
+                        | TokenKind::LeftBracket | TokenKind::Star | TokenKind::Hash
+                ) || token.kind.text().is_some_and(|text| text.starts_with(char::is_alphabetic))
+            }
+            None => false,
+        }
+    }
+
+    fn parse_class_member(&mut self) -> ParseResult<ClassMember> {
+        let start = self.start();
+        let mut decorators = self.parse_decorators()?;
+        if self.at(TokenKind::Static) && self.peek_kind(0) == TokenKind::LeftBrace {
+            self.report_invalid_decorators(&decorators);
+            self.advance();
+            return Ok(ClassMember::StaticBlock(self.parse_block()?));
+        }
+
+        let modifiers = self.parse_modifiers();
+        if self.at(TokenKind::At) {
+            let late_decorators = self.parse_decorators()?;
+            if let Some(decorator) = late_decorators.first() {
+                self.report(
+                    1436,
+                    "Decorators must precede the name and all keywords of property declarations.",
+                    decorator.span,
+                );
+            }
+            decorators.extend(late_decorators);
+        }
+        if self.at_contextual(ContextualKeyword::Constructor)
+            && matches!(self.peek_kind(0), TokenKind::LeftParen | TokenKind::Less)
+        {
+            self.report_invalid_decorators(&decorators);
+            self.advance();
+            // Type parameters are not allowed here, but are parsed so the checker can report them
+            self.parse_type_parameters()?;
+            let parameters = self.parse_class_member_parameters(false, false)?;
+            self.parse_optional_return_type()?;
+            let body = self.with_function_context(false, false, Self::parse_optional_body)?;
+            return Ok(ClassMember::Constructor(ConstructorDefinition {
+                modifiers,
+                parameters,
+                body,
+                span: self.span_from(start),
+            }));
+        }
+        if self.is_index_signature() {
+            self.report_invalid_decorators(&decorators);
+            let signature = self.parse_index_signature(start, modifiers)?;
+            self.consume_semicolon()?;
+            return Ok(ClassMember::IndexSignature(Box::new(signature)));
+        }
+
+        let is_async = self.at_contextual(ContextualKeyword::Async) && self.next_starts_member_name();
+        if is_async {
+            self.advance();
+        }
+        let is_generator = self.eat(TokenKind::Star);
+        let kind = if is_async || is_generator {
+            MethodKind::Method
+        } else if self.at_contextual(ContextualKeyword::Get) && self.next_starts_member_name() {
+            self.advance();
+            MethodKind::Get
+        } else if self.at_contextual(ContextualKeyword::Set) && self.next_starts_member_name() {
+            self.advance();
+            MethodKind::Set
+        } else {
+            MethodKind::Method
+        };
+
+        let name = self.parse_member_name()?;
+        let optional = self.eat(TokenKind::Question);
+        let definite = !optional && self.eat(TokenKind::Bang);
+
+        if kind != MethodKind::Method
+            || is_async
+            || is_generator
+            || matches!(self.kind(), TokenKind::LeftParen | TokenKind::Less)
+        {
+            let type_parameters = self.parse_type_parameters()?;
+            let parameters = self.parse_class_member_parameters(is_async, is_generator)?;
+            let return_type = self.parse_optional_return_type()?;
+            let body = self.with_function_context(is_async, is_generator, Self::parse_optional_body)?;
+            return Ok(ClassMember::Method(Box::new(MethodDefinition {
+                decorators,
+                name,
+                kind,
+                modifiers,
+                is_async,
+                is_generator,
+                type_parameters,
+                parameters,
+                return_type,
+                body,
+                optional,
+                span: self.span_from(start),
+            })));
+        }
+
+        let type_annotation = self.parse_optional_type_annotation()?;
+        let value = if self.eat(TokenKind::Equal) {
+            Some(self.parse_assignment_expression()?)
+        } else {
+            None
+        };
+        self.consume_semicolon()?;
+        Ok(ClassMember::Property(Box::new(PropertyDefinition {
+            decorators,
+            name,
+            modifiers,
+            type_annotation,
+            optional,
+            definite,
+            value,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse the parameters of a class method or constructor, which are the only
+    /// ones that may be decorated, and only under `experimentalDecorators`
+    fn parse_class_member_parameters(&mut self, is_async: bool, is_generator: bool) -> ParseResult<Vec<Parameter>> {
+        self.allow_parameter_decorators = self.experimental_decorators;
+        self.with_function_context(is_async, is_generator, Self::parse_parameter_list)
+    }
+
+    /// Parse the name of a class or interface member
+    fn parse_member_name(&mut self) -> ParseResult<PropertyName> {
+        let start = self.start();
+        match self.kind() {
+            TokenKind::String(value) => {
+                let token = self.advance();
+                Ok(PropertyName::Literal(LiteralExpression {
+                    value: Literal::String(resolve(value).unwrap_or_default()),
+                    span: token.span,
+                }))
+            }
+            TokenKind::Number(value) => {
+                let token = self.advance();
+                Ok(PropertyName::Literal(LiteralExpression {
+                    value: Literal::Number(value),
+                    span: token.span,
+                }))
+            }
+            TokenKind::Hash => {
+                // Private name: `#name`
+                self.advance();
+                let name = self.parse_identifier_name()?;
+                Ok(PropertyName::Private(Identifier {
+                    name: name.name,
+                    span: self.span_from(start),
+                }))
+            }
+            TokenKind::LeftBracket => {
+                self.advance();
+                let expression = self.parse_assignment_expression()?;
+                self.expect(TokenKind::RightBracket)?;
+                Ok(PropertyName::Computed(ComputedPropertyName {
+                    expression: Box::new(expression),
+                    span: self.span_from(start),
+                }))
+            }
+            _ => Ok(PropertyName::Identifier(self.parse_identifier_name()?)),
+        }
+    }
+
+    fn parse_interface_declaration(&mut self) -> ParseResult<InterfaceDeclaration> {
+        let start = self.start();
+        self.expect(TokenKind::Interface)?;
+        let name = self.parse_identifier()?;
+        let type_parameters = self.parse_type_parameters()?;
+        let extends = if self.eat(TokenKind::Extends) {
+            self.parse_type_list()?
+        } else {
+            Vec::new()
+        };
+        let members = self.parse_type_members()?;
+        Ok(InterfaceDeclaration {
+            name,
+            type_parameters,
+            extends,
+            members,
+            is_declare: false,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse the `{ ... }` member list of an interface
+    pub(super) fn parse_type_members(&mut self) -> ParseResult<Vec<InterfaceMember>> {
+        self.expect(TokenKind::LeftBrace)?;
+        let mut members = Vec::new();
+        while !self.at(TokenKind::RightBrace) && !self.at(TokenKind::Eof) {
+            self.parse_list_element(&mut members, Self::parse_type_member)?;
+            // Members are separated by `;`, `,` or a line break
+            if !self.eat(TokenKind::Semicolon)
+                && !self.eat(TokenKind::Comma)
+                && !self.at(TokenKind::RightBrace)
+                && !self.current_token.preceded_by_newline
+            {
+                self.recover_from(self.expected(";"))?;
+            }
+        }
+        self.expect(TokenKind::RightBrace)?;
+        Ok(members)
+    }
+
+    fn parse_type_member(&mut self) -> ParseResult<InterfaceMember> {
+        let start = self.start();
+        if matches!(self.kind(), TokenKind::LeftParen | TokenKind::Less) {
+            let (type_parameters, parameters, return_type) = self.parse_signature()?;
+            return Ok(InterfaceMember::CallSignature(SignatureDeclaration {
+                type_parameters,
+                parameters,
+                return_type,
+                span: self.span_from(start),
+            }));
+        }
+        if self.at(TokenKind::New) && matches!(self.peek_kind(0), TokenKind::LeftParen | TokenKind::Less) {
+            self.advance();
+            let (type_parameters, parameters, return_type) = self.parse_signature()?;
+            return Ok(InterfaceMember::ConstructSignature(SignatureDeclaration {
+                type_parameters,
+                parameters,
+                return_type,
+                span: self.span_from(start),
+            }));
+        }
+
+        let mut modifiers = Modifiers::default();
+        if self.at_contextual(ContextualKeyword::Readonly) && self.next_starts_member_name() {
+            self.advance();
+            modifiers.is_readonly = true;
+        }
+        if self.is_index_signature() {
+            return Ok(InterfaceMember::IndexSignature(Box::new(self.parse_index_signature(start, modifiers)?)));
+        }
+        let mut kind = MethodKind::Method;
+        if (self.at_contextual(ContextualKeyword::Get) || self.at_contextual(ContextualKeyword::Set))
+            && self.next_starts_member_name()
+        {
+            kind = if self.at_contextual(ContextualKeyword::Get) {
+                MethodKind::Get
+            } else {
+                MethodKind::Set
+            };
+            self.advance();
+        }
+
+        let name = self.parse_member_name()?;
+        let optional = self.eat(TokenKind::Question);
+        if kind != MethodKind::Method || matches!(self.kind(), TokenKind::LeftParen | TokenKind::Less) {
+            let (type_parameters, parameters, return_type) = self.parse_signature()?;
+            return Ok(InterfaceMember::Method(Box::new(InterfaceMethod {
+                name,
+                kind,
+                modifiers,
+                type_parameters,
+                parameters,
+                return_type,
+                optional,
+                span: self.span_from(start),
+            })));
+        }
+
+        let type_annotation = self.parse_optional_type_annotation()?;
+        if self.eat(TokenKind::Equal) {
+            // Not allowed, but parsed so that the checker can report it
+            self.parse_assignment_expression()?;
+        }
+        Ok(InterfaceMember::Property(InterfaceProperty {
+            name,
+            modifiers,
+            type_annotation,
+            optional,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse optional type parameters, a parameter list and optional return type annotation
+    fn parse_signature(&mut self) -> ParseResult<(Vec<TypeParameter>, Vec<Parameter>, Option<TypeNode>)> {
+        let type_parameters = self.parse_type_parameters()?;
+        let parameters = self.parse_parameter_list()?;
+        let return_type = self.parse_optional_return_type()?;
+        Ok((type_parameters, parameters, return_type))
+    }
+
+    /// Check for `[name: ...` starting an index signature rather than a computed name
+    fn is_index_signature(&mut self) -> bool {
+        self.at(TokenKind::LeftBracket)
+            && matches!(self.peek_kind(0), TokenKind::Identifier(_))
+            && self.peek_kind(1) == TokenKind::Colon
+    }
+
+    /// Parse `[key: string]: T`
+    fn parse_index_signature(&mut self, start: Position, modifiers: Modifiers) -> ParseResult<IndexSignature> {
+        self.expect(TokenKind::LeftBracket)?;
+        let parameter_start = self.start();
+        let name = self.parse_identifier()?;
+        let parameter_type = self.parse_optional_type_annotation()?;
+        let parameter = Parameter {
+            decorators: Vec::new(),
+            name: BindingPattern::Identifier(name),
+            modifiers: Modifiers::default(),
+            type_annotation: parameter_type,
+            optional: false,
+            rest: false,
+            initializer: None,
+            span: self.span_from(parameter_start),
+        };
+        self.expect(TokenKind::RightBracket)?;
+        self.expect(TokenKind::Colon)?;
+        let type_annotation = self.parse_type()?;
+        Ok(IndexSignature {
+            modifiers,
+            parameter,
+            type_annotation,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `type Name = Type;`
+    fn parse_type_alias_declaration(&mut self) -> ParseResult<TypeDeclaration> {
+        let start = self.start();
+        self.advance();
+        let name = self.parse_identifier()?;
+        let type_parameters = self.parse_type_parameters()?;
+        self.expect(TokenKind::Equal)?;
+        let type_annotation = if self.at_contextual(ContextualKeyword::Intrinsic) && self.peek_kind(0) != TokenKind::Dot {
+            // `type Uppercase<S extends string> = intrinsic;`
+            let token = self.advance();
+            TypeNode::Keyword(KeywordType {
+                kind: KeywordTypeKind::Intrinsic,
+                span: token.span,
+            })
+        } else {
+            self.parse_type()?
+        };
+        self.consume_semicolon()?;
+        Ok(TypeDeclaration {
+            name,
+            type_parameters,
+            type_annotation,
+            is_declare: false,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `enum Name { A, B = 1 }`, after any `const`
+    fn parse_enum_declaration(&mut self, is_const: bool) -> ParseResult<EnumDeclaration> {
+        let start = self.start();
+        self.expect(TokenKind::Enum)?;
+        let name = self.parse_identifier()?;
+        self.expect(TokenKind::LeftBrace)?;
+        let mut members = Vec::new();
+        while !self.at(TokenKind::RightBrace) && !self.at(TokenKind::Eof) {
+            self.parse_delimited_element(&mut members, Self::parse_enum_member)?;
+            if !self.parse_list_comma(false)? {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBrace)?;
+        Ok(EnumDeclaration {
+            name,
+            is_const,
+            members,
+            is_declare: false,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_enum_member(&mut self) -> ParseResult<EnumMember> {
+        let start = self.start();
+        let name = self.parse_member_name()?;
+        let value = if self.eat(TokenKind::Equal) {
+            Some(self.parse_assignment_expression()?)
+        } else {
+            None
+        };
+        Ok(EnumMember {
+            name,
+            value,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `namespace A.B { ... }`, `module "name" { ... }` or `global { ... }`
+    fn parse_namespace_declaration(&mut self) -> ParseResult<NamespaceDeclaration> {
+        let start = self.start();
+        let is_global = self.at_contextual(ContextualKeyword::Global);
+        let keyword = self.advance();
+
+        let name = if is_global {
+            self.identifier_from(&keyword)
+        } else if let TokenKind::String(value) = self.kind() {
+            let token = self.advance();
+            Identifier {
+                name: resolve(value).unwrap_or_default(),
+                span: token.span,
+            }
+        } else {
+            self.parse_identifier()?
+        };
+
+        if !is_global && self.at(TokenKind::Dot) {
+            // `namespace A.B { }` declares `B` inside `A`
+            self.advance();
+            let inner_start = self.start();
+            let inner = self.parse_namespace_body_after_dot(inner_start)?;
+            return Ok(NamespaceDeclaration {
+                name,
+                body: vec![Statement::Declaration(Box::new(Declaration::Namespace(inner)))],
+                is_declare: false,
+                span: self.span_from(start),
+            });
+        }
+
+        let body = self.parse_namespace_body()?;
+        Ok(NamespaceDeclaration {
+            name,
+            body,
+            is_declare: false,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse the rest of a dotted namespace name and its body
+    fn parse_namespace_body_after_dot(&mut self, start: Position) -> ParseResult<NamespaceDeclaration> {
+        let name = self.parse_identifier()?;
+        let body = if self.eat(TokenKind::Dot) {
+            let inner_start = self.start();
+            let inner = self.parse_namespace_body_after_dot(inner_start)?;
+            vec![Statement::Declaration(Box::new(Declaration::Namespace(inner)))]
+        } else {
+            self.parse_namespace_body()?
+        };
+        Ok(NamespaceDeclaration {
+            name,
+            body,
+            is_declare: false,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse a namespace's `{ ... }` body, or the `;` of a shorthand ambient module
+    fn parse_namespace_body(&mut self) -> ParseResult<Vec<Statement>> {
+        if !self.at(TokenKind::LeftBrace) {
+            self.consume_semicolon()?;
+            return Ok(Vec::new());
+        }
+        Ok(self.parse_block()?.statements)
+    }
+
+    fn parse_import_declaration(&mut self) -> ParseResult<Declaration> {
+        let start = self.start();
+        self.expect(TokenKind::Import)?;
+
+        // Side-effect import: `import "module";`
+        if let TokenKind::String(source) = self.kind() {
+            self.advance();
+            let attributes = self.parse_import_attributes()?;
+            self.consume_semicolon()?;
+            return Ok(Declaration::Import(ImportDeclaration {
+                specifiers: Vec::new(),
+                source: resolve(source).unwrap_or_default(),
+                is_type_only: false,
+                attributes,
+                span: self.span_from(start),
+            }));
+        }
+
+        let is_type_only = self.at_contextual(ContextualKeyword::Type) && self.next_starts_import_clause();
+        if is_type_only {
+            self.advance();
+        }
+
+        let mut specifiers = Vec::new();
+        if self.is_identifier() {
+            let name = self.parse_identifier()?;
+            if self.at(TokenKind::Equal) {
+                return self.parse_import_equals_rest(start, name);
+            }
+            specifiers.push(ImportSpecifier::Default(name));
+            if self.eat(TokenKind::Comma) {
+                self.parse_import_bindings(&mut specifiers)?;
+            }
+        } else {
+            self.parse_import_bindings(&mut specifiers)?;
+        }
+
+        if !self.eat_contextual(ContextualKeyword::From) {
+            return Err(self.expected("from"));
+        }
+        let source = self.parse_module_specifier()?;
+        let attributes = self.parse_import_attributes()?;
+        self.consume_semicolon()?;
+        Ok(Declaration::Import(ImportDeclaration {
+            specifiers,
+            source,
+            is_type_only,
+            attributes,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Check whether the token after `type` continues a type-only import clause,
+    /// rather than `type` being the default import's name
+    fn next_starts_import_clause(&mut self) -> bool {
+        match self.peek_token() {
+            Some(token) if matches!(token.kind, TokenKind::LeftBrace | TokenKind::Star) => true,
+            Some(token) => {
+                (matches!(token.kind, TokenKind::Identifier(_)) || token.is_strict_mode_reserved_word())
+                    && !token.is_contextual_keyword(ContextualKeyword::From)
+            }
+            None => false,
+        }
+    }
+
+    /// Parse `* as name` or `{ a, b as c }` in an import clause
+    fn parse_import_bindings(&mut self, specifiers: &mut Vec<ImportSpecifier>) -> ParseResult<()> {
+        if self.eat(TokenKind::Star) {
+            if !self.eat_contextual(ContextualKeyword::As) {
+                return Err(self.expected("as"));
+            }
+            specifiers.push(ImportSpecifier::Namespace(self.parse_identifier()?));
+            return Ok(());
+        }
+
+        self.expect(TokenKind::LeftBrace)?;
+        while !self.at(TokenKind::RightBrace) {
+            let specifier_start = self.start();
+            let is_type_only = self.at_type_specifier_modifier();
+            if is_type_only {
+                self.advance();
+            }
+            let imported = self.parse_module_export_name()?;
+            let local = if self.eat_contextual(ContextualKeyword::As) {
+                Some(self.parse_identifier()?)
+            } else {
+                None
+            };
+            specifiers.push(ImportSpecifier::Named(NamedImportSpecifier {
+                imported,
+                local,
+                is_type_only,
+                span: self.span_from(specifier_start),
+            }));
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBrace)?;
+        Ok(())
+    }
+
+    /// Check whether `type` before an import or export specifier is a modifier,
+    /// as in `{ type A }`, rather than the name itself, as in `{ type }` or `{ type as T }`
+    fn at_type_specifier_modifier(&mut self) -> bool {
+        if !self.at_contextual(ContextualKeyword::Type) {
+            return false;
+        }
+        match self.peek_token() {
+            Some(token) if token.is_contextual_keyword(ContextualKeyword::As) => {
+                // `{ type as as T }` imports `as`; `{ type as T }` imports `type`
+                self.peek_token_at(1).is_some_and(|token| token.is_contextual_keyword(ContextualKeyword::As))
+            }
+            Some(token) => !matches!(token.kind, TokenKind::Comma | TokenKind::RightBrace),
+            None => false,
+        }
+    }
+
+    /// Parse an imported or exported name, which may be any word or a string
+    fn parse_module_export_name(&mut self) -> ParseResult<Identifier> {
+        if let TokenKind::String(value) = self.kind() {
+            let token = self.advance();
+            return Ok(Identifier {
+                name: resolve(value).unwrap_or_default(),
+                span: token.span,
+            });
+        }
+        self.parse_identifier_name()
+    }
+
+    /// Parse the string naming a module after `from`
+    fn parse_module_specifier(&mut self) -> ParseResult<String> {
+        match self.kind() {
+            TokenKind::String(value) => {
+                self.advance();
+                Ok(resolve(value).unwrap_or_default())
+            }
+            _ => Err(self.error(1141, "String literal expected.")),
+        }
+    }
+
+    /// Parse `with { type: "json" }` or the older `assert { ... }` import attributes
+    /// after a module specifier
+    fn parse_import_attributes(&mut self) -> ParseResult<Option<ImportAttributes>> {
+        if !(self.at(TokenKind::With) || self.at_contextual(ContextualKeyword::Assert))
+            || self.current_token.preceded_by_newline
+        {
+            return Ok(None);
+        }
+        let start = self.start();
+        let is_assert = self.advance().kind != TokenKind::With;
+        self.expect(TokenKind::LeftBrace)?;
+        let mut elements = Vec::new();
+        while !self.at(TokenKind::RightBrace) {
+            let element_start = self.start();
+            let key = self.parse_property_key()?;
+            self.expect(TokenKind::Colon)?;
+            let value = self.parse_assignment_expression()?;
+            elements.push(ImportAttribute {
+                key,
+                value,
+                span: self.span_from(element_start),
+            });
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBrace)?;
+        Ok(Some(ImportAttributes {
+            is_assert,
+            elements,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse the `= require("module")` or `= A.B` of an import alias
+    fn parse_import_equals_rest(&mut self, start: Position, name: Identifier) -> ParseResult<Declaration> {
+        self.expect(TokenKind::Equal)?;
+        let module_reference = if self.at_contextual(ContextualKeyword::Require)
+            && self.peek_kind(0) == TokenKind::LeftParen
+        {
+            self.advance();
+            self.advance();
+            let source = self.parse_module_specifier()?;
+            self.expect(TokenKind::RightParen)?;
+            ModuleReference::External(source)
+        } else {
+            ModuleReference::Entity(self.parse_entity_name()?)
+        };
+        self.consume_semicolon()?;
+        Ok(Declaration::ImportEquals(Box::new(ImportEqualsDeclaration {
+            name,
+            module_reference,
+            span: self.span_from(start),
+        })))
+    }
+
+    fn parse_export_declaration(&mut self) -> ParseResult<Declaration> {
+        let start = self.start();
+        self.expect(TokenKind::Export)?;
+
+        match self.kind() {
+            TokenKind::Equal => {
+                self.advance();
+                let expression = self.parse_assignment_expression()?;
+                self.consume_semicolon()?;
+                Ok(Declaration::ExportAssignment(ExportAssignment {
+                    expression,
+                    span: self.span_from(start),
+                }))
+            }
+            TokenKind::Default => {
+                self.advance();
+                let decorators = self.parse_decorators()?;
+                let is_abstract_class =
+                    self.at_contextual(ContextualKeyword::Abstract) && self.peek_kind(0) == TokenKind::Class;
+                let value = if self.at(TokenKind::Class) || is_abstract_class {
+                    // The name of a default-exported class is optional
+                    if is_abstract_class {
+                        self.advance();
+                    }
+                    ExportDefault::Declaration(self.parse_class_declaration(decorators, is_abstract_class, false)?)
+                } else if !decorators.is_empty() {
+                    return Err(self.expected("class"));
+                } else if self.at(TokenKind::Function) || self.at_async_function() {
+                    // So is the name of a default-exported function
+                    ExportDefault::Declaration(Declaration::Function(Box::new(self.parse_function_declaration(false)?)))
+                } else if self.at(TokenKind::Interface) {
+                    ExportDefault::Declaration(self.parse_declaration()?)
+                } else {
+                    let expression = self.parse_assignment_expression()?;
+                    self.consume_semicolon()?;
+                    ExportDefault::Expression(expression)
+                };
+                Ok(Declaration::ExportDefault(Box::new(ExportDefaultDeclaration {
+                    value,
+                    span: self.span_from(start),
+                })))
+            }
+            TokenKind::Star | TokenKind::LeftBrace => self.parse_export_clause(start, false),
+            _ if self.at_contextual(ContextualKeyword::As)
+                && self.peek_token().is_some_and(|token| token.is_contextual_keyword(ContextualKeyword::Namespace)) =>
+            {
+                self.advance();
+                self.advance();
+                let name = self.parse_identifier()?;
+                self.consume_semicolon()?;
+                Ok(Declaration::NamespaceExport(NamespaceExportDeclaration {
+                    name,
+                    span: self.span_from(start),
+                }))
+            }
+            _ if self.at_contextual(ContextualKeyword::Type)
+                && matches!(self.peek_kind(0), TokenKind::LeftBrace | TokenKind::Star) =>
+            {
+                self.advance();
+                self.parse_export_clause(start, true)
+            }
+            _ => {
+                let declaration = self.parse_declaration()?;
+                Ok(Declaration::Export(Box::new(ExportDeclaration {
+                    declaration: Some(Box::new(declaration)),
+                    specifiers: Vec::new(),
+                    source: None,
+                    is_type_only: false,
+                    attributes: None,
+                    span: self.span_from(start),
+                })))
+            }
+        }
+    }
+
+    /// Parse `* from "m"`, `* as ns from "m"` or `{ a, b as c } [from "m"]` after `export`
+    fn parse_export_clause(&mut self, start: Position, is_type_only: bool) -> ParseResult<Declaration> {
+        let mut specifiers = Vec::new();
+        let source = if self.eat(TokenKind::Star) {
+            if self.eat_contextual(ContextualKeyword::As) {
+                specifiers.push(ExportSpecifier::Namespace(self.parse_module_export_name()?));
+            } else {
+                specifiers.push(ExportSpecifier::All);
+            }
+            if !self.eat_contextual(ContextualKeyword::From) {
+                return Err(self.expected("from"));
+            }
+            Some(self.parse_module_specifier()?)
+        } else {
+            self.expect(TokenKind::LeftBrace)?;
+            while !self.at(TokenKind::RightBrace) {
+                let specifier_start = self.start();
+                let is_type_only = self.at_type_specifier_modifier();
+                if is_type_only {
+                    self.advance();
+                }
+                let local = self.parse_module_export_name()?;
+                let exported = if self.eat_contextual(ContextualKeyword::As) {
+                    Some(self.parse_module_export_name()?)
+                } else {
+                    None
+                };
+                specifiers.push(ExportSpecifier::Named(NamedExportSpecifier {
+                    local,
+                    exported,
+                    is_type_only,
+                    span: self.span_from(specifier_start),
+                }));
+                if !self.eat(TokenKind::Comma) {
+                    break;
+                }
+            }
+            self.expect(TokenKind::RightBrace)?;
+            if self.eat_contextual(ContextualKeyword::From) {
+                Some(self.parse_module_specifier()?)
+            } else {
+                None
+            }
+        };
+        let attributes = self.parse_import_attributes()?;
+        self.consume_semicolon()?;
+
+        Ok(Declaration::Export(Box::new(ExportDeclaration {
+            declaration: None,
+            specifiers,
+            source,
+            is_type_only,
+            attributes,
+            span: self.span_from(start),
+        })))
+    }
+}
+
+/// Mark a declaration written after `declare` as ambient, extending its span over
+/// the modifier. Declarations that cannot be ambient are left as they are.
+fn mark_declare(declaration: &mut Declaration, span: Span) {
+    let (is_declare, declaration_span) = match declaration {
+        Declaration::Function(function) => (&mut function.is_declare, &mut function.span),
+        Declaration::Variable(variable) => (&mut variable.is_declare, &mut variable.span),
+        Declaration::Class(class) => (&mut class.is_declare, &mut class.span),
+        Declaration::Interface(interface) => (&mut interface.is_declare, &mut interface.span),
+        Declaration::Type(alias) => (&mut alias.is_declare, &mut alias.span),
+        Declaration::Enum(enumeration) => (&mut enumeration.is_declare, &mut enumeration.span),
+        Declaration::Namespace(namespace) => (&mut namespace.is_declare, &mut namespace.span),
+        _ => return,
+    };
+    *is_declare = true;
+    *declaration_span = span;
+}
@@ -0,0 +1,1216 @@
+//! Expression parsing
+//!
+//! Binary operators are parsed by precedence climbing: [`Parser::parse_binary_expression`]
+//! keeps consuming operators that bind tighter than the level it was called at.
+//! Everything that binds tighter than `**` is parsed by recursive descent.
+
+use super::{ParseResult, Parser};
+use crate::ast::{
+    ArrayExpression, ArrowFunctionBody, ArrowFunctionExpression, AsExpression, AssignmentExpression,
+    AssignmentOperator, AssignmentTarget, AwaitExpression, BinaryExpression, BinaryOperator,
+    BindingPattern, CallExpression, ConditionalExpression, Expression, FunctionExpression,
+    Identifier, InstantiationExpression, Literal, LiteralExpression, MemberExpression, MetaProperty,
+    Modifiers, NewExpression, NonNullExpression, ObjectExpression, ObjectMember, ObjectProperty,
+    OptionalChainExpression, Parameter, ParenthesizedExpression, PropertyKind, SatisfiesExpression,
+    SequenceExpression, SpreadElement, TaggedTemplateExpression, TemplateElement, TemplateLiteral,
+    TypeAssertionExpression, TypeNode, UnaryExpression, UnaryOperator, YieldExpression,
+};
+use crate::lexer::{ContextualKeyword, TokenKind};
+use crate::utils::intern::resolve;
+use crate::utils::span::Position;
+
+/// Binding power of binary operators, from loosest to tightest. `as` and
+/// `satisfies` bind like the relational operators.
+mod precedence {
+    pub const LOWEST: u8 = 0;
+    pub const COALESCE: u8 = 1;
+    pub const LOGICAL_OR: u8 = 2;
+    pub const LOGICAL_AND: u8 = 3;
+    pub const BITWISE_OR: u8 = 4;
+    pub const BITWISE_XOR: u8 = 5;
+    pub const BITWISE_AND: u8 = 6;
+    pub const EQUALITY: u8 = 7;
+    pub const RELATIONAL: u8 = 8;
+    pub const SHIFT: u8 = 9;
+    pub const ADDITIVE: u8 = 10;
+    pub const MULTIPLICATIVE: u8 = 11;
+    pub const EXPONENTIATION: u8 = 12;
+}
+
+impl<'a> Parser<'a> {
+    /// Parse an expression, including comma-separated sequences
+    pub(super) fn parse_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let first = self.parse_assignment_expression()?;
+        if !self.at(TokenKind::Comma) {
+            return Ok(first);
+        }
+        let mut expressions = vec![first];
+        while self.eat(TokenKind::Comma) {
+            expressions.push(self.parse_assignment_expression()?);
+        }
+        Ok(Expression::Sequence(SequenceExpression {
+            expressions,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse an assignment, conditional, arrow function, `yield` or binary expression
+    pub(super) fn parse_assignment_expression(&mut self) -> ParseResult<Expression> {
+        self.parse_assignment_expression_or_higher(true)
+    }
+
+    /// Parse an assignment expression. Where an arrow function's return type
+    /// is not allowed, as in the true branch of a conditional, `(a): T => b` is
+    /// only read as an arrow function if a `:` follows it, like tsc's
+    /// `allowReturnTypeInArrowFunction`.
+    fn parse_assignment_expression_or_higher(&mut self, allow_return_type: bool) -> ParseResult<Expression> {
+        self.nested(|parser| parser.parse_assignment_expression_worker(allow_return_type))
+    }
+
+    fn parse_assignment_expression_worker(&mut self, allow_return_type: bool) -> ParseResult<Expression> {
+        if self.is_yield_expression() {
+            return self.parse_yield_expression();
+        }
+        if let Some(arrow) = self.try_parse_arrow_function(allow_return_type)? {
+            return Ok(arrow);
+        }
+
+        let start = self.start();
+        let left = self.parse_binary_expression(precedence::LOWEST)?;
+        self.rescan_greater();
+        if let Some(operator) = assignment_operator(self.kind()) {
+            self.advance();
+            let left = if operator == AssignmentOperator::Assign {
+                self.reinterpret_as_assignment_target(left)
+            } else {
+                self.simple_assignment_target(left)
+            };
+            let right = self.parse_assignment_expression()?;
+            return Ok(Expression::Assignment(Box::new(AssignmentExpression {
+                left,
+                operator,
+                right: Box::new(right),
+                span: self.span_from(start),
+            })));
+        }
+
+        if self.eat(TokenKind::Question) {
+            // `x ? (a) : b => c` is `x ? a : (b => c)`
+            let consequent = self.with_in_operator(true, |parser| parser.parse_assignment_expression_or_higher(false))?;
+            self.expect(TokenKind::Colon)?;
+            let alternate = self.parse_assignment_expression_or_higher(allow_return_type)?;
+            return Ok(Expression::Conditional(ConditionalExpression {
+                test: Box::new(left),
+                consequent: Box::new(consequent),
+                alternate: Box::new(alternate),
+                span: self.span_from(start),
+            }));
+        }
+
+        Ok(left)
+    }
+
+    /// Check whether `yield` starts a yield expression. It always does in a
+    /// generator. Outside one it still does when an operand follows on the same
+    /// line, so that the misuse is reported as such rather than as a stray identifier.
+    fn is_yield_expression(&mut self) -> bool {
+        self.at(TokenKind::Yield) && (self.in_generator || self.next_is_operand_on_same_line())
+    }
+
+    /// Check whether `await` starts an await expression, by the same rule as
+    /// `yield` with async functions in place of generators
+    fn is_await_expression(&mut self) -> bool {
+        self.at_contextual(ContextualKeyword::Await) && (self.in_async || self.next_is_operand_on_same_line())
+    }
+
+    /// Run `parse` with the `in` operator allowed or disallowed. It is
+    /// disallowed in the initializer of a `for` statement and allowed again
+    /// inside brackets and function bodies, which also end a decorator's
+    /// restriction on element access.
+    pub(super) fn with_in_operator<T>(
+        &mut self,
+        allowed: bool,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let saved = (self.disallow_in, self.in_decorator);
+        self.disallow_in = !allowed;
+        self.in_decorator = false;
+        let result = parse(self);
+        (self.disallow_in, self.in_decorator) = saved;
+        result
+    }
+
+    /// Parse the expression of a decorator, where `[` ends the expression
+    /// instead of starting an element access, so that `@dec [key]() {}`
+    /// decorates a computed member
+    pub(super) fn parse_decorator_expression(&mut self) -> ParseResult<Expression> {
+        let saved = self.in_decorator;
+        self.in_decorator = true;
+        let result = self.parse_left_hand_side_expression();
+        self.in_decorator = saved;
+        result
+    }
+
+    /// Run `parse` for the parameters and body of a function, which decide
+    /// whether `await` and `yield` are operators inside it
+    pub(super) fn with_function_context<T>(
+        &mut self,
+        is_async: bool,
+        is_generator: bool,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let saved = (self.in_async, self.in_generator, self.in_function, self.disallow_in);
+        self.in_async = is_async;
+        self.in_generator = is_generator;
+        self.in_function = true;
+        self.disallow_in = false;
+        let result = parse(self);
+        (self.in_async, self.in_generator, self.in_function, self.disallow_in) = saved;
+        result
+    }
+
+    /// Check whether the next token is a word or literal on the same line
+    fn next_is_operand_on_same_line(&mut self) -> bool {
+        let Some(token) = self.peek_token() else {
+            return false;
+        };
+        !token.preceded_by_newline
+            && (matches!(
+                token.kind,
+                TokenKind::Identifier(_) | TokenKind::Number(_) | TokenKind::BigInt(_)
+                    | TokenKind::String(_) | TokenKind::NoSubstitutionTemplate { .. }
+                    | TokenKind::TemplateHead { .. } | TokenKind::Boolean(_) | TokenKind::Null
+            ) || token.kind.text().is_some_and(|text| text.starts_with(char::is_alphabetic)))
+    }
+
+    /// Parse `yield`, `yield value` or `yield* value`
+    fn parse_yield_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        self.expect(TokenKind::Yield)?;
+        let same_line = !self.current_token.preceded_by_newline;
+        let delegate = same_line && self.eat(TokenKind::Star);
+        let argument = if delegate || (same_line && self.is_start_of_expression()) {
+            Some(Box::new(self.parse_assignment_expression()?))
+        } else {
+            None
+        };
+        Ok(Expression::Yield(YieldExpression {
+            argument,
+            delegate,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Check whether the current token can begin an expression
+    fn is_start_of_expression(&self) -> bool {
+        match self.kind() {
+            TokenKind::This | TokenKind::Super | TokenKind::Null | TokenKind::Boolean(_)
+            | TokenKind::Number(_) | TokenKind::BigInt(_) | TokenKind::String(_)
+            | TokenKind::NoSubstitutionTemplate { .. } | TokenKind::TemplateHead { .. }
+            | TokenKind::LeftParen | TokenKind::LeftBracket | TokenKind::LeftBrace
+            | TokenKind::Function | TokenKind::Class | TokenKind::New | TokenKind::Import
+            | TokenKind::Slash | TokenKind::SlashEqual | TokenKind::Plus | TokenKind::Minus
+            | TokenKind::Tilde | TokenKind::Bang | TokenKind::Delete | TokenKind::Typeof
+            | TokenKind::Void | TokenKind::PlusPlus | TokenKind::MinusMinus | TokenKind::Less
+            | TokenKind::Yield | TokenKind::Hash | TokenKind::DotDotDot => true,
+            _ => self.is_identifier(),
+        }
+    }
+
+    /// Parse an arrow function if one starts here: `x => ...`, `(params) => ...`
+    /// or `<T>(params) => ...`, each optionally `async`
+    fn try_parse_arrow_function(&mut self, allow_return_type: bool) -> ParseResult<Option<Expression>> {
+        let start = self.start();
+        let checkpoint = (!allow_return_type).then(|| self.checkpoint());
+        // `async` only modifies an arrow function whose parameters follow on the same line
+        let is_async = self.at_contextual(ContextualKeyword::Async)
+            && !self.next_starts_line()
+            && match self.peek_kind(0) {
+                TokenKind::LeftParen | TokenKind::Less => true,
+                TokenKind::Identifier(_) => self.peek_kind(1) == TokenKind::Arrow,
+                _ => false,
+            };
+        let single_parameter = if is_async {
+            matches!(self.peek_kind(0), TokenKind::Identifier(_))
+        } else {
+            self.is_identifier() && self.peek_kind(0) == TokenKind::Arrow
+        };
+        let (type_parameters, parameters, return_type) = if single_parameter {
+            if is_async {
+                self.advance();
+            }
+            let name = self.parse_identifier()?;
+            (Vec::new(), vec![Parameter {
+                decorators: Vec::new(),
+                span: name.span,
+                name: BindingPattern::Identifier(name),
+                modifiers: Modifiers::default(),
+                type_annotation: None,
+                optional: false,
+                rest: false,
+                initializer: None,
+            }], None)
+        } else if (is_async
+            || self.at(TokenKind::LeftParen)
+            || (self.at(TokenKind::Less) && (!self.jsx || self.look_ahead(Self::is_generic_arrow_in_jsx))))
+            && !self.not_parenthesized_arrow.contains(&start.offset)
+        {
+            let signature = self.try_parse(|parser| {
+                if is_async {
+                    parser.advance();
+                }
+                let type_parameters = parser.parse_type_parameters()?;
+                let parameters = parser.parse_parameter_list()?;
+                let return_type = parser.parse_optional_return_type()?;
+                if parser.at(TokenKind::Arrow) {
+                    Ok((type_parameters, parameters, return_type))
+                } else {
+                    Err(parser.expected("=>"))
+                }
+            });
+            match signature {
+                Some(signature) => signature,
+                None => {
+                    self.not_parenthesized_arrow.insert(start.offset);
+                    return Ok(None);
+                }
+            }
+        } else {
+            return Ok(None);
+        };
+
+        if self.current_token.preceded_by_newline {
+            let span = self.current_token.span;
+            self.report(1200, "Line terminator not permitted before arrow.", span);
+        }
+        self.expect(TokenKind::Arrow)?;
+        let body = match checkpoint {
+            Some(checkpoint) if return_type.is_some() => {
+                let body = self.try_parse(|parser| {
+                    let body = parser.parse_arrow_function_body(is_async, false)?;
+                    if parser.at(TokenKind::Colon) {
+                        Ok(body)
+                    } else {
+                        Err(parser.expected(":"))
+                    }
+                });
+                match body {
+                    Some(body) => body,
+                    None => {
+                        self.rewind(checkpoint);
+                        self.not_parenthesized_arrow.insert(start.offset);
+                        return Ok(None);
+                    }
+                }
+            }
+            _ => self.parse_arrow_function_body(is_async, allow_return_type)?,
+        };
+        Ok(Some(Expression::Arrow(Box::new(ArrowFunctionExpression {
+            is_async,
+            type_parameters,
+            parameters,
+            return_type,
+            body: Box::new(body),
+            span: self.span_from(start),
+        }))))
+    }
+
+    fn parse_arrow_function_body(&mut self, is_async: bool, allow_return_type: bool) -> ParseResult<ArrowFunctionBody> {
+        if self.at(TokenKind::LeftBrace) {
+            let body = self.with_function_context(is_async, false, Self::parse_block)?;
+            return Ok(ArrowFunctionBody::Block(body));
+        }
+        let expression = self.with_function_context(is_async, false, |parser| {
+            parser.parse_assignment_expression_or_higher(allow_return_type)
+        })?;
+        Ok(ArrowFunctionBody::Expression(Box::new(expression)))
+    }
+
+    /// Check whether `<` starts the type parameters of an arrow function in a
+    /// `.tsx` file, where `<T>` opens an element. Only `<T,>`, `<T = D>` and
+    /// `<T extends C>` are type parameters, like tsc's check in
+    /// `isParenthesizedArrowFunctionExpressionWorker`.
+    fn is_generic_arrow_in_jsx(&mut self) -> bool {
+        self.advance();
+        self.eat(TokenKind::Const);
+        if !self.is_identifier() {
+            return false;
+        }
+        self.advance();
+        match self.kind() {
+            // `<T extends>`, `<T extends={x}>` and `<T extends/>` are elements with an `extends` attribute
+            TokenKind::Extends => !matches!(self.peek_kind(0), TokenKind::Equal | TokenKind::Greater | TokenKind::Slash),
+            TokenKind::Comma | TokenKind::Equal => true,
+            _ => false,
+        }
+    }
+
+    /// Parse a unary operand followed by any binary operators that bind tighter
+    /// than `precedence`
+    fn parse_binary_expression(&mut self, precedence: u8) -> ParseResult<Expression> {
+        let start = self.start();
+        let mut left = self.parse_unary_expression()?;
+        loop {
+            self.rescan_greater();
+
+            if (self.at_contextual(ContextualKeyword::As) || self.at_contextual(ContextualKeyword::Satisfies))
+                && !self.current_token.preceded_by_newline
+            {
+                if precedence::RELATIONAL <= precedence {
+                    return Ok(left);
+                }
+                let is_as = self.advance().is_contextual_keyword(ContextualKeyword::As);
+                let type_annotation = self.parse_type()?;
+                let expression = Box::new(left);
+                let span = self.span_from(start);
+                left = if is_as {
+                    Expression::As(Box::new(AsExpression { expression, type_annotation, span }))
+                } else {
+                    Expression::Satisfies(Box::new(SatisfiesExpression { expression, type_annotation, span }))
+                };
+                continue;
+            }
+
+            let Some((operator, operator_precedence)) = binary_operator(self.kind()) else {
+                return Ok(left);
+            };
+            if operator == BinaryOperator::In && self.disallow_in {
+                return Ok(left);
+            }
+            // `**` is right-associative; every other operator is left-associative
+            let binds = if operator == BinaryOperator::Exponent {
+                operator_precedence >= precedence
+            } else {
+                operator_precedence > precedence
+            };
+            if !binds {
+                return Ok(left);
+            }
+
+            if operator == BinaryOperator::Exponent {
+                self.check_exponentiation_operand(&left);
+            }
+            self.advance();
+            let right = self.nested(|parser| parser.parse_binary_expression(operator_precedence))?;
+            if operator == BinaryOperator::NullishCoalescing {
+                self.check_nullish_operand(&left);
+                self.check_nullish_operand(&right);
+            }
+            left = Expression::Binary(BinaryExpression {
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+                span: self.span_from(start),
+            });
+        }
+    }
+
+    /// Report a unary expression written without parentheses on the left of `**`,
+    /// as in `-x ** 2`
+    fn check_exponentiation_operand(&mut self, left: &Expression) {
+        let operator = match left {
+            Expression::Unary(unary) => match unary.operator {
+                UnaryOperator::Plus => "+",
+                UnaryOperator::Minus => "-",
+                UnaryOperator::LogicalNot => "!",
+                UnaryOperator::BitwiseNot => "~",
+                UnaryOperator::Typeof => "typeof",
+                UnaryOperator::Void => "void",
+                UnaryOperator::Delete => "delete",
+                _ => return,
+            },
+            Expression::Await(_) => "await",
+            Expression::TypeAssertion(assertion) => {
+                self.report(
+                    17007,
+                    "A type assertion expression is not allowed in the left-hand side of an exponentiation expression. Consider enclosing the expression in parentheses.",
+                    assertion.span,
+                );
+                return;
+            }
+            _ => return,
+        };
+        self.report(
+            17006,
+            &format!(
+                "An unary expression with the '{}' operator is not allowed in the left-hand side of an exponentiation expression. Consider enclosing the expression in parentheses.",
+                operator
+            ),
+            left.span(),
+        );
+    }
+
+    /// Report an operand of `??` that is an `||` or `&&` expression without parentheses
+    fn check_nullish_operand(&mut self, operand: &Expression) {
+        let Expression::Binary(binary) = operand else {
+            return;
+        };
+        let operator = match binary.operator {
+            BinaryOperator::LogicalOr => "||",
+            BinaryOperator::LogicalAnd => "&&",
+            _ => return,
+        };
+        self.report(
+            5076,
+            &format!("'{}' and '??' operations cannot be mixed without parentheses.", operator),
+            binary.span,
+        );
+    }
+
+    /// Parse a prefix unary expression, `await`, a type assertion, or a postfix
+    /// increment or decrement
+    fn parse_unary_expression(&mut self) -> ParseResult<Expression> {
+        self.nested(Self::parse_unary_expression_worker)
+    }
+
+    fn parse_unary_expression_worker(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let operator = match self.kind() {
+            TokenKind::Plus => Some(UnaryOperator::Plus),
+            TokenKind::Minus => Some(UnaryOperator::Minus),
+            TokenKind::Bang => Some(UnaryOperator::LogicalNot),
+            TokenKind::Tilde => Some(UnaryOperator::BitwiseNot),
+            TokenKind::Typeof => Some(UnaryOperator::Typeof),
+            TokenKind::Void => Some(UnaryOperator::Void),
+            TokenKind::Delete => Some(UnaryOperator::Delete),
+            TokenKind::PlusPlus => Some(UnaryOperator::PreIncrement),
+            TokenKind::MinusMinus => Some(UnaryOperator::PreDecrement),
+            _ => None,
+        };
+        if let Some(operator) = operator {
+            self.advance();
+            let operand = self.parse_unary_expression()?;
+            return Ok(Expression::Unary(UnaryExpression {
+                operator,
+                operand: Box::new(operand),
+                span: self.span_from(start),
+            }));
+        }
+
+        if self.is_await_expression() {
+            self.advance();
+            let argument = self.parse_unary_expression()?;
+            return Ok(Expression::Await(AwaitExpression {
+                argument: Box::new(argument),
+                span: self.span_from(start),
+            }));
+        }
+
+        if self.at(TokenKind::Less) && self.jsx {
+            return self.parse_jsx_element_or_fragment(true);
+        }
+        if self.at(TokenKind::Less) {
+            // Type assertion: `<T>expression`
+            self.advance();
+            let type_annotation = self.parse_type()?;
+            self.expect(TokenKind::Greater)?;
+            let expression = self.parse_unary_expression()?;
+            return Ok(Expression::TypeAssertion(Box::new(TypeAssertionExpression {
+                type_annotation,
+                expression: Box::new(expression),
+                span: self.span_from(start),
+            })));
+        }
+
+        let operand = self.parse_left_hand_side_expression()?;
+        // No line break is allowed before a postfix operator, so `a\n++b` is `a; ++b`
+        if self.current_token.preceded_by_newline {
+            return Ok(operand);
+        }
+        let operator = match self.kind() {
+            TokenKind::PlusPlus => UnaryOperator::PostIncrement,
+            TokenKind::MinusMinus => UnaryOperator::PostDecrement,
+            _ => return Ok(operand),
+        };
+        self.advance();
+        Ok(Expression::Unary(UnaryExpression {
+            operator,
+            operand: Box::new(operand),
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse a primary, `new`, `super` or `import` expression followed by member
+    /// accesses, calls and tagged templates
+    pub(super) fn parse_left_hand_side_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let expression = match self.kind() {
+            TokenKind::New => self.parse_new_expression()?,
+            TokenKind::Super => {
+                self.advance();
+                if !matches!(self.kind(), TokenKind::LeftParen | TokenKind::Dot | TokenKind::LeftBracket) {
+                    let span = self.current_token.span;
+                    self.report(1034, "'super' must be followed by an argument list or member access.", span);
+                }
+                Expression::Super(self.span_from(start))
+            }
+            TokenKind::Import => self.parse_import_expression()?,
+            _ => self.parse_primary_expression()?,
+        };
+        self.parse_member_expression_rest(start, expression, true)
+    }
+
+    /// Parse `import.meta`, or the `import` of a dynamic `import("module")` call.
+    /// The callee of a dynamic import is represented as an identifier named `import`.
+    fn parse_import_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let keyword = self.expect(TokenKind::Import)?;
+        let meta = self.identifier_from(&keyword);
+        if self.eat(TokenKind::Dot) {
+            let property = self.parse_identifier_name()?;
+            return Ok(Expression::MetaProperty(Box::new(MetaProperty {
+                meta,
+                property,
+                span: self.span_from(start),
+            })));
+        }
+        if !self.at(TokenKind::LeftParen) {
+            return Err(self.expected("("));
+        }
+        Ok(Expression::Identifier(meta))
+    }
+
+    /// Parse `new.target`, or `new Callee` with optional arguments
+    fn parse_new_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let keyword = self.expect(TokenKind::New)?;
+        if self.eat(TokenKind::Dot) {
+            let meta = self.identifier_from(&keyword);
+            let property = self.parse_identifier_name()?;
+            return Ok(Expression::MetaProperty(Box::new(MetaProperty {
+                meta,
+                property,
+                span: self.span_from(start),
+            })));
+        }
+
+        let callee_start = self.start();
+        let callee = if self.at(TokenKind::New) {
+            self.parse_new_expression()?
+        } else {
+            self.parse_primary_expression()?
+        };
+        let mut callee = self.parse_member_expression_rest(callee_start, callee, false)?;
+        if let Expression::OptionalChain(chain) = &callee {
+            let text = &self.source[chain.span.start.offset..chain.span.end.offset];
+            let message = format!("Invalid optional chain from new expression. Did you mean to call '{}()'?", text);
+            self.report(1209, &message, chain.span);
+        }
+
+        // `new C<T>()`: the type arguments were parsed as an instantiation expression
+        let mut type_arguments = Vec::new();
+        if let Expression::Instantiation(instantiation) = callee {
+            type_arguments = instantiation.type_arguments;
+            callee = *instantiation.expression;
+        }
+
+        let arguments = if self.at(TokenKind::LeftParen) {
+            Some(self.parse_arguments()?)
+        } else {
+            None
+        };
+        Ok(Expression::New(Box::new(NewExpression {
+            callee: Box::new(callee),
+            type_arguments,
+            arguments,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse the member accesses, non-null assertions, tagged templates and, if
+    /// `allow_calls` is set, calls following an expression. A chain containing
+    /// `?.` is wrapped in an optional chain expression.
+    fn parse_member_expression_rest(
+        &mut self,
+        start: Position,
+        mut expression: Expression,
+        allow_calls: bool,
+    ) -> ParseResult<Expression> {
+        let mut in_optional_chain = false;
+        loop {
+            let optional = self.at(TokenKind::QuestionDot);
+            if optional {
+                self.advance();
+                in_optional_chain = true;
+            }
+
+            expression = match self.kind() {
+                TokenKind::LeftBracket if optional || !self.in_decorator => {
+                    self.advance();
+                    let property = self.with_in_operator(true, Self::parse_expression)?;
+                    self.expect(TokenKind::RightBracket)?;
+                    Expression::Member(MemberExpression {
+                        object: Box::new(expression),
+                        property: Box::new(property),
+                        computed: true,
+                        optional,
+                        span: self.span_from(start),
+                    })
+                }
+                TokenKind::LeftParen if allow_calls || optional => {
+                    let arguments = self.parse_arguments()?;
+                    Expression::Call(Box::new(CallExpression {
+                        callee: Box::new(expression),
+                        type_arguments: Vec::new(),
+                        arguments,
+                        optional,
+                        span: self.span_from(start),
+                    }))
+                }
+                TokenKind::Less => {
+                    let Some(type_arguments) = self.try_parse(Self::parse_type_arguments_in_expression) else {
+                        break;
+                    };
+                    match self.kind() {
+                        TokenKind::LeftParen if allow_calls || optional => {
+                            let arguments = self.parse_arguments()?;
+                            Expression::Call(Box::new(CallExpression {
+                                callee: Box::new(expression),
+                                type_arguments,
+                                arguments,
+                                optional,
+                                span: self.span_from(start),
+                            }))
+                        }
+                        TokenKind::NoSubstitutionTemplate { .. } | TokenKind::TemplateHead { .. } => {
+                            let quasi = self.parse_template_literal(true)?;
+                            Expression::TaggedTemplate(Box::new(TaggedTemplateExpression {
+                                tag: Box::new(expression),
+                                type_arguments,
+                                quasi,
+                                span: self.span_from(start),
+                            }))
+                        }
+                        _ if optional => return Err(self.expected("(")),
+                        _ => Expression::Instantiation(InstantiationExpression {
+                            expression: Box::new(expression),
+                            type_arguments,
+                            span: self.span_from(start),
+                        }),
+                    }
+                }
+                _ if optional || self.at(TokenKind::Dot) => {
+                    if !optional {
+                        self.advance();
+                    }
+                    let property = self.parse_member_name_after_dot()?;
+                    Expression::Member(MemberExpression {
+                        object: Box::new(expression),
+                        property: Box::new(Expression::Identifier(property)),
+                        computed: false,
+                        optional,
+                        span: self.span_from(start),
+                    })
+                }
+                TokenKind::Bang if !self.current_token.preceded_by_newline => {
+                    self.advance();
+                    Expression::NonNull(NonNullExpression {
+                        expression: Box::new(expression),
+                        span: self.span_from(start),
+                    })
+                }
+                TokenKind::NoSubstitutionTemplate { .. } | TokenKind::TemplateHead { .. } => {
+                    if in_optional_chain {
+                        let span = self.current_token.span;
+                        self.report(1358, "Tagged template expressions are not permitted in an optional chain.", span);
+                    }
+                    let quasi = self.parse_template_literal(true)?;
+                    Expression::TaggedTemplate(Box::new(TaggedTemplateExpression {
+                        tag: Box::new(expression),
+                        type_arguments: Vec::new(),
+                        quasi,
+                        span: self.span_from(start),
+                    }))
+                }
+                _ => break,
+            };
+        }
+
+        if in_optional_chain {
+            expression = Expression::OptionalChain(OptionalChainExpression {
+                span: self.span_from(start),
+                expression: Box::new(expression),
+            });
+        }
+        Ok(expression)
+    }
+
+    /// Parse `<A, B>` after an expression, like tsc's `parseTypeArgumentsInExpression`.
+    /// Fails, so that the caller can rewind and read `<` as less-than, unless the
+    /// list is closed by a lone `>` and followed by something that cannot
+    /// continue a comparison, as in `f<T>(x)` but not `a < b > c`.
+    fn parse_type_arguments_in_expression(&mut self) -> ParseResult<Vec<TypeNode>> {
+        self.expect(TokenKind::Less)?;
+        let mut type_arguments = Vec::new();
+        while !self.at(TokenKind::Greater) {
+            type_arguments.push(self.parse_type()?);
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        // `a < b >= c` and `a < b >> c` end in an operator, not a type argument list
+        self.rescan_greater();
+        self.expect(TokenKind::Greater)?;
+        if self.can_follow_type_arguments_in_expression() {
+            Ok(type_arguments)
+        } else {
+            Err(self.expected("("))
+        }
+    }
+
+    fn can_follow_type_arguments_in_expression(&self) -> bool {
+        match self.kind() {
+            TokenKind::LeftParen | TokenKind::NoSubstitutionTemplate { .. } | TokenKind::TemplateHead { .. } => true,
+            TokenKind::Less | TokenKind::Greater | TokenKind::Plus | TokenKind::Minus => false,
+            kind => {
+                self.current_token.preceded_by_newline
+                    || binary_operator(kind).is_some()
+                    || self.at_contextual(ContextualKeyword::As)
+                    || self.at_contextual(ContextualKeyword::Satisfies)
+                    || !self.is_start_of_expression()
+            }
+        }
+    }
+
+    /// Parse the name after `.` or `?.`, which may be any word or a private `#name`
+    fn parse_member_name_after_dot(&mut self) -> ParseResult<Identifier> {
+        let start = self.start();
+        if self.eat(TokenKind::Hash) {
+            let name = self.parse_identifier_name()?;
+            return Ok(Identifier {
+                name: format!("#{}", name.name),
+                span: self.span_from(start),
+            });
+        }
+        self.parse_identifier_name()
+    }
+
+    /// Parse a parenthesized, comma-separated argument list
+    fn parse_arguments(&mut self) -> ParseResult<Vec<Expression>> {
+        self.expect(TokenKind::LeftParen)?;
+        let mut arguments = Vec::new();
+        while !self.at(TokenKind::RightParen) {
+            arguments.push(self.with_in_operator(true, Self::parse_spread_or_assignment_expression)?);
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightParen)?;
+        Ok(arguments)
+    }
+
+    /// Parse `...expression` or an assignment expression
+    fn parse_spread_or_assignment_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        if self.eat(TokenKind::DotDotDot) {
+            let argument = self.parse_assignment_expression()?;
+            return Ok(Expression::Spread(SpreadElement {
+                argument: Box::new(argument),
+                span: self.span_from(start),
+            }));
+        }
+        self.parse_assignment_expression()
+    }
+
+    fn parse_primary_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let literal = match self.kind() {
+            TokenKind::Number(value) => Literal::Number(value),
+            TokenKind::String(value) => Literal::String(resolve(value).unwrap_or_default()),
+            TokenKind::BigInt(digits) => Literal::BigInt(resolve(digits).unwrap_or_default()),
+            TokenKind::Boolean(value) => Literal::Boolean(value),
+            TokenKind::Null => Literal::Null,
+            TokenKind::Slash | TokenKind::SlashEqual => {
+                match self.lexer.rescan_slash_as_regex() {
+                    Some(token) => self.current_token = token,
+                    None => return Err(self.error(1109, "Expression expected.")),
+                }
+                match self.kind() {
+                    TokenKind::RegularExpression { pattern, flags } => Literal::RegExp {
+                        pattern: resolve(pattern).unwrap_or_default(),
+                        flags: resolve(flags).unwrap_or_default(),
+                    },
+                    _ => return Err(self.error(1109, "Expression expected.")),
+                }
+            }
+            TokenKind::This => {
+                self.advance();
+                return Ok(Expression::This(self.span_from(start)));
+            }
+            TokenKind::LeftParen => {
+                self.advance();
+                let expression = self.with_in_operator(true, Self::parse_expression)?;
+                self.expect(TokenKind::RightParen)?;
+                return Ok(Expression::Parenthesized(ParenthesizedExpression {
+                    expression: Box::new(expression),
+                    span: self.span_from(start),
+                }));
+            }
+            TokenKind::NoSubstitutionTemplate { .. } | TokenKind::TemplateHead { .. } => {
+                return Ok(Expression::Template(self.parse_template_literal(false)?));
+            }
+            TokenKind::LeftBracket => return self.parse_array_literal(),
+            TokenKind::LeftBrace => return self.parse_object_literal(),
+            TokenKind::Function => return self.parse_function_expression(),
+            _ if self.at_async_function() => return self.parse_function_expression(),
+            TokenKind::Class | TokenKind::At => return self.parse_class_expression(),
+            // A private name is only an expression when testing for it: `#x in obj`
+            TokenKind::Hash if self.peek_kind(1) == TokenKind::In => {
+                return Ok(Expression::PrivateName(self.parse_member_name_after_dot()?));
+            }
+            _ if self.is_identifier() => return Ok(Expression::Identifier(self.parse_identifier()?)),
+            _ => return Err(self.error(1109, "Expression expected.")),
+        };
+        self.advance();
+        Ok(Expression::Literal(LiteralExpression {
+            value: literal,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse a template literal, with its substitutions. Invalid escape sequences
+    /// leave the cooked value undefined and are errors only if the template is untagged.
+    fn parse_template_literal(&mut self, tagged: bool) -> ParseResult<TemplateLiteral> {
+        let start = self.start();
+        let mut quasis = Vec::new();
+        let mut expressions = Vec::new();
+        loop {
+            let first = quasis.is_empty();
+            let (cooked, raw, is_tail) = match self.kind() {
+                TokenKind::NoSubstitutionTemplate { cooked, raw } if first => (cooked, raw, true),
+                TokenKind::TemplateHead { cooked, raw } if first => (cooked, raw, false),
+                TokenKind::TemplateMiddle { cooked, raw } if !first => (cooked, raw, false),
+                TokenKind::TemplateTail { cooked, raw } if !first => (cooked, raw, true),
+                _ => return Err(self.expected("}")),
+            };
+            if cooked.is_none() && !tagged {
+                self.report_template_escapes();
+            }
+            let token = self.advance();
+            quasis.push(TemplateElement {
+                cooked: cooked.and_then(resolve),
+                raw: resolve(raw).unwrap_or_default(),
+                span: token.span,
+            });
+            if is_tail {
+                break;
+            }
+            expressions.push(self.with_in_operator(true, Self::parse_expression)?);
+        }
+        Ok(TemplateLiteral {
+            quasis,
+            expressions,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `[a, , ...b]`
+    fn parse_array_literal(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        self.expect(TokenKind::LeftBracket)?;
+        let mut elements = Vec::new();
+        let mut trailing_comma = false;
+        while !self.at(TokenKind::RightBracket) {
+            if self.eat(TokenKind::Comma) {
+                elements.push(None);
+                trailing_comma = true;
+                continue;
+            }
+            elements.push(Some(self.with_in_operator(true, Self::parse_spread_or_assignment_expression)?));
+            trailing_comma = self.eat(TokenKind::Comma);
+            if !trailing_comma {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBracket)?;
+        Ok(Expression::Array(ArrayExpression {
+            elements,
+            trailing_comma,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse `{ a: 1, b, [c]: 2, m() {}, ...d }`
+    fn parse_object_literal(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        self.expect(TokenKind::LeftBrace)?;
+        let mut properties = Vec::new();
+        let mut trailing_comma = false;
+        while !self.at(TokenKind::RightBrace) && !self.at(TokenKind::Eof) {
+            self.parse_delimited_element(&mut properties, |parser| {
+                parser.with_in_operator(true, Self::parse_object_member)
+            })?;
+            trailing_comma = self.at(TokenKind::Comma);
+            if !self.parse_list_comma(true)? {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBrace)?;
+        Ok(Expression::Object(ObjectExpression {
+            properties,
+            trailing_comma,
+            span: self.span_from(start),
+        }))
+    }
+
+    fn parse_object_member(&mut self) -> ParseResult<ObjectMember> {
+        let start = self.start();
+        if self.eat(TokenKind::DotDotDot) {
+            let argument = self.parse_assignment_expression()?;
+            return Ok(ObjectMember::Spread(SpreadElement {
+                argument: Box::new(argument),
+                span: self.span_from(start),
+            }));
+        }
+
+        let is_shorthand = self.is_identifier()
+            && matches!(self.peek_kind(0), TokenKind::Comma | TokenKind::RightBrace | TokenKind::Equal);
+        if is_shorthand {
+            let name = self.parse_identifier()?;
+            let mut value = Expression::Identifier(name.clone());
+            if self.eat(TokenKind::Equal) {
+                // `{ a = 1 }` is only valid if the literal turns out to be a pattern
+                let right = self.parse_assignment_expression()?;
+                value = Expression::Assignment(Box::new(AssignmentExpression {
+                    left: AssignmentTarget::Simple(Box::new(value)),
+                    operator: AssignmentOperator::Assign,
+                    right: Box::new(right),
+                    span: self.span_from(start),
+                }));
+                self.shorthand_initializers.push(self.span_from(start));
+            }
+            return Ok(ObjectMember::Property(Box::new(ObjectProperty {
+                key: Expression::Identifier(name),
+                value,
+                kind: PropertyKind::Init,
+                computed: false,
+                shorthand: true,
+                span: self.span_from(start),
+            })));
+        }
+
+        let is_async = self.at_contextual(ContextualKeyword::Async) && self.next_starts_member_name();
+        if is_async {
+            self.advance();
+        }
+        let is_generator = self.eat(TokenKind::Star);
+        let mut kind = PropertyKind::Init;
+        if !is_async
+            && !is_generator
+            && (self.at_contextual(ContextualKeyword::Get) || self.at_contextual(ContextualKeyword::Set))
+            && !matches!(
+                self.peek_kind(0),
+                TokenKind::LeftParen | TokenKind::Colon | TokenKind::Comma | TokenKind::RightBrace
+            )
+        {
+            kind = if self.at_contextual(ContextualKeyword::Get) {
+                PropertyKind::Get
+            } else {
+                PropertyKind::Set
+            };
+            self.advance();
+        }
+
+        let computed = self.at(TokenKind::LeftBracket);
+        let key = if computed {
+            self.advance();
+            let key = self.parse_assignment_expression()?;
+            self.expect(TokenKind::RightBracket)?;
+            key
+        } else {
+            self.parse_property_key()?
+        };
+
+        let is_function = kind != PropertyKind::Init
+            || is_async
+            || is_generator
+            || matches!(self.kind(), TokenKind::LeftParen | TokenKind::Less);
+        let value = if is_function {
+            // Method shorthand: `m(x) { ... }`, `async m() { ... }` or `*m() { ... }`,
+            // or an accessor
+            if kind == PropertyKind::Init {
+                kind = PropertyKind::Method;
+            }
+            let function_start = self.start();
+            let type_parameters = self.parse_type_parameters()?;
+            let parameters = self.with_function_context(is_async, is_generator, Self::parse_parameter_list)?;
+            let return_type = self.parse_optional_return_type()?;
+            let body = self.with_function_context(is_async, is_generator, Self::parse_block)?;
+            Expression::Function(Box::new(FunctionExpression {
+                name: None,
+                is_async,
+                is_generator,
+                type_parameters,
+                parameters,
+                return_type,
+                body,
+                span: self.span_from(function_start),
+            }))
+        } else {
+            self.expect(TokenKind::Colon)?;
+            self.parse_assignment_expression()?
+        };
+
+        Ok(ObjectMember::Property(Box::new(ObjectProperty {
+            key,
+            value,
+            kind,
+            computed,
+            shorthand: false,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse a literal property key: a word, a string or a number
+    pub(super) fn parse_property_key(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let literal = match self.kind() {
+            TokenKind::String(value) => Literal::String(resolve(value).unwrap_or_default()),
+            TokenKind::Number(value) => Literal::Number(value),
+            _ => return Ok(Expression::Identifier(self.parse_identifier_name()?)),
+        };
+        self.advance();
+        Ok(Expression::Literal(LiteralExpression {
+            value: literal,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse `function name(params): T { ... }` or `function*` in expression position, after
+    /// checking for any `async` modifier with [`Self::at_async_function`]
+    fn parse_function_expression(&mut self) -> ParseResult<Expression> {
+        let start = self.start();
+        let is_async = self.eat_contextual(ContextualKeyword::Async);
+        self.expect(TokenKind::Function)?;
+        let is_generator = self.eat(TokenKind::Star);
+        let name = if self.is_identifier() {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        let type_parameters = self.parse_type_parameters()?;
+        let parameters = self.with_function_context(is_async, is_generator, Self::parse_parameter_list)?;
+        let return_type = self.parse_optional_return_type()?;
+        let body = self.with_function_context(is_async, is_generator, Self::parse_block)?;
+        Ok(Expression::Function(Box::new(FunctionExpression {
+            name,
+            is_async,
+            is_generator,
+            type_parameters,
+            parameters,
+            return_type,
+            body,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse a parenthesized parameter list
+    pub(super) fn parse_parameter_list(&mut self) -> ParseResult<Vec<Parameter>> {
+        // Only this list may be decorated, not those of functions nested in it
+        let allow_decorators = std::mem::take(&mut self.allow_parameter_decorators);
+        self.expect(TokenKind::LeftParen)?;
+        let mut parameters = Vec::new();
+        while !self.at(TokenKind::RightParen) {
+            parameters.push(self.parse_parameter(allow_decorators)?);
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightParen)?;
+        Ok(parameters)
+    }
+
+    /// Parse one parameter such as `x?: number`, `...rest: T[]` or `y = 1`
+    fn parse_parameter(&mut self, allow_decorators: bool) -> ParseResult<Parameter> {
+        let start = self.start();
+        let decorators = self.parse_decorators()?;
+        if !allow_decorators {
+            self.report_invalid_decorators(&decorators);
+        }
+        // Parameter property modifiers such as `constructor(private x: number)`
+        let modifiers = self.parse_modifiers();
+        let rest = self.eat(TokenKind::DotDotDot);
+        let name = if self.at(TokenKind::This) {
+            // `this` parameter declaring the type of `this` in the body
+            let token = self.advance();
+            BindingPattern::Identifier(self.identifier_from(&token))
+        } else {
+            self.parse_binding_name()?
+        };
+        let optional = self.eat(TokenKind::Question);
+        let type_annotation = self.parse_optional_type_annotation()?;
+        let initializer = if self.eat(TokenKind::Equal) {
+            Some(self.parse_assignment_expression()?)
+        } else {
+            None
+        };
+        Ok(Parameter {
+            decorators,
+            name,
+            modifiers,
+            type_annotation,
+            optional,
+            rest,
+            initializer,
+            span: self.span_from(start),
+        })
+    }
+}
+
+/// Get the assignment operator a token spells, if any
+fn assignment_operator(kind: TokenKind) -> Option<AssignmentOperator> {
+    let operator = match kind {
+        TokenKind::Equal => AssignmentOperator::Assign,
+        TokenKind::PlusEqual => AssignmentOperator::AddAssign,
+        TokenKind::MinusEqual => AssignmentOperator::SubtractAssign,
+        TokenKind::StarEqual => AssignmentOperator::MultiplyAssign,
+        TokenKind::SlashEqual => AssignmentOperator::DivideAssign,
+        TokenKind::PercentEqual => AssignmentOperator::ModuloAssign,
+        TokenKind::AmpersandEqual => AssignmentOperator::BitwiseAndAssign,
+        TokenKind::PipeEqual => AssignmentOperator::BitwiseOrAssign,
+        TokenKind::CaretEqual => AssignmentOperator::BitwiseXorAssign,
+        TokenKind::LessLessEqual => AssignmentOperator::LeftShiftAssign,
+        TokenKind::GreaterGreaterEqual => AssignmentOperator::RightShiftAssign,
+        TokenKind::GreaterGreaterGreaterEqual => AssignmentOperator::UnsignedRightShiftAssign,
+        TokenKind::StarStarEqual => AssignmentOperator::ExponentAssign,
+        TokenKind::AmpersandAmpersandEqual => AssignmentOperator::LogicalAndAssign,
+        TokenKind::PipePipeEqual => AssignmentOperator::LogicalOrAssign,
+        TokenKind::QuestionQuestionEqual => AssignmentOperator::NullishAssign,
+        _ => return None,
+    };
+    Some(operator)
+}
+
+/// Get the binary operator a token spells and its precedence, if any
+fn binary_operator(kind: TokenKind) -> Option<(BinaryOperator, u8)> {
+    let operator = match kind {
+        TokenKind::QuestionQuestion => (BinaryOperator::NullishCoalescing, precedence::COALESCE),
+        TokenKind::PipePipe => (BinaryOperator::LogicalOr, precedence::LOGICAL_OR),
+        TokenKind::AmpersandAmpersand => (BinaryOperator::LogicalAnd, precedence::LOGICAL_AND),
+        TokenKind::Pipe => (BinaryOperator::BitwiseOr, precedence::BITWISE_OR),
+        TokenKind::Caret => (BinaryOperator::BitwiseXor, precedence::BITWISE_XOR),
+        TokenKind::Ampersand => (BinaryOperator::BitwiseAnd, precedence::BITWISE_AND),
+        TokenKind::EqualEqual => (BinaryOperator::Equal, precedence::EQUALITY),
+        TokenKind::BangEqual => (BinaryOperator::NotEqual, precedence::EQUALITY),
+        TokenKind::EqualEqualEqual => (BinaryOperator::StrictEqual, precedence::EQUALITY),
+        TokenKind::BangEqualEqual => (BinaryOperator::StrictNotEqual, precedence::EQUALITY),
+        TokenKind::Less => (BinaryOperator::LessThan, precedence::RELATIONAL),
+        TokenKind::LessEqual => (BinaryOperator::LessThanOrEqual, precedence::RELATIONAL),
+        TokenKind::Greater => (BinaryOperator::GreaterThan, precedence::RELATIONAL),
+        TokenKind::GreaterEqual => (BinaryOperator::GreaterThanOrEqual, precedence::RELATIONAL),
+        TokenKind::Instanceof => (BinaryOperator::Instanceof, precedence::RELATIONAL),
+        TokenKind::In => (BinaryOperator::In, precedence::RELATIONAL),
+        TokenKind::LessLess => (BinaryOperator::LeftShift, precedence::SHIFT),
+        TokenKind::GreaterGreater => (BinaryOperator::RightShift, precedence::SHIFT),
+        TokenKind::GreaterGreaterGreater => (BinaryOperator::UnsignedRightShift, precedence::SHIFT),
+        TokenKind::Plus => (BinaryOperator::Add, precedence::ADDITIVE),
+        TokenKind::Minus => (BinaryOperator::Subtract, precedence::ADDITIVE),
+        TokenKind::Star => (BinaryOperator::Multiply, precedence::MULTIPLICATIVE),
+        TokenKind::Slash => (BinaryOperator::Divide, precedence::MULTIPLICATIVE),
+        TokenKind::Percent => (BinaryOperator::Modulo, precedence::MULTIPLICATIVE),
+        TokenKind::StarStar => (BinaryOperator::Exponent, precedence::EXPONENTIATION),
+        _ => return None,
+    };
+    Some(operator)
+}
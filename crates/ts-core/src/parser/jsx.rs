@@ -0,0 +1,350 @@
+//! JSX parsing, in `.tsx` files
+//!
+//! Inside an element the parser switches the lexer between its JSX modes: the
+//! content after a tag or `{expression}` is scanned as JSX text, tag and
+//! attribute names are rescanned to take in `-` and `namespace:`, and quoted
+//! attribute values are scanned without escape sequences.
+
+use super::{ParseResult, Parser};
+use crate::ast::{
+    Expression, Identifier, JsxAttribute, JsxAttributeItem, JsxAttributeName, JsxAttributeValue,
+    JsxChild, JsxClosingElement, JsxElement, JsxExpressionContainer, JsxFragment, JsxMemberName,
+    JsxNamespacedName, JsxOpeningElement, JsxTagName, JsxText, Literal, LiteralExpression,
+    SpreadElement,
+};
+use crate::diagnostics::Diagnostic;
+use crate::lexer::{Token, TokenKind};
+use crate::utils::intern::{resolve, Symbol};
+use crate::utils::span::{Position, Span};
+
+impl<'a> Parser<'a> {
+    /// Parse an element or fragment starting at `<`, like tsc's
+    /// `parseJsxElementOrSelfClosingElementOrFragment`. In expression context,
+    /// as an operand or attribute value, the token after it is scanned as usual;
+    /// as a child of another element it is scanned as JSX child content.
+    pub(super) fn parse_jsx_element_or_fragment(&mut self, in_expression_context: bool) -> ParseResult<Expression> {
+        let element = self.nested(|parser| parser.parse_jsx_element_or_fragment_worker(in_expression_context))?;
+        // `<a /><b />` without a parent: parse the rest so that it is reported once
+        if in_expression_context && self.at(TokenKind::Less) {
+            let start = self.start();
+            if self.try_parse(|parser| parser.parse_jsx_element_or_fragment_worker(true)).is_some() {
+                let span = self.span_from(start);
+                self.report(2657, "JSX expressions must have one parent element.", span);
+            }
+        }
+        Ok(element)
+    }
+
+    fn parse_jsx_element_or_fragment_worker(&mut self, in_expression_context: bool) -> ParseResult<Expression> {
+        let start = self.start();
+        self.expect(TokenKind::Less)?;
+        if self.at(TokenKind::Greater) {
+            self.advance_jsx_child();
+            let children = self.parse_jsx_children(None)?;
+            self.expect(TokenKind::LessSlash)?;
+            if !self.at(TokenKind::Greater) {
+                let span = self.current_token.span;
+                self.report(17015, "Expected corresponding closing tag for JSX fragment.", span);
+                self.parse_jsx_tag_name()?;
+            }
+            self.parse_jsx_tag_end(in_expression_context)?;
+            return Ok(Expression::JsxFragment(JsxFragment {
+                children,
+                span: self.span_from(start),
+            }));
+        }
+
+        let name = self.parse_jsx_tag_name()?;
+        let type_arguments = if self.at(TokenKind::Less) {
+            self.parse_type_arguments()?
+        } else {
+            Vec::new()
+        };
+        let attributes = self.parse_jsx_attributes()?;
+        let self_closing = self.eat(TokenKind::Slash);
+        self.parse_jsx_tag_end(self_closing && in_expression_context)?;
+        let opening = JsxOpeningElement {
+            name,
+            type_arguments,
+            attributes,
+            self_closing,
+            span: self.span_from(start),
+        };
+
+        let (children, closing) = if self_closing {
+            (Vec::new(), None)
+        } else {
+            let children = self.parse_jsx_children(Some(&opening.name))?;
+            let closing = self.parse_jsx_closing_element(&opening.name, in_expression_context)?;
+            (children, Some(closing))
+        };
+        Ok(Expression::JsxElement(Box::new(JsxElement {
+            opening,
+            children,
+            closing,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Consume the `>` ending a tag. Unless `in_expression_context` is set, the
+    /// token after it is the next child of the enclosing element.
+    fn parse_jsx_tag_end(&mut self, in_expression_context: bool) -> ParseResult<()> {
+        if in_expression_context {
+            self.expect(TokenKind::Greater)?;
+        } else {
+            self.expect_then_scan_jsx_child(TokenKind::Greater)?;
+        }
+        Ok(())
+    }
+
+    /// Parse the closing tag of the element named `opening`
+    fn parse_jsx_closing_element(
+        &mut self,
+        opening: &JsxTagName,
+        in_expression_context: bool,
+    ) -> ParseResult<JsxClosingElement> {
+        let start = self.start();
+        self.expect(TokenKind::LessSlash)?;
+        let name = self.parse_jsx_tag_name()?;
+        if !jsx_tag_names_match(opening, &name) {
+            let message = format!("Expected corresponding JSX closing tag for '{}'.", self.jsx_tag_name_text(opening));
+            self.report(17002, &message, name.span());
+        }
+        self.parse_jsx_tag_end(in_expression_context)?;
+        Ok(JsxClosingElement {
+            name,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse the children of an element up to its closing `</`. `opening` is the
+    /// name of the element, or `None` for a fragment.
+    fn parse_jsx_children(&mut self, opening: Option<&JsxTagName>) -> ParseResult<Vec<JsxChild>> {
+        let mut children = Vec::new();
+        loop {
+            let child = match self.kind() {
+                TokenKind::LessSlash => return Ok(children),
+                TokenKind::JsxText { value, raw } => {
+                    let token = self.advance_jsx_child();
+                    JsxChild::Text(JsxText {
+                        value: resolve(value).unwrap_or_default(),
+                        raw: resolve(raw).unwrap_or_default(),
+                        span: token.span,
+                    })
+                }
+                TokenKind::LeftBrace => self.parse_jsx_child_expression()?,
+                TokenKind::Less => JsxChild::Element(Box::new(self.parse_jsx_element_or_fragment(false)?)),
+                _ => {
+                    return Err(match opening {
+                        Some(name) => {
+                            let message = format!(
+                                "JSX element '{}' has no corresponding closing tag.",
+                                self.jsx_tag_name_text(name)
+                            );
+                            Diagnostic::syntax_error(message, name.span()).with_code(17008)
+                        }
+                        None => self.error(17014, "JSX fragment has no corresponding closing tag."),
+                    });
+                }
+            };
+            children.push(child);
+        }
+    }
+
+    /// Parse `{expression}`, `{...expression}` or `{}` among the children of an
+    /// element, scanning the token after the `}` as the next child
+    fn parse_jsx_child_expression(&mut self) -> ParseResult<JsxChild> {
+        let start = self.start();
+        self.expect(TokenKind::LeftBrace)?;
+        let spread = self.eat(TokenKind::DotDotDot);
+        let expression = if self.at(TokenKind::RightBrace) && !spread {
+            None
+        } else {
+            Some(Box::new(self.with_in_operator(true, Self::parse_expression)?))
+        };
+        self.expect_then_scan_jsx_child(TokenKind::RightBrace)?;
+        let span = self.span_from(start);
+        Ok(match expression {
+            Some(argument) if spread => JsxChild::Spread(SpreadElement { argument, span }),
+            expression => JsxChild::Expression(JsxExpressionContainer { expression, span }),
+        })
+    }
+
+    /// Parse the attributes of an opening tag, up to its `>` or `/>`
+    fn parse_jsx_attributes(&mut self) -> ParseResult<Vec<JsxAttributeItem>> {
+        let mut attributes = Vec::new();
+        while !matches!(self.kind(), TokenKind::Greater | TokenKind::Slash | TokenKind::Eof) {
+            let start = self.start();
+            if self.eat(TokenKind::LeftBrace) {
+                self.expect(TokenKind::DotDotDot)?;
+                let argument = self.with_in_operator(true, Self::parse_assignment_expression)?;
+                self.expect(TokenKind::RightBrace)?;
+                attributes.push(JsxAttributeItem::Spread(SpreadElement {
+                    argument: Box::new(argument),
+                    span: self.span_from(start),
+                }));
+                continue;
+            }
+
+            let name = self.parse_jsx_name()?;
+            let value = if self.at(TokenKind::Equal) {
+                self.advance_jsx_attribute_value();
+                Some(self.parse_jsx_attribute_value()?)
+            } else {
+                None
+            };
+            attributes.push(JsxAttributeItem::Attribute(Box::new(JsxAttribute {
+                name,
+                value,
+                span: self.span_from(start),
+            })));
+        }
+        Ok(attributes)
+    }
+
+    /// Parse the value after `=` in an attribute: a string, `{expression}` or an element
+    fn parse_jsx_attribute_value(&mut self) -> ParseResult<JsxAttributeValue> {
+        let start = self.start();
+        match self.kind() {
+            TokenKind::String(value) => {
+                self.advance();
+                Ok(JsxAttributeValue::String(LiteralExpression {
+                    value: Literal::String(resolve(value).unwrap_or_default()),
+                    span: self.span_from(start),
+                }))
+            }
+            TokenKind::LeftBrace => {
+                self.advance();
+                let expression = if self.at(TokenKind::RightBrace) {
+                    None
+                } else {
+                    Some(Box::new(self.with_in_operator(true, Self::parse_assignment_expression)?))
+                };
+                self.expect(TokenKind::RightBrace)?;
+                let span = self.span_from(start);
+                if expression.is_none() {
+                    self.report(17000, "JSX attributes must only be assigned a non-empty 'expression'.", span);
+                }
+                Ok(JsxAttributeValue::Expression(JsxExpressionContainer { expression, span }))
+            }
+            TokenKind::Less => Ok(JsxAttributeValue::Element(Box::new(self.parse_jsx_element_or_fragment(true)?))),
+            _ => Err(self.error(1145, "'{' or JSX element expected.")),
+        }
+    }
+
+    /// Parse a tag name: a JSX name, possibly followed by `.property` accesses
+    fn parse_jsx_tag_name(&mut self) -> ParseResult<JsxTagName> {
+        let start = self.start();
+        let mut name = match self.parse_jsx_name()? {
+            JsxAttributeName::Namespaced(name) => return Ok(JsxTagName::Namespaced(name)),
+            JsxAttributeName::Identifier(name) => JsxTagName::Identifier(name),
+        };
+        while self.eat(TokenKind::Dot) {
+            let property = self.parse_identifier_name()?;
+            name = JsxTagName::Member(Box::new(JsxMemberName {
+                object: name,
+                property,
+                span: self.span_from(start),
+            }));
+        }
+        Ok(name)
+    }
+
+    /// Parse a word rescanned as a JSX name, which may contain `-` or be
+    /// prefixed with `namespace:`, as an attribute name or the start of a tag name
+    fn parse_jsx_name(&mut self) -> ParseResult<JsxAttributeName> {
+        if !self.is_identifier_name() {
+            return Err(self.error(1003, "Identifier expected."));
+        }
+        if let Some(token) = self.lexer.rescan_jsx_identifier() {
+            self.current_token = token;
+        }
+        let token = self.advance();
+        Ok(match token.kind {
+            TokenKind::JsxNamespacedName { namespace, name } => {
+                JsxAttributeName::Namespaced(jsx_namespaced_name(&token, namespace, name))
+            }
+            TokenKind::JsxIdentifier(name) => JsxAttributeName::Identifier(Identifier {
+                name: resolve(name).unwrap_or_default(),
+                span: token.span,
+            }),
+            _ => JsxAttributeName::Identifier(self.identifier_from(&token)),
+        })
+    }
+
+    /// Consume the current token and scan the next one as JSX child content,
+    /// returning the consumed token
+    fn advance_jsx_child(&mut self) -> Token {
+        let end = self.current_token.span.end;
+        let next = self
+            .lexer
+            .scan_jsx_token()
+            .unwrap_or_else(|| Token::new(TokenKind::Eof, Span::new(end, end)));
+        self.previous_end = end;
+        std::mem::replace(&mut self.current_token, next)
+    }
+
+    /// Consume a token of the given kind and scan the one after it as JSX child content
+    fn expect_then_scan_jsx_child(&mut self, kind: TokenKind) -> ParseResult<()> {
+        if !self.at(kind) {
+            return Err(self.expected(kind.text().unwrap_or("token")));
+        }
+        self.advance_jsx_child();
+        Ok(())
+    }
+
+    /// Consume the `=` of an attribute and scan the token after it as an attribute value
+    fn advance_jsx_attribute_value(&mut self) {
+        let end = self.current_token.span.end;
+        let next = self
+            .lexer
+            .scan_jsx_attribute_value()
+            .unwrap_or_else(|| Token::new(TokenKind::Eof, Span::new(end, end)));
+        self.previous_end = end;
+        self.current_token = next;
+    }
+
+    /// The source text of a tag name, for diagnostics
+    fn jsx_tag_name_text(&self, name: &JsxTagName) -> &'a str {
+        let span = name.span();
+        &self.source[span.start.offset..span.end.offset]
+    }
+}
+
+/// Build a namespaced name from a `JsxNamespacedName` token, splitting its span at the `:`
+fn jsx_namespaced_name(token: &Token, namespace: Symbol, name: Symbol) -> JsxNamespacedName {
+    let namespace = resolve(namespace).unwrap_or_default();
+    let name = resolve(name).unwrap_or_default();
+    let start = token.span.start;
+    let namespace_end = Position::new(
+        start.line,
+        start.column + namespace.chars().count(),
+        start.offset + namespace.len(),
+    );
+    let name_start = Position::new(namespace_end.line, namespace_end.column + 1, namespace_end.offset + 1);
+    JsxNamespacedName {
+        namespace: Identifier {
+            name: namespace,
+            span: Span::new(start, namespace_end),
+        },
+        name: Identifier {
+            name,
+            span: Span::new(name_start, token.span.end),
+        },
+        span: token.span,
+    }
+}
+
+/// Check whether a closing tag names the same element as its opening tag
+fn jsx_tag_names_match(opening: &JsxTagName, closing: &JsxTagName) -> bool {
+    match (opening, closing) {
+        (JsxTagName::Identifier(a), JsxTagName::Identifier(b)) => a.name == b.name,
+        (JsxTagName::Namespaced(a), JsxTagName::Namespaced(b)) => {
+            a.namespace.name == b.namespace.name && a.name.name == b.name.name
+        }
+        (JsxTagName::Member(a), JsxTagName::Member(b)) => {
+            a.property.name == b.property.name && jsx_tag_names_match(&a.object, &b.object)
+        }
+        _ => false,
+    }
+}
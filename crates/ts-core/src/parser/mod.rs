@@ -0,0 +1,653 @@
+//! Parser implementation for TypeScript AST
+//!
+//! A recursive-descent parser driven by the lexer one token at a time. Each
+//! grammar area lives in its own module as an `impl Parser` block; this module
+//! holds the token-level helpers they share.
+
+mod declarations;
+mod expressions;
+mod jsx;
+mod patterns;
+mod statements;
+mod types;
+
+use std::collections::HashSet;
+
+use crate::ast::{Declaration, Identifier, ModuleReference, Program, Statement};
+use crate::diagnostics::Diagnostic;
+use crate::lexer::{Checkpoint, ContextualKeyword, Lexer, Token, TokenKind};
+use crate::utils::intern::resolve;
+use crate::utils::span::{Position, Span};
+
+/// How deeply statements, expressions, types and patterns may nest before the
+/// parser gives up on the file rather than descending further
+const MAX_NESTING_DEPTH: usize = 500;
+
+/// Stack space left below which a deeper level first extends the stack, and
+/// the size of each extension
+const STACK_RED_ZONE: usize = 128 * 1024;
+const STACK_GROWTH: usize = 2 * 1024 * 1024;
+
+/// Result of parsing one construct. The error is the diagnostic for the first
+/// syntax error found.
+type ParseResult<T> = Result<T, Diagnostic>;
+
+/// Parser for TypeScript source code
+pub struct Parser<'a> {
+    lexer: Lexer<'a>,
+    source: &'a str,
+    /// The token being looked at, not yet consumed
+    current_token: Token,
+    /// End of the most recently consumed token, where node spans end
+    previous_end: Position,
+    diagnostics: Vec<Diagnostic>,
+    /// Whether JSX syntax is recognised, as in `.tsx` files
+    jsx: bool,
+    /// Offsets of `(` tokens already found not to start an arrow function, so
+    /// that nested parentheses are not re-parsed speculatively at every level
+    not_parenthesized_arrow: HashSet<usize>,
+    /// Whether a type may not continue into a conditional `extends` clause, as
+    /// in the constraint of an `infer` type inside another extends clause
+    disallow_conditional_types: bool,
+    /// Spans of shorthand properties with an initializer, `{ a = 1 }`, not yet
+    /// known to be part of a destructuring assignment. Any left at the end are
+    /// reported.
+    shorthand_initializers: Vec<Span>,
+    /// Whether `in` is not a binary operator, as in the initializer of a `for`
+    /// statement where it would start a for-in loop
+    disallow_in: bool,
+    /// Whether the innermost enclosing function is a generator, where `yield`
+    /// always starts a yield expression
+    in_generator: bool,
+    /// Whether the innermost enclosing function is async, or the code is at the
+    /// top level of a module, where `await` always starts an await expression
+    in_async: bool,
+    /// Whether a function's parameters or body are being parsed, where `await`
+    /// does not depend on whether the file is a module
+    in_function: bool,
+    /// Whether the current top-level statement has consumed an `await`
+    /// identifier outside any function, so that it may parse differently once
+    /// the file is known to be a module
+    saw_top_level_await: bool,
+    /// Whether a decorator expression is being parsed, where `[` cannot start
+    /// an element access
+    in_decorator: bool,
+    /// Whether decorators follow the legacy `experimentalDecorators` semantics
+    /// rather than the standard ones, which decides where they are allowed
+    experimental_decorators: bool,
+    /// Whether the parameters being parsed may have decorators, which is only
+    /// the case for class methods and constructors under `experimentalDecorators`
+    allow_parameter_decorators: bool,
+    /// How many statements, expressions, types and patterns enclose the one
+    /// being parsed
+    nesting_depth: usize,
+    /// How many `try_parse` or `look_ahead` calls are in progress. While
+    /// speculating, every syntax error fails so that the caller can rewind
+    /// instead of recovering.
+    speculation_depth: usize,
+}
+
+/// A saved parser state, for trying one interpretation of ambiguous syntax
+struct ParserCheckpoint {
+    lexer: Checkpoint,
+    current_token: Token,
+    previous_end: Position,
+    diagnostic_count: usize,
+    shorthand_initializer_count: usize,
+}
+
+/// A statement from the first parse of the top level
+struct TopLevelStatement {
+    /// `None` for a statement that failed to parse and was skipped
+    statement: Option<Statement>,
+    /// Offset of the statement's first token
+    start: usize,
+    /// The state before the statement, kept if it used `await` as an identifier
+    /// so that it can be parsed again as a module statement
+    await_checkpoint: Option<ParserCheckpoint>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(mut lexer: Lexer<'a>) -> Self {
+        lexer.set_split_greater(true);
+        let source = lexer.source();
+        let current_token = lexer
+            .next_token()
+            .unwrap_or_else(|| Token::new(TokenKind::Eof, Span::default()));
+        // Option comments in the file header have been scanned with the first token
+        let experimental_decorators = lexer.directives().iter().any(|directive| {
+            directive.key.eq_ignore_ascii_case("experimentalDecorators")
+                && directive.value.eq_ignore_ascii_case("true")
+        });
+        Self {
+            lexer,
+            source,
+            current_token,
+            previous_end: Position::default(),
+            diagnostics: Vec::new(),
+            jsx: false,
+            not_parenthesized_arrow: HashSet::new(),
+            disallow_conditional_types: false,
+            shorthand_initializers: Vec::new(),
+            disallow_in: false,
+            in_generator: false,
+            in_async: false,
+            in_function: false,
+            saw_top_level_await: false,
+            in_decorator: false,
+            experimental_decorators,
+            allow_parameter_decorators: false,
+            nesting_depth: 0,
+            speculation_depth: 0,
+        }
+    }
+
+    /// Enable or disable JSX syntax
+    pub fn set_jsx(&mut self, jsx: bool) {
+        self.jsx = jsx;
+    }
+
+    /// Choose between the legacy `experimentalDecorators` semantics and the
+    /// standard decorators, which allow decorators in different places.
+    ///
+    /// A `// @experimentalDecorators: true` comment before the first token
+    /// turns the legacy semantics on without a call.
+    pub fn set_experimental_decorators(&mut self, enabled: bool) {
+        self.experimental_decorators = enabled;
+    }
+
+    /// Parse the whole source into a program.
+    ///
+    /// Syntax errors do not stop parsing: missing tokens are reported and
+    /// assumed present, and a statement that cannot be parsed is skipped, so a
+    /// tree is always returned along with the scanner and syntax diagnostics.
+    pub fn parse(&mut self) -> (Program, Vec<Diagnostic>) {
+        let mut statements = self.parse_top_level_statements();
+        self.diagnostics.extend_from_slice(self.lexer.diagnostics());
+        // Top-level `await` is an operator only in a module, which is not known
+        // until an import or export is found, so statements of a module that
+        // used `await` as an identifier are parsed again
+        let is_module = statements
+            .iter()
+            .any(|item| item.statement.as_ref().is_some_and(is_module_statement));
+        if is_module && statements.iter().any(|item| item.await_checkpoint.is_some()) {
+            statements = self.reparse_top_level_await(statements);
+        }
+
+        for span in std::mem::take(&mut self.shorthand_initializers) {
+            self.report(
+                1312,
+                "Did you mean to use a ':'? An '=' can only follow a property name when the containing object literal is part of a destructuring pattern.",
+                span,
+            );
+        }
+        let mut diagnostics = std::mem::take(&mut self.diagnostics);
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.start.offset);
+
+        let program = Program {
+            statements: statements.into_iter().filter_map(|item| item.statement).collect(),
+            experimental_decorators: self.experimental_decorators,
+            directives: self.lexer.directives().to_vec(),
+            span: Span::new(Position::default(), self.current_token.span.end),
+        };
+        (program, diagnostics)
+    }
+
+    fn parse_top_level_statements(&mut self) -> Vec<TopLevelStatement> {
+        let mut statements = Vec::new();
+        while !self.at(TokenKind::Eof) {
+            let checkpoint = self.checkpoint();
+            let start = self.start();
+            self.saw_top_level_await = false;
+            let statement = match self.parse_statement() {
+                Ok(statement) => Some(statement),
+                Err(diagnostic) => {
+                    self.skip_failed_element(diagnostic, start);
+                    None
+                }
+            };
+            statements.push(TopLevelStatement {
+                statement,
+                start: start.offset,
+                await_checkpoint: self.saw_top_level_await.then_some(checkpoint),
+            });
+        }
+        statements
+    }
+
+    /// Parse the statements that used `await` as an identifier again with
+    /// `await` as an operator, like tsc's `reparseTopLevelAwait`. Each reparse
+    /// runs until it is back in step with a statement of the first parse that
+    /// did not use `await`, and the diagnostics in its range are replaced.
+    fn reparse_top_level_await(&mut self, statements: Vec<TopLevelStatement>) -> Vec<TopLevelStatement> {
+        let end = self.checkpoint();
+        let mut kept_diagnostics = std::mem::take(&mut self.diagnostics);
+        let mut kept_shorthand_initializers = std::mem::take(&mut self.shorthand_initializers);
+        let mut new_diagnostics = Vec::new();
+        let mut new_shorthand_initializers = Vec::new();
+        let mut reparsed_ranges = Vec::new();
+        self.not_parenthesized_arrow.clear();
+        self.in_async = true;
+
+        let mut result = Vec::with_capacity(statements.len());
+        let mut remaining = statements.into_iter().peekable();
+        while let Some(item) = remaining.next() {
+            let Some(checkpoint) = item.await_checkpoint else {
+                result.push(item);
+                continue;
+            };
+            self.rewind(checkpoint);
+            let lexer_diagnostic_count = self.lexer.diagnostics().len();
+            while !self.at(TokenKind::Eof) {
+                let start = self.start();
+                match self.parse_statement() {
+                    Ok(statement) => result.push(TopLevelStatement {
+                        statement: Some(statement),
+                        start: start.offset,
+                        await_checkpoint: None,
+                    }),
+                    Err(diagnostic) => self.skip_failed_element(diagnostic, start),
+                }
+                // Drop the statements of the first parse that this one covered
+                let offset = self.start().offset;
+                while remaining.next_if(|next| next.start < offset).is_some() {}
+                if remaining
+                    .peek()
+                    .is_some_and(|next| next.start == offset && next.await_checkpoint.is_none())
+                {
+                    break;
+                }
+            }
+
+            // Scanning may have looked past the end of the range, whose
+            // diagnostics are kept from the first parse
+            let range = item.start..if self.at(TokenKind::Eof) { usize::MAX } else { self.start().offset };
+            let lexer_diagnostics = &self.lexer.diagnostics()[lexer_diagnostic_count..];
+            new_diagnostics.extend(
+                self.diagnostics
+                    .drain(..)
+                    .chain(lexer_diagnostics.iter().cloned())
+                    .filter(|diagnostic| range.contains(&diagnostic.span.start.offset)),
+            );
+            new_shorthand_initializers.append(&mut self.shorthand_initializers);
+            reparsed_ranges.push(range);
+        }
+
+        self.rewind(end);
+        self.in_async = false;
+        let reparsed = |span: &Span| reparsed_ranges.iter().any(|range| range.contains(&span.start.offset));
+        kept_diagnostics.retain(|diagnostic| !reparsed(&diagnostic.span));
+        kept_shorthand_initializers.retain(|span| !reparsed(span));
+        kept_diagnostics.append(&mut new_diagnostics);
+        kept_shorthand_initializers.append(&mut new_shorthand_initializers);
+        self.diagnostics = kept_diagnostics;
+        self.shorthand_initializers = kept_shorthand_initializers;
+        result
+    }
+
+    /// Get the kind of the current token
+    fn kind(&self) -> TokenKind {
+        self.current_token.kind
+    }
+
+    /// Check whether the current token is of the given kind
+    fn at(&self, kind: TokenKind) -> bool {
+        self.current_token.kind == kind
+    }
+
+    /// Check whether the current token is an identifier spelling the given contextual keyword
+    fn at_contextual(&self, keyword: ContextualKeyword) -> bool {
+        self.current_token.is_contextual_keyword(keyword)
+    }
+
+    /// Where the current token starts, for opening a node span
+    fn start(&self) -> Position {
+        self.current_token.span.start
+    }
+
+    /// The span from `start` to the end of the most recently consumed token
+    fn span_from(&self, start: Position) -> Span {
+        Span::new(start, self.previous_end)
+    }
+
+    /// Consume the current token and move to the next one, returning the consumed token
+    fn advance(&mut self) -> Token {
+        if !self.in_async && !self.in_function && self.at_contextual(ContextualKeyword::Await) {
+            self.saw_top_level_await = true;
+        }
+        let end = self.current_token.span.end;
+        let next = self
+            .lexer
+            .next_token()
+            .unwrap_or_else(|| Token::new(TokenKind::Eof, Span::new(end, end)));
+        self.previous_end = end;
+        std::mem::replace(&mut self.current_token, next)
+    }
+
+    /// Consume the current token if it is of the given kind
+    fn eat(&mut self, kind: TokenKind) -> bool {
+        if self.at(kind) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume the current token if it spells the given contextual keyword
+    fn eat_contextual(&mut self, keyword: ContextualKeyword) -> bool {
+        if self.at_contextual(keyword) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume a token of the given kind. If it is missing, report "'x' expected."
+    /// and return an empty token in its place, like tsc's `parseExpected`.
+    fn expect(&mut self, kind: TokenKind) -> ParseResult<Token> {
+        if self.at(kind) {
+            return Ok(self.advance());
+        }
+        self.recover_from(self.expected(kind.text().unwrap_or("token")))?;
+        Ok(Token::new(kind, Span::new(self.previous_end, self.previous_end)))
+    }
+
+    /// Record a syntax error and carry on, or fail with it while speculating
+    fn recover_from(&mut self, diagnostic: Diagnostic) -> ParseResult<()> {
+        if self.speculation_depth > 0 {
+            return Err(diagnostic);
+        }
+        self.record_syntax_error(diagnostic);
+        Ok(())
+    }
+
+    /// Record a syntax error recovered from. One at the same place as the
+    /// previous error is dropped, since it usually follows from it.
+    fn record_syntax_error(&mut self, diagnostic: Diagnostic) {
+        let offset = diagnostic.span.start.offset;
+        if self.diagnostics.last().is_none_or(|last| last.span.start.offset != offset) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    /// Parse one statement or member of a list into `elements`. If it fails, the
+    /// error is recorded and the rest of the element skipped, unless speculating.
+    fn parse_list_element<T>(
+        &mut self,
+        elements: &mut Vec<T>,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<()> {
+        let start = self.start();
+        match parse(self) {
+            Ok(element) => elements.push(element),
+            Err(diagnostic) if self.speculation_depth > 0 => return Err(diagnostic),
+            Err(diagnostic) => self.skip_failed_element(diagnostic, start),
+        }
+        Ok(())
+    }
+
+    /// Record the error that stopped a statement or member begun at `start`, and
+    /// skip to where the next one can begin: past a `;`, or before a `}` or a
+    /// token on a new line. At least one token is skipped so that the list
+    /// always makes progress.
+    fn skip_failed_element(&mut self, diagnostic: Diagnostic, start: Position) {
+        self.record_syntax_error(diagnostic);
+        if self.start().offset == start.offset && !self.at(TokenKind::Eof) {
+            self.advance();
+        }
+        while !self.at(TokenKind::Eof) && !self.at(TokenKind::RightBrace) && !self.current_token.preceded_by_newline {
+            if self.advance().kind == TokenKind::Semicolon {
+                break;
+            }
+        }
+    }
+
+    /// Parse one element of a comma-separated list in braces, such as an enum
+    /// member or an object literal property, into `elements`. If it fails, the
+    /// error is recorded and the element skipped up to the next `,`, the `}` or
+    /// a token on a new line, so that the elements after it are still parsed.
+    fn parse_delimited_element<T>(
+        &mut self,
+        elements: &mut Vec<T>,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<()> {
+        let start = self.start();
+        match parse(self) {
+            Ok(element) => elements.push(element),
+            Err(diagnostic) if self.speculation_depth > 0 => return Err(diagnostic),
+            Err(diagnostic) => {
+                self.record_syntax_error(diagnostic);
+                // A stray `,` is left for the caller, which makes progress by eating it
+                if self.start().offset == start.offset && !self.at(TokenKind::Comma) && !self.at(TokenKind::Eof) {
+                    self.advance();
+                }
+                while !matches!(self.kind(), TokenKind::Eof | TokenKind::Comma | TokenKind::RightBrace)
+                    && !self.current_token.preceded_by_newline
+                {
+                    self.advance();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse the `,` after an element of a comma-separated list in braces,
+    /// returning whether another element follows. Like tsc's `parseDelimitedList`,
+    /// a missing comma is reported and the list carries on, so that `{ A B }` has
+    /// one error; where `semicolon_delimits`, a `;` written instead is skipped.
+    fn parse_list_comma(&mut self, semicolon_delimits: bool) -> ParseResult<bool> {
+        if self.eat(TokenKind::Comma) {
+            return Ok(true);
+        }
+        if self.at(TokenKind::RightBrace) || self.at(TokenKind::Eof) {
+            return Ok(false);
+        }
+        self.recover_from(self.expected(","))?;
+        if semicolon_delimits && self.at(TokenKind::Semicolon) && !self.current_token.preceded_by_newline {
+            self.advance();
+        }
+        Ok(true)
+    }
+
+    /// A syntax error at the current token
+    fn error(&self, code: u32, message: &str) -> Diagnostic {
+        Diagnostic::syntax_error(message.to_string(), self.current_token.span).with_code(code)
+    }
+
+    /// Record a syntax error that does not stop parsing
+    fn report(&mut self, code: u32, message: &str, span: Span) {
+        self.diagnostics
+            .push(Diagnostic::syntax_error(message.to_string(), span).with_code(code));
+    }
+
+    /// "'x' expected." at the current token
+    fn expected(&self, text: &str) -> Diagnostic {
+        self.error(1005, &format!("'{}' expected.", text))
+    }
+
+    /// Look at the kind of the token `n` positions after the current one
+    fn peek_kind(&mut self, n: usize) -> TokenKind {
+        self.lexer.peek(n).map_or(TokenKind::Eof, |token| token.kind)
+    }
+
+    /// Look at the token after the current one
+    fn peek_token(&mut self) -> Option<Token> {
+        self.lexer.peek(0)
+    }
+
+    /// Look at the token `n` positions after the current one
+    fn peek_token_at(&mut self, n: usize) -> Option<Token> {
+        self.lexer.peek(n)
+    }
+
+    fn checkpoint(&self) -> ParserCheckpoint {
+        ParserCheckpoint {
+            lexer: self.lexer.checkpoint(),
+            current_token: self.current_token.clone(),
+            previous_end: self.previous_end,
+            diagnostic_count: self.diagnostics.len(),
+            shorthand_initializer_count: self.shorthand_initializers.len(),
+        }
+    }
+
+    fn rewind(&mut self, checkpoint: ParserCheckpoint) {
+        self.lexer.rewind(checkpoint.lexer);
+        self.current_token = checkpoint.current_token;
+        self.previous_end = checkpoint.previous_end;
+        self.diagnostics.truncate(checkpoint.diagnostic_count);
+        self.shorthand_initializers.truncate(checkpoint.shorthand_initializer_count);
+    }
+
+    /// Run `parse` one level deeper in the grammar, extending the stack if it is
+    /// running low. Past `MAX_NESTING_DEPTH` the rest of the file is skipped, so
+    /// that the error is reported once rather than by every enclosing level.
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        if self.nesting_depth >= MAX_NESTING_DEPTH {
+            let diagnostic = Diagnostic::syntax_error(
+                "Code is nested too deeply to parse.".to_string(),
+                self.current_token.span,
+            );
+            if self.speculation_depth == 0 {
+                while !self.at(TokenKind::Eof) {
+                    self.advance();
+                }
+            }
+            return Err(diagnostic);
+        }
+        self.nesting_depth += 1;
+        let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_GROWTH, || parse(self));
+        self.nesting_depth -= 1;
+        result
+    }
+
+    /// Run `parse` speculatively, keeping its result if it succeeds and returning
+    /// to the starting point if it fails, like tsc's `tryParse`
+    fn try_parse<T>(&mut self, parse: impl FnOnce(&mut Self) -> ParseResult<T>) -> Option<T> {
+        let checkpoint = self.checkpoint();
+        match self.speculate(parse) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                self.rewind(checkpoint);
+                None
+            }
+        }
+    }
+
+    /// Run `lookahead` and return to the starting point whatever it consumed,
+    /// like tsc's `lookAhead`
+    fn look_ahead<T>(&mut self, lookahead: impl FnOnce(&mut Self) -> T) -> T {
+        let checkpoint = self.checkpoint();
+        let result = self.speculate(lookahead);
+        self.rewind(checkpoint);
+        result
+    }
+
+    /// Run `parse` with every syntax error failing rather than being recovered from
+    fn speculate<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        self.speculation_depth += 1;
+        let result = parse(self);
+        self.speculation_depth -= 1;
+        result
+    }
+
+    /// Combine the current `>` with the characters after it into an operator such
+    /// as `>=` or `>>>`, where a binary operator may appear
+    fn rescan_greater(&mut self) {
+        if self.at(TokenKind::Greater) {
+            if let Some(token) = self.lexer.rescan_greater_token() {
+                self.current_token = token;
+            }
+        }
+    }
+
+    /// Rescan the current template token to report its invalid escape sequences,
+    /// which are only allowed when the template is tagged
+    fn report_template_escapes(&mut self) {
+        if let Some(token) = self.lexer.rescan_template_token() {
+            self.current_token = token;
+        }
+    }
+
+    /// Consume the `;` ending a statement. It may be omitted before a `}`, at the
+    /// end of the file, or when the next token starts a new line.
+    fn consume_semicolon(&mut self) -> ParseResult<()> {
+        if self.eat(TokenKind::Semicolon) || self.can_insert_semicolon() {
+            Ok(())
+        } else {
+            self.recover_from(self.expected(";"))
+        }
+    }
+
+    /// Check whether a statement may end here without a `;`: before a `}`, at
+    /// the end of the file, or where the current token starts a new line
+    fn can_insert_semicolon(&self) -> bool {
+        self.at(TokenKind::RightBrace) || self.at(TokenKind::Eof) || self.current_token.preceded_by_newline
+    }
+
+    /// The source text of a token
+    fn token_text(&self, token: &Token) -> &'a str {
+        &self.source[token.span.start.offset..token.span.end.offset]
+    }
+
+    /// Check whether the current token can be used as an identifier: a plain
+    /// identifier or a word reserved only in strict mode
+    fn is_identifier(&self) -> bool {
+        matches!(self.kind(), TokenKind::Identifier(_)) || self.current_token.is_strict_mode_reserved_word()
+    }
+
+    /// Check whether the current token is any word, including reserved words,
+    /// as allowed for property names
+    fn is_identifier_name(&self) -> bool {
+        matches!(self.kind(), TokenKind::Identifier(_))
+            || self.kind().text().is_some_and(|text| text.starts_with(|ch: char| ch.is_ascii_alphabetic()))
+    }
+
+    /// Parse an identifier, which may not be a reserved word
+    fn parse_identifier(&mut self) -> ParseResult<Identifier> {
+        if !self.is_identifier() {
+            return Err(self.error(1003, "Identifier expected."));
+        }
+        let token = self.advance();
+        Ok(self.identifier_from(&token))
+    }
+
+    /// Parse any word as a name, as after `.` or in a property name
+    fn parse_identifier_name(&mut self) -> ParseResult<Identifier> {
+        if !self.is_identifier_name() {
+            return Err(self.error(1003, "Identifier expected."));
+        }
+        let token = self.advance();
+        Ok(self.identifier_from(&token))
+    }
+
+    /// Build an identifier node from a word token
+    fn identifier_from(&self, token: &Token) -> Identifier {
+        let name = match token.kind {
+            TokenKind::Identifier(symbol) => resolve(symbol).unwrap_or_default(),
+            _ => self.token_text(token).to_string(),
+        };
+        Identifier {
+            name,
+            span: token.span,
+        }
+    }
+}
+
+/// Check whether a top-level statement makes the file a module
+fn is_module_statement(statement: &Statement) -> bool {
+    let Statement::Declaration(declaration) = statement else {
+        return false;
+    };
+    matches!(
+        &**declaration,
+        Declaration::Import(_) | Declaration::Export(_) | Declaration::ExportDefault(_) | Declaration::ExportAssignment(_)
+    ) || matches!(
+        &**declaration,
+        Declaration::ImportEquals(import) if matches!(import.module_reference, ModuleReference::External(_))
+    )
+}
+
+#[cfg(test)]
+mod tests;
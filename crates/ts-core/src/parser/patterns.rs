@@ -0,0 +1,321 @@
+//! Destructuring patterns
+//!
+//! Binding patterns in declarations and parameters are parsed directly. An
+//! object or array literal on the left of `=` is only known to be a pattern
+//! once the `=` is reached, so it is parsed as an expression first and then
+//! reinterpreted as an assignment target.
+
+use super::{ParseResult, Parser, STACK_GROWTH, STACK_RED_ZONE};
+use crate::ast::{
+    ArrayAssignmentPattern, ArrayBindingPattern, ArrayExpression, AssignmentElement,
+    AssignmentOperator, AssignmentTarget, BindingElement, BindingPattern, Expression,
+    ObjectAssignmentPattern, ObjectBindingPattern, ObjectExpression, ObjectMember, ObjectProperty,
+};
+use crate::lexer::TokenKind;
+use crate::utils::span::Span;
+
+impl<'a> Parser<'a> {
+    /// Parse an identifier or a destructuring pattern
+    pub(super) fn parse_binding_name(&mut self) -> ParseResult<BindingPattern> {
+        match self.kind() {
+            TokenKind::LeftBrace => self.nested(Self::parse_object_binding_pattern),
+            TokenKind::LeftBracket => self.nested(Self::parse_array_binding_pattern),
+            _ => Ok(BindingPattern::Identifier(self.parse_identifier()?)),
+        }
+    }
+
+    /// Parse `{ a, b: c = 1, [key]: d, ...rest }`
+    fn parse_object_binding_pattern(&mut self) -> ParseResult<BindingPattern> {
+        let start = self.start();
+        self.expect(TokenKind::LeftBrace)?;
+        let mut elements = Vec::new();
+        let mut trailing_comma = false;
+        while !self.at(TokenKind::RightBrace) {
+            elements.push(self.parse_object_binding_element()?);
+            trailing_comma = self.eat(TokenKind::Comma);
+            if !trailing_comma {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBrace)?;
+        self.check_binding_rest(elements.iter().map(Some), trailing_comma);
+        Ok(BindingPattern::Object(ObjectBindingPattern {
+            elements,
+            span: self.span_from(start),
+        }))
+    }
+
+    fn parse_object_binding_element(&mut self) -> ParseResult<BindingElement> {
+        let start = self.start();
+        let rest = self.eat(TokenKind::DotDotDot);
+        let is_identifier = self.is_identifier();
+        let computed = self.at(TokenKind::LeftBracket);
+        let property = if computed {
+            self.advance();
+            let key = self.parse_assignment_expression()?;
+            self.expect(TokenKind::RightBracket)?;
+            key
+        } else {
+            self.parse_property_key()?
+        };
+
+        let (property_name, name) = match property {
+            // Shorthand: `{ a }` binds the property `a` to `a`
+            Expression::Identifier(identifier) if is_identifier && !self.at(TokenKind::Colon) => {
+                (None, BindingPattern::Identifier(identifier))
+            }
+            property => {
+                self.expect(TokenKind::Colon)?;
+                (Some(property), self.parse_binding_name()?)
+            }
+        };
+        let initializer = self.parse_binding_initializer()?;
+        Ok(BindingElement {
+            property_name,
+            computed,
+            name,
+            initializer,
+            rest,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `[a, , b = 1, ...rest]`
+    fn parse_array_binding_pattern(&mut self) -> ParseResult<BindingPattern> {
+        let start = self.start();
+        self.expect(TokenKind::LeftBracket)?;
+        let mut elements = Vec::new();
+        let mut trailing_comma = false;
+        while !self.at(TokenKind::RightBracket) {
+            if self.eat(TokenKind::Comma) {
+                elements.push(None);
+                continue;
+            }
+            let element_start = self.start();
+            let rest = self.eat(TokenKind::DotDotDot);
+            let name = self.parse_binding_name()?;
+            let initializer = self.parse_binding_initializer()?;
+            elements.push(Some(BindingElement {
+                property_name: None,
+                computed: false,
+                name,
+                initializer,
+                rest,
+                span: self.span_from(element_start),
+            }));
+            trailing_comma = self.eat(TokenKind::Comma);
+            if !trailing_comma {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBracket)?;
+        self.check_binding_rest(elements.iter().map(Option::as_ref), trailing_comma);
+        Ok(BindingPattern::Array(ArrayBindingPattern {
+            elements,
+            span: self.span_from(start),
+        }))
+    }
+
+    fn parse_binding_initializer(&mut self) -> ParseResult<Option<Expression>> {
+        if self.eat(TokenKind::Equal) {
+            Ok(Some(self.parse_assignment_expression()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Report a rest element that is not last, has an initializer, or is
+    /// followed by a trailing comma
+    fn check_binding_rest<'e>(
+        &mut self,
+        elements: impl ExactSizeIterator<Item = Option<&'e BindingElement>>,
+        trailing_comma: bool,
+    ) {
+        let count = elements.len();
+        for (index, element) in elements.enumerate() {
+            if let Some(element) = element.filter(|element| element.rest) {
+                let has_initializer = element.initializer.is_some();
+                self.check_rest_element(element.span, has_initializer, index + 1 == count, trailing_comma);
+            }
+        }
+    }
+
+    fn check_rest_element(&mut self, span: Span, has_initializer: bool, is_last: bool, trailing_comma: bool) {
+        if !is_last {
+            self.report(2462, "A rest element must be last in a destructuring pattern.", span);
+        } else if trailing_comma {
+            self.report(1013, "A rest parameter or binding pattern may not have a trailing comma.", span);
+        }
+        if has_initializer {
+            self.report(1186, "A rest element cannot have an initializer.", span);
+        }
+    }
+
+    /// Reinterpret the left operand of `=` as an assignment target, turning
+    /// object and array literals into destructuring patterns
+    pub(super) fn reinterpret_as_assignment_target(&mut self, expression: Expression) -> AssignmentTarget {
+        // The literal was parsed within the nesting limit, but the stack may
+        // still need extending to walk it again
+        stacker::maybe_grow(STACK_RED_ZONE, STACK_GROWTH, || match expression {
+            Expression::Object(object) => AssignmentTarget::Object(self.reinterpret_object_literal(object)),
+            Expression::Array(array) => AssignmentTarget::Array(self.reinterpret_array_literal(array)),
+            expression => self.simple_assignment_target(expression),
+        })
+    }
+
+    /// Wrap the operand of a compound assignment such as `+=`, which must not be a pattern
+    pub(super) fn simple_assignment_target(&mut self, expression: Expression) -> AssignmentTarget {
+        self.check_simple_assignment_target(&expression);
+        AssignmentTarget::Simple(Box::new(expression))
+    }
+
+    /// Report an expression that cannot be assigned to
+    fn check_simple_assignment_target(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(_) | Expression::Member(_) => {}
+            Expression::Parenthesized(parenthesized) => {
+                self.check_simple_assignment_target(&parenthesized.expression)
+            }
+            Expression::NonNull(non_null) => self.check_simple_assignment_target(&non_null.expression),
+            Expression::As(as_expression) => self.check_simple_assignment_target(&as_expression.expression),
+            Expression::Satisfies(satisfies) => self.check_simple_assignment_target(&satisfies.expression),
+            Expression::TypeAssertion(assertion) => self.check_simple_assignment_target(&assertion.expression),
+            Expression::OptionalChain(chain) => self.report(
+                2779,
+                "The left-hand side of an assignment expression may not be an optional property access.",
+                chain.span,
+            ),
+            expression => self.report(
+                2364,
+                "The left-hand side of an assignment expression must be a variable or a property access.",
+                expression.span(),
+            ),
+        }
+    }
+
+    fn reinterpret_object_literal(&mut self, object: ObjectExpression) -> ObjectAssignmentPattern {
+        let trailing_comma = object.trailing_comma;
+        let count = object.properties.len();
+        let mut elements = Vec::with_capacity(count);
+        for (index, member) in object.properties.into_iter().enumerate() {
+            let element = match member {
+                ObjectMember::Spread(spread) => {
+                    let is_last = index + 1 == count;
+                    let argument = *spread.argument;
+                    let has_initializer = is_default_assignment(&argument);
+                    self.check_rest_element(spread.span, has_initializer, is_last, trailing_comma);
+                    let target = match argument {
+                        Expression::Object(_) | Expression::Array(_) => {
+                            self.report(
+                                2701,
+                                "The target of an object rest assignment must be a variable or a property access.",
+                                argument.span(),
+                            );
+                            AssignmentTarget::Simple(Box::new(argument))
+                        }
+                        argument => self.split_assignment_element(argument).0,
+                    };
+                    AssignmentElement {
+                        property_name: None,
+                        computed: false,
+                        target,
+                        initializer: None,
+                        rest: true,
+                        span: spread.span,
+                    }
+                }
+                ObjectMember::Property(property) => self.reinterpret_object_property(*property),
+            };
+            elements.push(element);
+        }
+        ObjectAssignmentPattern {
+            elements,
+            span: object.span,
+        }
+    }
+
+    fn reinterpret_object_property(&mut self, property: ObjectProperty) -> AssignmentElement {
+        let ObjectProperty { key, value, computed, shorthand, span, .. } = property;
+        if shorthand {
+            // `{ a }` or `{ a = 1 }`, whose initializer is now known to be allowed
+            self.shorthand_initializers.retain(|pending| *pending != span);
+            let (target, initializer) = self.split_assignment_element(value);
+            return AssignmentElement {
+                property_name: None,
+                computed,
+                target,
+                initializer,
+                rest: false,
+                span,
+            };
+        }
+
+        let (target, initializer) = self.split_assignment_element(value);
+        AssignmentElement {
+            property_name: Some(key),
+            computed,
+            target,
+            initializer,
+            rest: false,
+            span,
+        }
+    }
+
+    fn reinterpret_array_literal(&mut self, array: ArrayExpression) -> ArrayAssignmentPattern {
+        let trailing_comma = array.trailing_comma;
+        let count = array.elements.len();
+        let mut elements = Vec::with_capacity(count);
+        for (index, element) in array.elements.into_iter().enumerate() {
+            let element = match element {
+                None => None,
+                Some(Expression::Spread(spread)) => {
+                    let argument = *spread.argument;
+                    let has_initializer = is_default_assignment(&argument);
+                    self.check_rest_element(spread.span, has_initializer, index + 1 == count, trailing_comma);
+                    let (target, _) = self.split_assignment_element(argument);
+                    Some(AssignmentElement {
+                        property_name: None,
+                        computed: false,
+                        target,
+                        initializer: None,
+                        rest: true,
+                        span: spread.span,
+                    })
+                }
+                Some(expression) => {
+                    let span = expression.span();
+                    let (target, initializer) = self.split_assignment_element(expression);
+                    Some(AssignmentElement {
+                        property_name: None,
+                        computed: false,
+                        target,
+                        initializer,
+                        rest: false,
+                        span,
+                    })
+                }
+            };
+            elements.push(element);
+        }
+        ArrayAssignmentPattern {
+            elements,
+            span: array.span,
+        }
+    }
+
+    /// Split a pattern element such as `x.y = 1` into its target and default value.
+    /// The target of a nested `=` was already reinterpreted when it was parsed.
+    fn split_assignment_element(&mut self, expression: Expression) -> (AssignmentTarget, Option<Expression>) {
+        match expression {
+            Expression::Assignment(assignment) if assignment.operator == AssignmentOperator::Assign => {
+                (assignment.left, Some(*assignment.right))
+            }
+            expression => (self.reinterpret_as_assignment_target(expression), None),
+        }
+    }
+}
+
+/// Check for `target = value`, which as an element of a pattern is a default value
+fn is_default_assignment(expression: &Expression) -> bool {
+    matches!(expression, Expression::Assignment(assignment) if assignment.operator == AssignmentOperator::Assign)
+}
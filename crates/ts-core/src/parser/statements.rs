@@ -0,0 +1,520 @@
+//! Statement parsing
+
+use super::{ParseResult, Parser};
+use crate::ast::{
+    BlockStatement, BreakStatement, CatchClause, ContinueStatement, DoWhileStatement, Expression,
+    ForInOfLeft, ForInStatement, ForOfStatement, ForStatement, Identifier, IfStatement,
+    LabeledStatement, ReturnStatement, Statement, SwitchCase, SwitchStatement, ThrowStatement,
+    TryStatement, VariableDeclaration, VariableKind, VariableStatement, WhileStatement,
+    WithStatement,
+};
+use crate::lexer::{ContextualKeyword, TokenKind};
+use crate::utils::span::{Position, Span};
+
+impl<'a> Parser<'a> {
+    /// Parse a statement or declaration
+    pub(super) fn parse_statement(&mut self) -> ParseResult<Statement> {
+        self.nested(Self::parse_statement_worker)
+    }
+
+    fn parse_statement_worker(&mut self) -> ParseResult<Statement> {
+        match self.kind() {
+            TokenKind::LeftBrace => Ok(Statement::BlockStatement(self.parse_block()?)),
+            TokenKind::Semicolon => Ok(Statement::Empty(self.advance().span)),
+            TokenKind::Var => self.parse_variable_statement(),
+            TokenKind::Const if self.peek_kind(0) != TokenKind::Enum => self.parse_variable_statement(),
+            TokenKind::Let if self.is_let_declaration() => self.parse_variable_statement(),
+            TokenKind::If => self.parse_if_statement(),
+            TokenKind::While => self.parse_while_statement(),
+            TokenKind::With => self.parse_with_statement(),
+            TokenKind::Do => self.parse_do_while_statement(),
+            TokenKind::For => self.parse_for_statement(),
+            TokenKind::Return => self.parse_return_statement(),
+            TokenKind::Break => self.parse_break_statement(),
+            TokenKind::Continue => self.parse_continue_statement(),
+            TokenKind::Throw => self.parse_throw_statement(),
+            TokenKind::Switch => self.parse_switch_statement(),
+            TokenKind::Try => self.parse_try_statement(),
+            TokenKind::Debugger => {
+                let start = self.start();
+                self.advance();
+                self.consume_semicolon()?;
+                Ok(Statement::DebuggerStatement(self.span_from(start)))
+            }
+            _ if self.is_using_declaration(false) => self.parse_variable_statement(),
+            _ if self.is_identifier() && self.peek_kind(0) == TokenKind::Colon => self.parse_labeled_statement(),
+            _ if self.is_start_of_declaration() => Ok(Statement::Declaration(Box::new(self.parse_declaration()?))),
+            _ => {
+                let expression = self.parse_expression()?;
+                self.consume_semicolon()?;
+                Ok(Statement::Expression(expression))
+            }
+        }
+    }
+
+    /// Parse a `{ ... }` block
+    pub(super) fn parse_block(&mut self) -> ParseResult<BlockStatement> {
+        let start = self.start();
+        self.expect(TokenKind::LeftBrace)?;
+        let mut statements = Vec::new();
+        while !self.at(TokenKind::RightBrace) && !self.at(TokenKind::Eof) {
+            self.parse_list_element(&mut statements, Self::parse_statement)?;
+        }
+        self.expect(TokenKind::RightBrace)?;
+        Ok(BlockStatement {
+            statements,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Check whether `let` starts a declaration rather than being used as an identifier
+    fn is_let_declaration(&mut self) -> bool {
+        match self.peek_kind(0) {
+            TokenKind::Identifier(_) | TokenKind::LeftBracket | TokenKind::LeftBrace => true,
+            kind => matches!(
+                kind,
+                TokenKind::Let | TokenKind::Yield | TokenKind::Static | TokenKind::Implements
+                    | TokenKind::Interface | TokenKind::Package | TokenKind::Private
+                    | TokenKind::Protected | TokenKind::Public
+            ),
+        }
+    }
+
+    /// Check whether `using` or `await using` starts a declaration rather than an
+    /// expression. In the head of a for statement, `using of` reads `using` as the
+    /// loop variable unless `of` is followed by `=`, `:` or `;`. Binding patterns
+    /// are accepted so the checker can reject them.
+    pub(super) fn is_using_declaration(&mut self, in_for_head: bool) -> bool {
+        let binding = if self.at_contextual(ContextualKeyword::Using) {
+            0
+        } else if self.at_contextual(ContextualKeyword::Await)
+            && self
+                .peek_token()
+                .is_some_and(|token| token.is_contextual_keyword(ContextualKeyword::Using) && !token.preceded_by_newline)
+        {
+            1
+        } else {
+            return false;
+        };
+        let Some(token) = self.peek_token_at(binding) else {
+            return false;
+        };
+        if token.preceded_by_newline {
+            return false;
+        }
+        if in_for_head && binding == 0 && token.is_contextual_keyword(ContextualKeyword::Of) {
+            return matches!(self.peek_kind(1), TokenKind::Equal | TokenKind::Colon | TokenKind::Semicolon);
+        }
+        matches!(token.kind, TokenKind::Identifier(_) | TokenKind::LeftBrace) || token.is_strict_mode_reserved_word()
+    }
+
+    /// Parse a `var`, `let`, `const`, `using` or `await using` statement
+    pub(super) fn parse_variable_statement(&mut self) -> ParseResult<Statement> {
+        let statement = self.parse_variable_declaration_list()?;
+        self.consume_semicolon()?;
+        Ok(Statement::VariableStatement(VariableStatement {
+            span: self.span_from(statement.span.start),
+            ..statement
+        }))
+    }
+
+    /// Parse the keyword and declarators of a variable statement, without the
+    /// terminating semicolon
+    pub(super) fn parse_variable_declaration_list(&mut self) -> ParseResult<VariableStatement> {
+        let start = self.start();
+        let keyword = self.advance();
+        let kind = match keyword.kind {
+            TokenKind::Var => VariableKind::Var,
+            TokenKind::Let => VariableKind::Let,
+            TokenKind::Const => VariableKind::Const,
+            _ if keyword.is_contextual_keyword(ContextualKeyword::Await) => {
+                self.advance();
+                VariableKind::AwaitUsing
+            }
+            _ => VariableKind::Using,
+        };
+
+        // In `for (let of x)`, `of` is read as the keyword after an empty list,
+        // which the checker reports
+        if self.at_contextual(ContextualKeyword::Of)
+            && matches!(self.peek_kind(0), TokenKind::Identifier(_))
+            && self.peek_kind(1) == TokenKind::RightParen
+        {
+            return Ok(VariableStatement {
+                kind,
+                declarations: Vec::new(),
+                is_declare: false,
+                span: self.span_from(start),
+            });
+        }
+
+        let mut declarations = vec![self.parse_variable_declaration()?];
+        while self.eat(TokenKind::Comma) {
+            declarations.push(self.parse_variable_declaration()?);
+        }
+
+        Ok(VariableStatement {
+            kind,
+            declarations,
+            is_declare: false,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse one declarator such as `x: number = 1`
+    fn parse_variable_declaration(&mut self) -> ParseResult<VariableDeclaration> {
+        let start = self.start();
+        let name = self.parse_binding_name()?;
+        // Definite assignment assertion: `let x!: number`
+        self.eat(TokenKind::Bang);
+        let type_annotation = self.parse_optional_type_annotation()?;
+        let init = if self.eat(TokenKind::Equal) {
+            Some(self.parse_assignment_expression()?)
+        } else {
+            None
+        };
+        Ok(VariableDeclaration {
+            name,
+            type_annotation,
+            init,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_if_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        self.expect(TokenKind::LeftParen)?;
+        let test = self.parse_expression()?;
+        self.expect(TokenKind::RightParen)?;
+        let consequent = Box::new(self.parse_statement()?);
+        let alternate = if self.eat(TokenKind::Else) {
+            Some(Box::new(self.parse_statement()?))
+        } else {
+            None
+        };
+        Ok(Statement::IfStatement(Box::new(IfStatement {
+            test,
+            consequent,
+            alternate,
+            span: self.span_from(start),
+        })))
+    }
+
+    fn parse_while_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        self.expect(TokenKind::LeftParen)?;
+        let test = self.parse_expression()?;
+        self.expect(TokenKind::RightParen)?;
+        let body = Box::new(self.parse_statement()?);
+        Ok(Statement::WhileStatement(Box::new(WhileStatement {
+            test,
+            body,
+            span: self.span_from(start),
+        })))
+    }
+
+    fn parse_with_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        self.expect(TokenKind::LeftParen)?;
+        let object = self.parse_expression()?;
+        self.expect(TokenKind::RightParen)?;
+        let body = Box::new(self.parse_statement()?);
+        Ok(Statement::WithStatement(Box::new(WithStatement {
+            object,
+            body,
+            span: self.span_from(start),
+        })))
+    }
+
+    fn parse_do_while_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        let body = Box::new(self.parse_statement()?);
+        self.expect(TokenKind::While)?;
+        self.expect(TokenKind::LeftParen)?;
+        let test = self.parse_expression()?;
+        self.expect(TokenKind::RightParen)?;
+        // The `;` after `do ... while (x)` may always be omitted, even before
+        // another statement on the same line
+        self.eat(TokenKind::Semicolon);
+        Ok(Statement::DoWhileStatement(Box::new(DoWhileStatement {
+            body,
+            test,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse a C-style `for`, a `for...in`, a `for...of` or a `for await...of` loop
+    fn parse_for_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        let is_await = self.eat_contextual(ContextualKeyword::Await);
+        self.expect(TokenKind::LeftParen)?;
+
+        // `in` in the initializer would be read as the start of a for-in loop
+        let init = match self.kind() {
+            TokenKind::Semicolon => None,
+            TokenKind::Var | TokenKind::Const => {
+                Some(ForInOfLeft::Variable(self.with_in_operator(false, Self::parse_variable_declaration_list)?))
+            }
+            TokenKind::Let if self.is_let_declaration() => {
+                Some(ForInOfLeft::Variable(self.with_in_operator(false, Self::parse_variable_declaration_list)?))
+            }
+            _ if self.is_using_declaration(true) => {
+                Some(ForInOfLeft::Variable(self.with_in_operator(false, Self::parse_variable_declaration_list)?))
+            }
+            _ => {
+                let expression = self.with_in_operator(false, Self::parse_expression)?;
+                if !is_await && !self.at_contextual(ContextualKeyword::Of) && !self.at(TokenKind::In) {
+                    self.expect(TokenKind::Semicolon)?;
+                    return self.parse_for_rest(start, Some(Box::new(Statement::Expression(expression))));
+                }
+                Some(ForInOfLeft::Target(self.reinterpret_as_assignment_target(expression)))
+            }
+        };
+
+        match init {
+            Some(left) if is_await || self.at_contextual(ContextualKeyword::Of) => {
+                self.parse_for_of_rest(start, is_await, left)
+            }
+            Some(left) if self.at(TokenKind::In) => self.parse_for_in_rest(start, left),
+            Some(ForInOfLeft::Variable(list)) => {
+                self.expect(TokenKind::Semicolon)?;
+                self.parse_for_rest(start, Some(Box::new(Statement::VariableStatement(list))))
+            }
+            _ => {
+                self.expect(TokenKind::Semicolon)?;
+                self.parse_for_rest(start, None)
+            }
+        }
+    }
+
+    /// Parse the rest of a for-of loop from `of`
+    fn parse_for_of_rest(&mut self, start: Position, is_await: bool, left: ForInOfLeft) -> ParseResult<Statement> {
+        if !self.eat_contextual(ContextualKeyword::Of) {
+            self.recover_from(self.expected("of"))?;
+        }
+        let right = self.parse_assignment_expression()?;
+        self.expect(TokenKind::RightParen)?;
+        let body = Box::new(self.parse_statement()?);
+        Ok(Statement::ForOfStatement(Box::new(ForOfStatement {
+            is_await,
+            left,
+            right,
+            body,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse the rest of a for-in loop from `in`
+    fn parse_for_in_rest(&mut self, start: Position, left: ForInOfLeft) -> ParseResult<Statement> {
+        self.expect(TokenKind::In)?;
+        let right = self.parse_expression()?;
+        self.expect(TokenKind::RightParen)?;
+        let body = Box::new(self.parse_statement()?);
+        Ok(Statement::ForInStatement(Box::new(ForInStatement {
+            left,
+            right,
+            body,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse the test, update and body of a `for` statement after the first `;`
+    fn parse_for_rest(&mut self, start: Position, init: Option<Box<Statement>>) -> ParseResult<Statement> {
+        let test = if self.at(TokenKind::Semicolon) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.expect(TokenKind::Semicolon)?;
+        let update = if self.at(TokenKind::RightParen) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.expect(TokenKind::RightParen)?;
+        let body = Box::new(self.parse_statement()?);
+        Ok(Statement::ForStatement(Box::new(ForStatement {
+            init,
+            test,
+            update,
+            body,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse `return` with an optional value, which must start on the same line:
+    /// `return\nvalue` returns nothing
+    fn parse_return_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        let argument = if self.at(TokenKind::Semicolon) || self.can_insert_semicolon() {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.consume_semicolon()?;
+        Ok(Statement::ReturnStatement(Box::new(ReturnStatement {
+            argument,
+            span: self.span_from(start),
+        })))
+    }
+
+    fn parse_break_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        let label = if self.is_identifier() && !self.current_token.preceded_by_newline {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        self.consume_semicolon()?;
+        Ok(Statement::BreakStatement(BreakStatement {
+            label,
+            span: self.span_from(start),
+        }))
+    }
+
+    fn parse_continue_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        let label = if self.is_identifier() && !self.current_token.preceded_by_newline {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        self.consume_semicolon()?;
+        Ok(Statement::ContinueStatement(ContinueStatement {
+            label,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse `throw value`. A line break after `throw` is an error, and the value
+    /// is then left empty rather than taken from the next line.
+    fn parse_throw_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        let argument = if self.current_token.preceded_by_newline {
+            let span = Span::new(self.previous_end, self.previous_end);
+            self.report(1142, "Line break not permitted here.", span);
+            Expression::Identifier(Identifier {
+                name: String::new(),
+                span,
+            })
+        } else {
+            self.parse_expression()?
+        };
+        self.consume_semicolon()?;
+        Ok(Statement::ThrowStatement(Box::new(ThrowStatement {
+            argument,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse `label: statement`
+    fn parse_labeled_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        let label = self.parse_identifier()?;
+        self.expect(TokenKind::Colon)?;
+        let body = Box::new(self.parse_statement()?);
+        Ok(Statement::LabeledStatement(LabeledStatement {
+            label,
+            body,
+            span: self.span_from(start),
+        }))
+    }
+
+    fn parse_switch_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        self.expect(TokenKind::LeftParen)?;
+        let discriminant = self.parse_expression()?;
+        self.expect(TokenKind::RightParen)?;
+        self.expect(TokenKind::LeftBrace)?;
+        let mut cases = Vec::new();
+        while !self.at(TokenKind::RightBrace) && !self.at(TokenKind::Eof) {
+            self.parse_list_element(&mut cases, Self::parse_switch_case)?;
+        }
+        self.expect(TokenKind::RightBrace)?;
+        Ok(Statement::SwitchStatement(Box::new(SwitchStatement {
+            discriminant,
+            cases,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Parse a `case x:` or `default:` clause and the statements up to the next one
+    fn parse_switch_case(&mut self) -> ParseResult<SwitchCase> {
+        let start = self.start();
+        let test = match self.kind() {
+            TokenKind::Case => {
+                self.advance();
+                Some(self.parse_expression()?)
+            }
+            TokenKind::Default => {
+                self.advance();
+                None
+            }
+            _ => return Err(self.error(1130, "'case' or 'default' expected.")),
+        };
+        self.expect(TokenKind::Colon)?;
+        let mut consequent = Vec::new();
+        while !matches!(
+            self.kind(),
+            TokenKind::Case | TokenKind::Default | TokenKind::RightBrace | TokenKind::Eof
+        ) {
+            self.parse_list_element(&mut consequent, Self::parse_statement)?;
+        }
+        Ok(SwitchCase {
+            test,
+            consequent,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parse `try { } catch (e) { } finally { }`, where the catch binding is
+    /// optional and at least one of the clauses must be present
+    fn parse_try_statement(&mut self) -> ParseResult<Statement> {
+        let start = self.start();
+        self.advance();
+        let block = self.parse_block()?;
+        let handler = if self.at(TokenKind::Catch) {
+            let catch_start = self.start();
+            self.advance();
+            let (param, type_annotation) = if self.eat(TokenKind::LeftParen) {
+                let param = self.parse_binding_name()?;
+                let type_annotation = self.parse_optional_type_annotation()?;
+                self.expect(TokenKind::RightParen)?;
+                (Some(param), type_annotation)
+            } else {
+                (None, None)
+            };
+            let body = self.parse_block()?;
+            Some(CatchClause {
+                param,
+                type_annotation,
+                body,
+                span: self.span_from(catch_start),
+            })
+        } else {
+            None
+        };
+        let finalizer = if handler.is_none() || self.at(TokenKind::Finally) {
+            self.expect(TokenKind::Finally)?;
+            Some(self.parse_block()?)
+        } else {
+            None
+        };
+        Ok(Statement::TryStatement(Box::new(TryStatement {
+            block,
+            handler,
+            finalizer,
+            span: self.span_from(start),
+        })))
+    }
+}
@@ -0,0 +1,1311 @@
+//! Parser tests
+
+use super::Parser;
+use crate::ast::*;
+use crate::diagnostics::Diagnostic;
+use crate::lexer::Lexer;
+
+fn parse(source: &str) -> (Program, Vec<Diagnostic>) {
+    Parser::new(Lexer::new(source)).parse()
+}
+
+/// Parse `source`, asserting that it has no syntax errors
+fn parse_ok(source: &str) -> Vec<Statement> {
+    let (program, diagnostics) = parse(source);
+    assert!(diagnostics.is_empty(), "unexpected diagnostics for {:?}: {:?}", source, diagnostics);
+    program.statements
+}
+
+/// The error codes reported for `source`, in source order
+fn error_codes(source: &str) -> Vec<u32> {
+    parse(source).1.iter().filter_map(|diagnostic| diagnostic.code).collect()
+}
+
+/// Parse `source`, which must hold a single expression statement
+fn expression(source: &str) -> Expression {
+    match parse_ok(source).into_iter().next() {
+        Some(Statement::Expression(expression)) => expression,
+        statement => panic!("expected an expression statement, found {:?}", statement),
+    }
+}
+
+/// Parse `source` as a `.tsx` file
+fn parse_jsx(source: &str) -> (Program, Vec<Diagnostic>) {
+    let mut parser = Parser::new(Lexer::new(source));
+    parser.set_jsx(true);
+    parser.parse()
+}
+
+/// Parse `source` as a `.tsx` file holding a single expression statement
+fn jsx_expression(source: &str) -> Expression {
+    let (program, diagnostics) = parse_jsx(source);
+    assert!(diagnostics.is_empty(), "unexpected diagnostics for {:?}: {:?}", source, diagnostics);
+    match program.statements.into_iter().next() {
+        Some(Statement::Expression(expression)) => expression,
+        statement => panic!("expected an expression statement, found {:?}", statement),
+    }
+}
+
+fn jsx_element(expression: Expression) -> JsxElement {
+    match expression {
+        Expression::JsxElement(element) => *element,
+        expression => panic!("expected a JSX element, found {:?}", expression),
+    }
+}
+
+fn jsx_attribute(item: &JsxAttributeItem) -> &JsxAttribute {
+    match item {
+        JsxAttributeItem::Attribute(attribute) => attribute,
+        item => panic!("expected an attribute, found {:?}", item),
+    }
+}
+
+/// The declaration made by a declaration statement
+fn declaration(statement: &Statement) -> &Declaration {
+    match statement {
+        Statement::Declaration(declaration) => declaration,
+        statement => panic!("expected a declaration, found {:?}", statement),
+    }
+}
+
+/// The function declared by an `export default` statement
+fn default_exported_function(statement: &Statement) -> &FunctionDeclaration {
+    match declaration(statement) {
+        Declaration::ExportDefault(export) => match &export.value {
+            ExportDefault::Declaration(Declaration::Function(function)) => function,
+            value => panic!("expected a function, found {:?}", value),
+        },
+        declaration => panic!("expected export default, found {:?}", declaration),
+    }
+}
+
+#[test]
+fn export_default_function_may_be_anonymous() {
+    for (source, is_async, is_generator) in [
+        ("export default function () {}", false, false),
+        ("export default function* () {}", false, true),
+        ("export default async function () {}", true, false),
+        ("export default async function* () {}", true, true),
+    ] {
+        let statements = parse_ok(source);
+        let function = default_exported_function(&statements[0]);
+        assert_eq!(function.name, None, "parsing {:?}", source);
+        assert_eq!((function.is_async, function.is_generator), (is_async, is_generator));
+        assert!(function.body.is_some());
+    }
+}
+
+#[test]
+fn export_default_function_keeps_its_name() {
+    let statements = parse_ok("export default function f<T>(x: T) {}");
+    let function = default_exported_function(&statements[0]);
+    assert_eq!(function.name.as_ref().map(|name| name.name.as_str()), Some("f"));
+    assert_eq!(function.type_parameters.len(), 1);
+}
+
+#[test]
+fn function_declaration_without_name_is_reported() {
+    assert_eq!(error_codes("function () {}"), [1003]);
+    assert_eq!(error_codes("export function () {}"), [1003]);
+    assert_eq!(error_codes("async function* () {}"), [1003]);
+    // Parsing carries on with the function after the error
+    let (program, _) = parse("function () {}\nlet x = 1;");
+    assert_eq!(program.statements.len(), 2);
+}
+
+#[test]
+fn declare_marks_declarations_as_ambient() {
+    let source = "declare function f(): void;\ndeclare const x: number;\ndeclare class C {}\ndeclare interface I {}\n\
+        declare type T = 1;\ndeclare const enum E {}\ndeclare namespace N {}\ndeclare module \"m\";\n\
+        declare global {}\ndeclare abstract class D {}\nexport declare let y: string;\nfunction g() {}\nclass K {}";
+    let statements = parse_ok(source);
+    let ambient = |declaration: &Declaration| match declaration {
+        Declaration::Function(function) => (function.is_declare, function.span),
+        Declaration::Variable(variable) => (variable.is_declare, variable.span),
+        Declaration::Class(class) => (class.is_declare, class.span),
+        Declaration::Interface(interface) => (interface.is_declare, interface.span),
+        Declaration::Type(alias) => (alias.is_declare, alias.span),
+        Declaration::Enum(enumeration) => (enumeration.is_declare, enumeration.span),
+        Declaration::Namespace(namespace) => (namespace.is_declare, namespace.span),
+        Declaration::Export(export) => match export.declaration.as_deref() {
+            Some(Declaration::Variable(variable)) => (variable.is_declare, variable.span),
+            declaration => panic!("unexpected export {:?}", declaration),
+        },
+        declaration => panic!("unexpected declaration {:?}", declaration),
+    };
+    let lines: Vec<_> = source.lines().collect();
+    for (index, statement) in statements.iter().enumerate() {
+        let (is_declare, span) = ambient(declaration(statement));
+        assert_eq!(is_declare, lines[index].contains("declare"), "{}", lines[index]);
+        if is_declare {
+            let text = &source[span.start.offset..span.end.offset];
+            assert!(text.starts_with("declare"), "{:?}", text);
+        }
+    }
+}
+
+#[test]
+fn interface_members_keep_readonly_and_accessor_kinds() {
+    let statements = parse_ok(
+        "interface I { readonly a: number; b?: string; get c(): number; set c(v: number); get(): void; readonly [k: string]: any; readonly: 1 }",
+    );
+    let members = match declaration(&statements[0]) {
+        Declaration::Interface(interface) => &interface.members,
+        declaration => panic!("expected an interface, found {:?}", declaration),
+    };
+    let summary: Vec<_> = members
+        .iter()
+        .map(|member| match member {
+            InterfaceMember::Property(property) => (None, property.modifiers.is_readonly),
+            InterfaceMember::Method(method) => (Some(method.kind), method.modifiers.is_readonly),
+            InterfaceMember::IndexSignature(index) => (None, index.modifiers.is_readonly),
+            member => panic!("unexpected member {:?}", member),
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (None, true),
+            (None, false),
+            (Some(MethodKind::Get), false),
+            (Some(MethodKind::Set), false),
+            (Some(MethodKind::Method), false),
+            (None, true),
+            (None, false),
+        ]
+    );
+    assert_eq!(error_codes("interface I { get x: number }"), [1005]);
+}
+
+#[test]
+fn import_attributes_are_kept_on_imports_and_exports() {
+    let statements = parse_ok(
+        "import data from \"./d.json\" with { type: \"json\" };\nimport \"./e.json\" assert { type: \"json\", \"x-y\": \"z\" };\n\
+         export * from \"m\" with { type: \"json\" };\nexport { a } from \"n\";\nimport b from \"o\"\nassert(b);",
+    );
+    let attributes: Vec<_> = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Declaration(declaration) => Some(match &**declaration {
+                Declaration::Import(import) => import.attributes.clone(),
+                Declaration::Export(export) => export.attributes.clone(),
+                declaration => panic!("unexpected declaration {:?}", declaration),
+            }),
+            _ => None,
+        })
+        .collect();
+    let summary: Vec<_> = attributes
+        .iter()
+        .map(|attributes| attributes.as_ref().map(|attributes| (attributes.is_assert, attributes.elements.len())))
+        .collect();
+    assert_eq!(summary, [Some((false, 1)), Some((true, 2)), Some((false, 1)), None, None]);
+    let element = &attributes[0].as_ref().unwrap().elements[0];
+    assert!(matches!(&element.key, Expression::Identifier(key) if key.name == "type"));
+    assert!(matches!(
+        &element.value,
+        Expression::Literal(LiteralExpression { value: Literal::String(value), .. }) if value == "json"
+    ));
+    // `assert` on the next line starts a new statement
+    assert!(matches!(statements.last(), Some(Statement::Expression(Expression::Call(_)))));
+}
+
+#[test]
+fn empty_statements_have_a_span() {
+    let statements = parse_ok("a;\n ;");
+    match &statements[1] {
+        Statement::Empty(span) => assert_eq!((span.start.offset, span.end.offset), (4, 5)),
+        statement => panic!("expected an empty statement, found {:?}", statement),
+    }
+}
+
+#[test]
+fn using_declarations() {
+    let kind = |statement: &Statement| match statement {
+        Statement::VariableStatement(variable) => Some((variable.kind, variable.declarations.len())),
+        Statement::ForOfStatement(for_of) => match &for_of.left {
+            ForInOfLeft::Variable(variable) => Some((variable.kind, variable.declarations.len())),
+            ForInOfLeft::Target(_) => None,
+        },
+        Statement::ForStatement(for_statement) => match for_statement.init.as_deref() {
+            Some(Statement::VariableStatement(variable)) => Some((variable.kind, variable.declarations.len())),
+            _ => None,
+        },
+        _ => None,
+    };
+    let source = "using a = b, c = d;\nawait using e = f;\nfor (using g of h);\nfor await (await using i of j);\n\
+        for (await using of of k);\nfor (using of = null;;);\nfor (await using of l);\n\
+        using;\nusing\nm = n;\nawait\nusing o = p;\nusing(q);\nfor (using of r);\nusing.x = 1;";
+    let statements = parse_ok(source);
+    let kinds: Vec<_> = statements.iter().map(kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            Some((VariableKind::Using, 2)),
+            Some((VariableKind::AwaitUsing, 1)),
+            Some((VariableKind::Using, 1)),
+            Some((VariableKind::AwaitUsing, 1)),
+            Some((VariableKind::AwaitUsing, 1)),
+            Some((VariableKind::Using, 1)),
+            // `of` is the keyword, after a list the checker reports as empty
+            Some((VariableKind::AwaitUsing, 0)),
+            None,
+            None,
+            None,
+            // `await` ends at the line break, leaving a `using` declaration
+            None,
+            Some((VariableKind::Using, 1)),
+            None,
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn export_as_namespace() {
+    let statements = parse_ok("export as namespace Lib;\nexport { as };");
+    match declaration(&statements[0]) {
+        Declaration::NamespaceExport(export) => {
+            assert_eq!(export.name.name, "Lib");
+            assert_eq!((export.span.start.offset, export.span.end.offset), (0, 24));
+        }
+        declaration => panic!("expected a namespace export, found {:?}", declaration),
+    }
+    assert_eq!(error_codes("export as namespace;"), [1003]);
+}
+
+#[test]
+fn with_statement_is_parsed() {
+    let statements = parse_ok("with (a.b) { c; }\nwith (d) e();");
+    let summary: Vec<_> = statements
+        .iter()
+        .map(|statement| match statement {
+            Statement::WithStatement(with) => (matches!(with.object, Expression::Member(_)), with.span.end.offset),
+            statement => panic!("expected a with statement, found {:?}", statement),
+        })
+        .collect();
+    assert_eq!(summary, [(true, 17), (false, 31)]);
+    // Strict mode errors are the checker's to report
+    parse_ok("\"use strict\";\nwith (a) {}");
+}
+
+#[test]
+fn private_name_is_an_expression_only_before_in() {
+    let statements = parse_ok("class C { #x = 1; static f(o: object) { return #x in o && this.#x; } }");
+    let body = match declaration(&statements[0]) {
+        Declaration::Class(class) => &class.body,
+        declaration => panic!("expected a class, found {:?}", declaration),
+    };
+    let ClassMember::Method(method) = &body[1] else {
+        panic!("expected a method, found {:?}", body[1]);
+    };
+    let Some(Statement::ReturnStatement(ret)) = method.body.as_ref().and_then(|body| body.statements.first()) else {
+        panic!("expected a return statement");
+    };
+    let Some(Expression::Binary(and)) = &ret.argument else {
+        panic!("expected a binary expression, found {:?}", ret.argument);
+    };
+    let Expression::Binary(test) = &*and.left else {
+        panic!("expected `#x in o`, found {:?}", and.left);
+    };
+    assert_eq!(test.operator, BinaryOperator::In);
+    assert!(matches!(&*test.left, Expression::PrivateName(name) if name.name == "#x"));
+
+    assert_eq!(error_codes("class C { #x; m() { #x; } }"), [1109]);
+    assert_eq!(error_codes("class C { #x; m() { return #x + 1; } }"), [1109]);
+}
+
+#[test]
+fn jsx_element_with_text_and_expression_children() {
+    let element = jsx_element(jsx_expression("<a href=\"x\">Hi &amp; {name}!</a>;"));
+    assert!(matches!(&element.opening.name, JsxTagName::Identifier(id) if id.name == "a"));
+    assert!(!element.opening.self_closing);
+    assert!(element.closing.is_some());
+    let attribute = jsx_attribute(&element.opening.attributes[0]);
+    assert!(matches!(&attribute.name, JsxAttributeName::Identifier(id) if id.name == "href"));
+    assert!(matches!(
+        &attribute.value,
+        Some(JsxAttributeValue::String(LiteralExpression { value: Literal::String(value), .. })) if value == "x"
+    ));
+    match &element.children[..] {
+        [JsxChild::Text(text), JsxChild::Expression(container), JsxChild::Text(bang)] => {
+            assert_eq!((text.value.as_str(), text.raw.as_str()), ("Hi & ", "Hi &amp; "));
+            assert!(matches!(container.expression.as_deref(), Some(Expression::Identifier(id)) if id.name == "name"));
+            assert_eq!(bang.value, "!");
+        }
+        children => panic!("unexpected children {:?}", children),
+    }
+}
+
+#[test]
+fn jsx_self_closing_element_and_attribute_forms() {
+    let element = jsx_element(jsx_expression(
+        "<svg:use data-id=\"1\" xlink:href={url} disabled {...rest} icon=<b /> />;",
+    ));
+    assert!(element.opening.self_closing);
+    assert!(element.closing.is_none() && element.children.is_empty());
+    assert!(matches!(&element.opening.name, JsxTagName::Namespaced(name) if name.namespace.name == "svg" && name.name.name == "use"));
+    let attributes = &element.opening.attributes;
+    assert_eq!(attributes.len(), 5);
+    assert!(matches!(&jsx_attribute(&attributes[0]).name, JsxAttributeName::Identifier(id) if id.name == "data-id"));
+    let href = jsx_attribute(&attributes[1]);
+    assert!(matches!(&href.name, JsxAttributeName::Namespaced(name) if name.namespace.name == "xlink"));
+    assert!(matches!(&href.value, Some(JsxAttributeValue::Expression(_))));
+    assert!(jsx_attribute(&attributes[2]).value.is_none());
+    assert!(matches!(&attributes[3], JsxAttributeItem::Spread(_)));
+    assert!(matches!(&jsx_attribute(&attributes[4]).value, Some(JsxAttributeValue::Element(_))));
+}
+
+#[test]
+fn jsx_fragments_member_tags_and_nested_children() {
+    let fragment = match jsx_expression("<><Foo.Bar>{...items}<br/></Foo.Bar>{}</>;") {
+        Expression::JsxFragment(fragment) => fragment,
+        expression => panic!("expected a fragment, found {:?}", expression),
+    };
+    match &fragment.children[..] {
+        [JsxChild::Element(inner), JsxChild::Expression(empty)] => {
+            assert!(empty.expression.is_none());
+            let inner = jsx_element((**inner).clone());
+            assert!(matches!(&inner.opening.name, JsxTagName::Member(_)));
+            assert!(matches!(&inner.children[..], [JsxChild::Spread(_), JsxChild::Element(_)]));
+        }
+        children => panic!("unexpected children {:?}", children),
+    }
+}
+
+#[test]
+fn jsx_generic_arrow_function_is_not_an_element() {
+    let expression = jsx_expression("<T,>(x: T) => x;");
+    assert!(matches!(expression, Expression::Arrow(_)), "found {:?}", expression);
+}
+
+#[test]
+fn jsx_errors_are_reported() {
+    let codes = |source| parse_jsx(source).1.iter().filter_map(|d| d.code).collect::<Vec<_>>();
+    assert_eq!(codes("<a></b>;"), [17002]);
+    assert_eq!(codes("<>text</a>;"), [17015]);
+    assert_eq!(codes("<a>"), [17008]);
+    assert_eq!(codes("<a x={} />;"), [17000]);
+    assert_eq!(codes("<a></a><b></b>;"), [2657]);
+    // `<` starts a type assertion when JSX is off
+    assert_eq!(error_codes("<any>x;"), []);
+}
+
+#[test]
+fn rest_element_before_a_trailing_comma_is_reported() {
+    assert_eq!(error_codes("[a, ...b,] = x;"), [1013]);
+    assert_eq!(error_codes("[a, ...b, // c\n] = x;"), [1013]);
+    assert_eq!(error_codes("({ a, ...b, /* , */ } = x);"), [1013]);
+    assert_eq!(error_codes("[a, ...b /* , */] = x;"), []);
+    assert_eq!(error_codes("({ a, ...b } = x);"), []);
+}
+
+#[test]
+fn literals_record_a_trailing_comma() {
+    let trailing_comma = |source| match parse_ok(source).into_iter().next() {
+        Some(Statement::Expression(Expression::Array(array))) => array.trailing_comma,
+        Some(Statement::Expression(Expression::Parenthesized(parenthesized))) => match *parenthesized.expression {
+            Expression::Object(object) => object.trailing_comma,
+            expression => panic!("expected an object, found {:?}", expression),
+        },
+        statement => panic!("unexpected statement {:?}", statement),
+    };
+    assert!(trailing_comma("[a, b,];"));
+    assert!(trailing_comma("[a, ,];"));
+    assert!(!trailing_comma("[a, b];"));
+    assert!(!trailing_comma("[];"));
+    assert!(trailing_comma("({ a: 1, });"));
+    assert!(!trailing_comma("({ a: 1 });"));
+}
+
+#[test]
+fn object_literal_properties_record_their_kind() {
+    let kinds = |source| match parse_ok(source).into_iter().next() {
+        Some(Statement::Expression(Expression::Parenthesized(parenthesized))) => match *parenthesized.expression {
+            Expression::Object(object) => object
+                .properties
+                .into_iter()
+                .map(|member| match member {
+                    ObjectMember::Property(property) => property.kind,
+                    ObjectMember::Spread(spread) => panic!("unexpected spread {:?}", spread),
+                })
+                .collect::<Vec<_>>(),
+            expression => panic!("expected an object, found {:?}", expression),
+        },
+        statement => panic!("unexpected statement {:?}", statement),
+    };
+    assert_eq!(
+        kinds("({ get x() { return 1; }, set x(v) {}, m() {}, async n() {}, *o() {}, a: 1, b });"),
+        [
+            PropertyKind::Get,
+            PropertyKind::Set,
+            PropertyKind::Method,
+            PropertyKind::Method,
+            PropertyKind::Method,
+            PropertyKind::Init,
+            PropertyKind::Init,
+        ]
+    );
+    // `get` and `set` are ordinary names unless a property name follows them
+    assert_eq!(
+        kinds("({ get() {}, set: 1, get, get [k]() {}, set 0(v) {} });"),
+        [
+            PropertyKind::Method,
+            PropertyKind::Init,
+            PropertyKind::Init,
+            PropertyKind::Get,
+            PropertyKind::Set,
+        ]
+    );
+}
+
+#[test]
+fn return_argument_must_start_on_the_same_line() {
+    let statements = parse_ok("function f() {\n    return\n    value\n}");
+    let body = match declaration(&statements[0]) {
+        Declaration::Function(function) => &function.body.as_ref().unwrap().statements,
+        declaration => panic!("expected a function, found {:?}", declaration),
+    };
+    match &body[..] {
+        [Statement::ReturnStatement(ret), Statement::Expression(Expression::Identifier(value))] => {
+            assert!(ret.argument.is_none());
+            assert_eq!(value.name, "value")
+        }
+        body => panic!("unexpected body {:?}", body),
+    }
+}
+
+#[test]
+fn lines_starting_with_a_paren_or_bracket_continue_the_expression() {
+    match &parse_ok("a\n(b)")[..] {
+        [Statement::Expression(Expression::Call(call))] => {
+            assert!(matches!(&*call.callee, Expression::Identifier(id) if id.name == "a"));
+            assert_eq!(call.arguments.len(), 1);
+        }
+        statements => panic!("expected one call, found {:?}", statements),
+    }
+    match &parse_ok("a\n[b]")[..] {
+        [Statement::Expression(Expression::Member(member))] => {
+            assert!(member.computed);
+            assert!(matches!(&*member.object, Expression::Identifier(id) if id.name == "a"));
+        }
+        statements => panic!("expected one element access, found {:?}", statements),
+    }
+}
+
+#[test]
+fn increment_on_the_next_line_is_a_prefix_operator() {
+    match &parse_ok("a\n++b")[..] {
+        [Statement::Expression(Expression::Identifier(a)), Statement::Expression(Expression::Unary(increment))] => {
+            assert_eq!(a.name, "a");
+            assert_eq!(increment.operator, UnaryOperator::PreIncrement);
+            assert!(matches!(&*increment.operand, Expression::Identifier(id) if id.name == "b"));
+        }
+        statements => panic!("expected two statements, found {:?}", statements),
+    }
+    // On the same line it is a postfix operator, and `b` then needs a separator
+    assert_eq!(error_codes("a ++b"), [1005]);
+}
+
+#[test]
+fn async_before_a_line_break_is_an_identifier() {
+    let (program, diagnostics) = parse("async\n(x) => x");
+    match &program.statements[0] {
+        Statement::Expression(Expression::Call(call)) => {
+            assert!(matches!(&*call.callee, Expression::Identifier(id) if id.name == "async"));
+        }
+        statement => panic!("expected a call to async, found {:?}", statement),
+    }
+    let errors: Vec<_> = diagnostics.iter().map(|d| (d.code, d.span.start.offset)).collect();
+    assert_eq!(errors, [(Some(1005), 10)]);
+    // Without the line break it is an async arrow function
+    match &parse_ok("async (x) => x")[..] {
+        [Statement::Expression(Expression::Arrow(arrow))] => assert!(arrow.is_async),
+        statements => panic!("expected an async arrow function, found {:?}", statements),
+    }
+}
+
+#[test]
+fn throw_argument_must_start_on_the_same_line() {
+    let (program, diagnostics) = parse("throw\nx");
+    match &program.statements[..] {
+        [Statement::ThrowStatement(throw), Statement::Expression(Expression::Identifier(x))] => {
+            assert!(matches!(&throw.argument, Expression::Identifier(id) if id.name.is_empty()));
+            assert_eq!(x.name, "x");
+        }
+        statements => panic!("unexpected statements {:?}", statements),
+    }
+    let errors: Vec<_> = diagnostics.iter().map(|d| (d.code, d.span.start.offset)).collect();
+    assert_eq!(errors, [(Some(1142), 5)]);
+}
+
+/// Whether each top-level statement of the first parse is marked for reparsing
+fn marked_for_await_reparse(source: &str) -> Vec<bool> {
+    let mut parser = Parser::new(Lexer::new(source));
+    parser
+        .parse_top_level_statements()
+        .iter()
+        .map(|item| item.await_checkpoint.is_some())
+        .collect()
+}
+
+#[test]
+fn only_statements_using_await_at_the_top_level_are_marked() {
+    assert_eq!(marked_for_await_reparse("let a = await;\nlet b = 1;\nawait;"), [true, false, true]);
+    // Inside functions `await` does not depend on the file being a module
+    assert_eq!(
+        marked_for_await_reparse("function f() { var await; }\nconst g = () => { await; };"),
+        [false, false]
+    );
+    // Names and comments that merely contain the word are not identifiers
+    assert_eq!(marked_for_await_reparse("const awaited = '/* await */';\n// await"), [false]);
+}
+
+#[test]
+fn await_is_an_operator_at_the_top_level_of_a_module() {
+    let (program, diagnostics) = parse("await;");
+    assert!(diagnostics.is_empty());
+    assert!(matches!(&program.statements[0], Statement::Expression(Expression::Identifier(id)) if id.name == "await"));
+
+    let errors: Vec<_> = parse("export {};\nawait;").1.iter().map(|d| (d.code, d.span.start.offset)).collect();
+    assert_eq!(errors, [(Some(1109), 16)]);
+
+    // The reparsed statement takes in the next line, which is dropped from the first parse
+    match &parse_ok("export {};\nawait\n++x;")[..] {
+        [_, Statement::Expression(Expression::Await(await_expression))] => {
+            assert!(matches!(&*await_expression.argument, Expression::Unary(_)));
+        }
+        statements => panic!("expected an await expression, found {:?}", statements),
+    }
+    assert_eq!(parse_ok("await\n++x;").len(), 2);
+}
+
+#[test]
+fn reparsing_keeps_the_diagnostics_of_other_statements() {
+    let errors: Vec<_> = parse("let a = 1 +;\nawait;\nlet b = 2 +;\nexport {}")
+        .1
+        .iter()
+        .map(|d| (d.code, d.span.start.offset))
+        .collect();
+    assert_eq!(errors, [(Some(1109), 11), (Some(1109), 18), (Some(1109), 31)]);
+}
+
+#[test]
+fn program_records_the_decorator_semantics() {
+    let source = "class C { m(@dec x) {} }";
+    let (program, diagnostics) = parse(source);
+    assert!(!program.experimental_decorators);
+    assert!(!diagnostics.is_empty());
+
+    let mut parser = Parser::new(Lexer::new(source));
+    parser.set_experimental_decorators(true);
+    let (program, diagnostics) = parser.parse();
+    assert!(program.experimental_decorators);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn header_option_comment_turns_on_experimental_decorators() {
+    let (program, diagnostics) =
+        parse("// @target: es5\n// @experimentalDecorators: true\nclass C { m(@dec x) {} }");
+    assert!(program.experimental_decorators);
+    assert!(diagnostics.is_empty());
+    let keys: Vec<_> = program.directives.iter().map(|d| d.key.as_str()).collect();
+    assert_eq!(keys, ["target", "experimentalDecorators"]);
+
+    let (program, _) = parse("class C {}\n// @experimentalDecorators: true\n");
+    assert!(!program.experimental_decorators);
+    assert_eq!(program.directives.len(), 1);
+}
+
+#[test]
+fn nesting_within_the_limit_parses() {
+    let depth = 200;
+    let parens = format!("x = {}1{};", "(".repeat(depth), ")".repeat(depth));
+    let callbacks = format!("{}g(){};", "f(function () { ".repeat(50), " })".repeat(50));
+    let objects = format!("x = {}1{};", "{ a: ".repeat(100), " }".repeat(100));
+    let arrays = format!("{}a{} = x;", "[".repeat(100), "]".repeat(100));
+    let types = format!("let x: {}T{};", "Array<".repeat(100), ">".repeat(100));
+    let blocks = format!("{}{}", "{".repeat(depth), "}".repeat(depth));
+    let elements = format!("<a>{}</a>;", "<b>".repeat(100) + &"</b>".repeat(100));
+    for source in [&parens, &callbacks, &objects, &arrays, &types, &blocks] {
+        let (program, diagnostics) = parse(source);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(program.statements.len(), 1);
+    }
+    assert!(parse_jsx(&elements).1.is_empty());
+
+    // A tree just inside the limit can still be walked on a small test thread
+    let (program, diagnostics) = parse(&format!("x = {}y;", "!".repeat(490)));
+    assert!(diagnostics.is_empty());
+    assert_eq!(program.clone(), program);
+}
+
+#[test]
+fn nesting_past_the_limit_is_reported_once() {
+    let depth = 5000;
+    for source in [
+        format!("x = {}1{};\nlet y = 2;", "(".repeat(depth), ")".repeat(depth)),
+        format!("{}g(){};", "f(function () { ".repeat(depth), " })".repeat(depth)),
+        format!("x = {}1{};", "{ a: ".repeat(depth), " }".repeat(depth)),
+        format!("{}a{} = x;", "[".repeat(depth), "]".repeat(depth)),
+        format!("let x: {}T{};", "Array<".repeat(depth), ">".repeat(depth)),
+        format!("{}{}", "{".repeat(depth), "}".repeat(depth)),
+        format!("x = {}y;", "!".repeat(depth)),
+        format!("x = {}1;", "1 ** ".repeat(depth)),
+    ] {
+        let (_, diagnostics) = parse(&source);
+        let too_deep = diagnostics.iter().filter(|d| d.message.contains("nested too deeply")).count();
+        assert_eq!(too_deep, 1, "{:?}", &source[..20]);
+        assert!(diagnostics.len() <= 2, "{:?}", diagnostics);
+    }
+}
+
+/// The single template element of `` tag`...` `` or `` `...` `` in `source`
+fn template_quasi(statements: &[Statement]) -> &TemplateElement {
+    let expression = match statements {
+        [Statement::Expression(expression)] => expression,
+        statements => panic!("expected an expression statement, found {:?}", statements),
+    };
+    match expression {
+        Expression::TaggedTemplate(tagged) => &tagged.quasi.quasis[0],
+        Expression::Template(template) => &template.quasis[0],
+        expression => panic!("expected a template, found {:?}", expression),
+    }
+}
+
+#[test]
+fn tagged_templates_allow_invalid_escapes() {
+    let statements = parse_ok("String.raw`\\unicode`;");
+    let quasi = template_quasi(&statements);
+    assert_eq!(quasi.cooked, None);
+    assert_eq!(quasi.raw, "\\unicode");
+
+    parse_ok("tag`\\xg${a}\\u{110000}${b}\\01`;");
+    parse_ok("tag<T>`\\u{`;");
+    parse_ok("a.b`${c}\\x`;");
+    let statements = parse_ok("tag`\\n`;");
+    assert_eq!(template_quasi(&statements).cooked.as_deref(), Some("\n"));
+}
+
+#[test]
+fn untagged_templates_report_invalid_escapes() {
+    let (program, diagnostics) = parse("`\\unicode`;");
+    assert_eq!(diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(), [Some(1125)]);
+    assert_eq!(diagnostics[0].span.start.offset, 1);
+    let quasi = template_quasi(&program.statements);
+    assert_eq!(quasi.cooked, None);
+    assert_eq!(quasi.raw, "\\unicode");
+
+    assert_eq!(error_codes("`${a}\\xg${b}\\u{`;"), [1125, 1125]);
+    assert_eq!(error_codes("type T = `\\x${string}`;"), [1125]);
+    assert_eq!(error_codes("type T = `\\x`;"), [1125]);
+    // A template inside a tagged template's substitution is not tagged itself
+    assert_eq!(error_codes("tag`${`\\x`}`;"), [1125]);
+}
+
+/// The pattern and flags of a regular expression literal
+fn regex(expression: &Expression) -> (&str, &str) {
+    match expression {
+        Expression::Literal(LiteralExpression {
+            value: Literal::RegExp { pattern, flags },
+            ..
+        }) => (pattern, flags),
+        other => panic!("expected a regular expression, found {:?}", other),
+    }
+}
+
+#[test]
+fn slash_is_division_after_an_operand_and_a_regex_elsewhere() {
+    let Expression::Binary(outer) = expression("a / b / g") else {
+        panic!("expected a division");
+    };
+    assert_eq!(outer.operator, BinaryOperator::Divide);
+    assert!(matches!(&*outer.left, Expression::Binary(inner) if inner.operator == BinaryOperator::Divide));
+
+    let Expression::Assignment(assignment) = expression("x = /a/g") else {
+        panic!("expected an assignment");
+    };
+    assert_eq!(regex(&assignment.right), ("a", "g"));
+
+    let Expression::Call(call) = expression("f(/=/, (a) / 2)") else {
+        panic!("expected a call");
+    };
+    assert_eq!(regex(&call.arguments[0]), ("=", ""));
+    assert!(matches!(&call.arguments[1], Expression::Binary(_)));
+
+    // A slash on a new line still divides
+    assert!(matches!(expression("a\n/b/g"), Expression::Binary(_)));
+    parse_ok("function f(s) { return /x/i.test(s) ? 1 / 2 : /y/; }");
+}
+
+#[test]
+fn contextual_keywords_are_identifiers_elsewhere() {
+    let words = [
+        "type", "from", "as", "async", "await", "readonly", "abstract", "declare", "module", "namespace",
+        "global", "satisfies", "keyof", "infer", "is", "asserts", "unique", "override", "accessor",
+        "out", "using", "of", "get", "set",
+    ];
+    for word in words {
+        let source = format!("let {word} = 1; {word} = {word} + obj.{word}; function f({word}: string) {{}}");
+        let statements = parse_ok(&source);
+        let Statement::VariableStatement(variable) = &statements[0] else {
+            panic!("expected a variable for {:?}", word);
+        };
+        assert!(
+            matches!(&variable.declarations[0].name, BindingPattern::Identifier(name) if name.name == word),
+            "{}",
+            word
+        );
+    }
+    parse_ok("class C { get; set; type = 1; declare: number; readonly readonly = 1; async() {} static of() {} }");
+    parse_ok("for (const of of []) {}\nlet x = { get: 1, set() {}, async: 2, type };");
+    parse_ok("import { as as as } from 'm';\ntype as = string;\nlet is = (x: unknown): x is string => true;");
+}
+
+/// Render an expression of identifiers and operators with every operation parenthesized
+fn grouping(expression: &Expression) -> String {
+    match expression {
+        Expression::Identifier(identifier) => identifier.name.clone(),
+        Expression::Binary(binary) => {
+            let operator = match binary.operator {
+                BinaryOperator::Add => "+",
+                BinaryOperator::Subtract => "-",
+                BinaryOperator::Multiply => "*",
+                BinaryOperator::Exponent => "**",
+                BinaryOperator::LessThan => "<",
+                BinaryOperator::StrictEqual => "===",
+                BinaryOperator::LogicalAnd => "&&",
+                BinaryOperator::LogicalOr => "||",
+                BinaryOperator::NullishCoalescing => "??",
+                BinaryOperator::BitwiseOr => "|",
+                BinaryOperator::BitwiseAnd => "&",
+                BinaryOperator::LeftShift => "<<",
+                BinaryOperator::In => "in",
+                ref operator => panic!("unexpected operator {:?}", operator),
+            };
+            format!("({} {} {})", grouping(&binary.left), operator, grouping(&binary.right))
+        }
+        Expression::Unary(unary) => match unary.operator {
+            UnaryOperator::Minus => format!("(-{})", grouping(&unary.operand)),
+            UnaryOperator::LogicalNot => format!("(!{})", grouping(&unary.operand)),
+            UnaryOperator::Typeof => format!("(typeof {})", grouping(&unary.operand)),
+            UnaryOperator::PostIncrement => format!("({}++)", grouping(&unary.operand)),
+            ref operator => panic!("unexpected operator {:?}", operator),
+        },
+        Expression::Conditional(conditional) => format!(
+            "({} ? {} : {})",
+            grouping(&conditional.test),
+            grouping(&conditional.consequent),
+            grouping(&conditional.alternate)
+        ),
+        Expression::Assignment(assignment) => {
+            let AssignmentTarget::Simple(target) = &assignment.left else {
+                panic!("unexpected assignment target {:?}", assignment.left);
+            };
+            format!("({} = {})", grouping(target), grouping(&assignment.right))
+        }
+        Expression::Parenthesized(parenthesized) => grouping(&parenthesized.expression),
+        expression => panic!("unexpected expression {:?}", expression),
+    }
+}
+
+#[test]
+fn binary_operators_follow_precedence_and_associativity() {
+    let cases = [
+        ("a + b * c", "(a + (b * c))"),
+        ("a * b + c", "((a * b) + c)"),
+        ("a - b - c", "((a - b) - c)"),
+        ("a ** b ** c", "(a ** (b ** c))"),
+        ("a * b ** c", "(a * (b ** c))"),
+        ("a << b + c", "(a << (b + c))"),
+        ("a < b === c", "((a < b) === c)"),
+        ("a | b & c", "(a | (b & c))"),
+        ("a || b && c", "(a || (b && c))"),
+        ("a ?? b ?? c", "((a ?? b) ?? c)"),
+        ("a in b < c", "((a in b) < c)"),
+        ("-a * b", "((-a) * b)"),
+        ("!a++ + b", "((!(a++)) + b)"),
+        ("typeof a === b", "((typeof a) === b)"),
+        ("a = b = c", "(a = (b = c))"),
+        ("a ? b : c ? d : e", "(a ? b : (c ? d : e))"),
+        ("a || b ? c = d : e", "((a || b) ? (c = d) : e)"),
+        ("(a + b) * c", "((a + b) * c)"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(grouping(&expression(source)), expected, "{}", source);
+    }
+}
+
+#[test]
+fn ambiguous_operator_mixes_are_reported() {
+    assert_eq!(error_codes("-a ** b;"), [17006]);
+    assert_eq!(error_codes("typeof a ** b;"), [17006]);
+    assert!(error_codes("(-a) ** b; a ** -b;").is_empty());
+    assert_eq!(error_codes("a ?? b || c;"), [5076]);
+    assert_eq!(error_codes("a && b ?? c;"), [5076]);
+    assert!(error_codes("(a && b) ?? c; a ?? (b || c);").is_empty());
+}
+
+/// Parse `source` as the type of a type alias
+fn type_node(source: &str) -> TypeNode {
+    let statements = parse_ok(&format!("type T = {};", source));
+    match declaration(&statements[0]) {
+        Declaration::Type(alias) => alias.type_annotation.clone(),
+        declaration => panic!("expected a type alias, found {:?}", declaration),
+    }
+}
+
+fn entity_name(name: &EntityName) -> String {
+    match name {
+        EntityName::Identifier(identifier) => identifier.name.clone(),
+        EntityName::Qualified(qualified) => format!("{}.{}", entity_name(&qualified.left), qualified.right.name),
+    }
+}
+
+/// Render a type with every compound type parenthesized
+fn type_shape(type_node: &TypeNode) -> String {
+    let list = |types: &[TypeNode], separator: &str| {
+        types.iter().map(type_shape).collect::<Vec<_>>().join(separator)
+    };
+    match type_node {
+        TypeNode::Keyword(keyword) => format!("{:?}", keyword.kind).to_lowercase(),
+        TypeNode::Reference(reference) if reference.type_arguments.is_empty() => entity_name(&reference.name),
+        TypeNode::Reference(reference) => {
+            format!("{}<{}>", entity_name(&reference.name), list(&reference.type_arguments, ", "))
+        }
+        TypeNode::Literal(literal) => match &literal.value {
+            Literal::String(value) => format!("{:?}", value),
+            Literal::Number(value) => value.to_string(),
+            value => format!("{:?}", value),
+        },
+        TypeNode::Union(union) => format!("({})", list(&union.types, " | ")),
+        TypeNode::Intersection(intersection) => format!("({})", list(&intersection.types, " & ")),
+        TypeNode::Array(array) => format!("{}[]", type_shape(&array.element_type)),
+        TypeNode::Tuple(tuple) => {
+            let elements: Vec<_> = tuple
+                .elements
+                .iter()
+                .map(|element| {
+                    format!(
+                        "{}{}{}{}",
+                        if element.rest { "..." } else { "" },
+                        element.name.as_ref().map(|name| format!("{}: ", name.name)).unwrap_or_default(),
+                        type_shape(&element.type_node),
+                        if element.optional { "?" } else { "" },
+                    )
+                })
+                .collect();
+            format!("[{}]", elements.join(", "))
+        }
+        TypeNode::Function(function) => {
+            format!("(({} params) => {})", function.parameters.len(), type_shape(&function.return_type))
+        }
+        TypeNode::Constructor(function) => {
+            format!("(new ({} params) => {})", function.parameters.len(), type_shape(&function.return_type))
+        }
+        TypeNode::Operator(operator) => {
+            let operator_name = format!("{:?}", operator.operator).to_lowercase();
+            format!("({} {})", operator_name, type_shape(&operator.type_node))
+        }
+        TypeNode::IndexedAccess(access) => {
+            format!("{}[{}]", type_shape(&access.object_type), type_shape(&access.index_type))
+        }
+        TypeNode::Conditional(conditional) => format!(
+            "({} extends {} ? {} : {})",
+            type_shape(&conditional.check_type),
+            type_shape(&conditional.extends_type),
+            type_shape(&conditional.true_type),
+            type_shape(&conditional.false_type)
+        ),
+        TypeNode::Infer(infer) => match &infer.type_parameter.constraint {
+            Some(constraint) => format!("(infer {} extends {})", infer.type_parameter.name.name, type_shape(constraint)),
+            None => format!("(infer {})", infer.type_parameter.name.name),
+        },
+        TypeNode::Query(query) => format!("(typeof {})", entity_name(&query.expr_name)),
+        TypeNode::Parenthesized(parenthesized) => type_shape(&parenthesized.type_node),
+        type_node => panic!("unexpected type {:?}", type_node),
+    }
+}
+
+#[test]
+fn type_operators_follow_precedence() {
+    let cases = [
+        ("A | B & C", "(A | (B & C))"),
+        ("| A | B", "(A | B)"),
+        ("& A", "A"),
+        ("keyof T[]", "(keyof T[])"),
+        ("(keyof T)[]", "(keyof T)[]"),
+        ("readonly string[][]", "(readonly string[][])"),
+        ("T['a'][number]", "T[\"a\"][number]"),
+        ("A | (() => void)[]", "(A | ((0 params) => void)[])"),
+        ("(a: A, b?: B) => C | D", "((2 params) => (C | D))"),
+        ("new () => A", "(new (0 params) => A)"),
+        ("typeof a.b | unique symbol", "((typeof a.b) | (unique symbol))"),
+        ("Map<string, Set<T>>", "Map<string, Set<T>>"),
+        ("[a: A, b?: B, ...C[]]", "[a: A, b: B?, ...C[]]"),
+        ("[A?, ...B]", "[A?, ...B]"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(type_shape(&type_node(source)), expected, "{}", source);
+    }
+}
+
+#[test]
+fn conditional_types_nest_to_the_right() {
+    let cases = [
+        ("A extends B ? C : D extends E ? F : G", "(A extends B ? C : (D extends E ? F : G))"),
+        ("T extends (infer U)[] ? U : never", "(T extends (infer U)[] ? U : never)"),
+        (
+            "T extends [infer H extends string, ...infer R] ? H : R",
+            "(T extends [(infer H extends string), ...(infer R)] ? H : R)",
+        ),
+        // The extends type is only a conditional type when parenthesized
+        ("A extends (B extends C ? D : E) ? F : G", "(A extends (B extends C ? D : E) ? F : G)"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(type_shape(&type_node(source)), expected, "{}", source);
+    }
+}
+
+#[test]
+fn mapped_and_template_literal_types() {
+    let TypeNode::Mapped(mapped) = type_node("{ -readonly [K in keyof T as `get${K}`]+?: T[K] }") else {
+        panic!("expected a mapped type");
+    };
+    assert_eq!(mapped.readonly_modifier, Some(MappedTypeModifier::Remove));
+    assert_eq!(mapped.optional_modifier, Some(MappedTypeModifier::Add));
+    assert_eq!(mapped.type_parameter.name.name, "K");
+    assert_eq!(type_shape(mapped.type_parameter.constraint.as_ref().unwrap()), "(keyof T)");
+    assert!(matches!(mapped.name_type, Some(TypeNode::TemplateLiteral(ref template)) if template.types.len() == 1));
+    assert_eq!(type_shape(mapped.type_node.as_ref().unwrap()), "T[K]");
+
+    let TypeNode::TypeLiteral(literal) = type_node("{ [key: string]: number; a?: A }") else {
+        panic!("expected a type literal");
+    };
+    assert_eq!(literal.members.len(), 2);
+}
+
+#[test]
+fn type_predicates_in_return_types() {
+    let statements = parse_ok(
+        "function f(x: unknown): x is string {}\nfunction g(x): asserts x {}\nclass C { h(): asserts this is D {} }",
+    );
+    let predicate = |statement: &Statement| match declaration(statement) {
+        Declaration::Function(function) => match &function.return_type {
+            Some(TypeNode::Predicate(predicate)) => predicate.clone(),
+            other => panic!("expected a predicate, found {:?}", other),
+        },
+        other => panic!("expected a function, found {:?}", other),
+    };
+    let is_string = predicate(&statements[0]);
+    assert!(!is_string.asserts);
+    assert_eq!(is_string.parameter_name.name, "x");
+    assert_eq!(is_string.type_node.as_deref().map(type_shape).as_deref(), Some("string"));
+    let asserts = predicate(&statements[1]);
+    assert!(asserts.asserts && asserts.type_node.is_none());
+}
+
+#[test]
+fn malformed_types_are_reported() {
+    assert_eq!(error_codes("type T = ;"), [1110]);
+    assert_eq!(error_codes("let x: A | ;"), [1110]);
+    assert_eq!(error_codes("let x: [A, B;"), [1005]);
+}
+
+/// Render comparisons with every operation parenthesized
+fn comparison(expression: &Expression) -> String {
+    match expression {
+        Expression::Identifier(identifier) => identifier.name.clone(),
+        Expression::Binary(binary) => {
+            let operator = match binary.operator {
+                BinaryOperator::LessThan => "<",
+                BinaryOperator::GreaterThan => ">",
+                BinaryOperator::GreaterThanOrEqual => ">=",
+                BinaryOperator::RightShift => ">>",
+                ref operator => panic!("unexpected operator {:?}", operator),
+            };
+            format!("({} {} {})", comparison(&binary.left), operator, comparison(&binary.right))
+        }
+        Expression::Unary(unary) if unary.operator == UnaryOperator::Plus => {
+            format!("(+{})", comparison(&unary.operand))
+        }
+        Expression::Sequence(sequence) => {
+            let expressions: Vec<_> = sequence.expressions.iter().map(comparison).collect();
+            format!("({})", expressions.join(", "))
+        }
+        Expression::Parenthesized(parenthesized) => comparison(&parenthesized.expression),
+        expression => panic!("unexpected expression {:?}", expression),
+    }
+}
+
+#[test]
+fn type_arguments_in_expressions_versus_comparisons() {
+    let Expression::Call(call) = expression("f<T, U[]>(x)") else {
+        panic!("expected a call");
+    };
+    assert_eq!(call.type_arguments.len(), 2);
+    assert_eq!(call.arguments.len(), 1);
+    assert!(matches!(expression("f < T > (x)"), Expression::Call(call) if call.type_arguments.len() == 1));
+    assert!(matches!(
+        expression("f<T>`a${b}`"),
+        Expression::TaggedTemplate(tagged) if tagged.type_arguments.len() == 1
+    ));
+    assert!(matches!(
+        expression("new A<T>()"),
+        Expression::New(new) if new.type_arguments.len() == 1 && new.arguments.is_some()
+    ));
+    assert!(matches!(
+        expression("f<T>;"),
+        Expression::Instantiation(instantiation) if instantiation.type_arguments.len() == 1
+    ));
+    assert!(matches!(expression("x = f<T>\nlet"), Expression::Assignment(_)));
+
+    let cases = [
+        ("a < b > c", "((a < b) > c)"),
+        ("a < b > +c", "((a < b) > (+c))"),
+        ("a < b >= c", "((a < b) >= c)"),
+        ("a < b >> c", "(a < (b >> c))"),
+        ("(a < b, c > d)", "((a < b), (c > d))"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(comparison(&expression(source)), expected, "{}", source);
+    }
+}
+
+/// The members of the class declared by `source`
+fn class_members(source: &str) -> Vec<ClassMember> {
+    let statements = parse_ok(source);
+    match declaration(&statements[0]) {
+        Declaration::Class(class) => class.body.clone(),
+        declaration => panic!("expected a class, found {:?}", declaration),
+    }
+}
+
+fn property_name(name: &PropertyName) -> String {
+    match name {
+        PropertyName::Identifier(identifier) => identifier.name.clone(),
+        PropertyName::Private(identifier) => format!("#{}", identifier.name),
+        PropertyName::Literal(literal) => match &literal.value {
+            Literal::String(value) => format!("{:?}", value),
+            Literal::Number(value) => value.to_string(),
+            value => panic!("unexpected name {:?}", value),
+        },
+        PropertyName::Computed(_) => "[computed]".to_string(),
+    }
+}
+
+#[test]
+fn class_members_of_every_kind() {
+    let members = class_members(
+        "class C<T> extends B<T> implements I, J {\n\
+           static readonly #x: number = 1;\n\
+           declare y: string;\n\
+           w!: string;\n\
+           private accessor z = 2;\n\
+           constructor(private a: number, readonly b?: string) { super(); }\n\
+           get v(): number { return 1; }\n\
+           set v(value) {}\n\
+           static async *gen() {}\n\
+           protected abstract m?(): void;\n\
+           [Symbol.iterator]() {}\n\
+           'quoted'() {}\n\
+           0 = 1;\n\
+           static [key: string]: any;\n\
+           static { init(); }\n\
+           override f<U>(u: U): U { return u; }\n\
+           ;\n\
+         }",
+    );
+    let summary: Vec<_> = members
+        .iter()
+        .map(|member| match member {
+            ClassMember::Method(method) => format!("{:?} {}", method.kind, property_name(&method.name)),
+            ClassMember::Property(property) => format!("Property {}", property_name(&property.name)),
+            ClassMember::Constructor(constructor) => format!("Constructor {}", constructor.parameters.len()),
+            ClassMember::IndexSignature(_) => "IndexSignature".to_string(),
+            ClassMember::StaticBlock(block) => format!("StaticBlock {}", block.statements.len()),
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            "Property #x",
+            "Property y",
+            "Property w",
+            "Property z",
+            "Constructor 2",
+            "Get v",
+            "Set v",
+            "Method gen",
+            "Method m",
+            "Method [computed]",
+            "Method \"quoted\"",
+            "Property 0",
+            "IndexSignature",
+            "StaticBlock 1",
+            "Method f",
+        ]
+    );
+
+    let property = |index: usize| match &members[index] {
+        ClassMember::Property(property) => property.clone(),
+        member => panic!("expected a property, found {:?}", member),
+    };
+    let method = |index: usize| match &members[index] {
+        ClassMember::Method(method) => method.clone(),
+        member => panic!("expected a method, found {:?}", member),
+    };
+    let x = property(0);
+    assert!(x.modifiers.is_static && x.modifiers.is_readonly && x.value.is_some());
+    assert!(property(1).modifiers.is_declare);
+    assert!(property(2).definite);
+    let z = property(3);
+    assert!(z.modifiers.is_accessor);
+    assert_eq!(z.modifiers.accessibility, Some(Accessibility::Private));
+    let gen = method(7);
+    assert!(gen.modifiers.is_static && gen.is_async && gen.is_generator);
+    let m = method(8);
+    assert!(m.modifiers.is_abstract && m.optional && m.body.is_none());
+    assert_eq!(m.modifiers.accessibility, Some(Accessibility::Protected));
+    let f = method(14);
+    assert!(f.modifiers.is_override);
+    assert_eq!(f.type_parameters.len(), 1);
+}
+
+#[test]
+fn modifier_names_are_member_names_when_nothing_follows() {
+    let members = class_members("class C { static; readonly?: number; get() {} async\nfoo() {} static static() {} }");
+    let names: Vec<_> = members
+        .iter()
+        .map(|member| match member {
+            ClassMember::Property(property) => property_name(&property.name),
+            ClassMember::Method(method) => property_name(&method.name),
+            member => panic!("unexpected member {:?}", member),
+        })
+        .collect();
+    assert_eq!(names, ["static", "readonly", "get", "async", "foo", "static"]);
+}
+
+#[test]
+fn repeated_modifiers_are_reported() {
+    assert_eq!(error_codes("class C { public private x; }"), [1028]);
+    assert_eq!(error_codes("class C { static static y; readonly readonly z; }"), [1030, 1030]);
+}
+
+/// Describe an arrow function as its parameter count, asyncness and body kind
+fn arrow(expression: &Expression) -> (usize, bool, &'static str) {
+    match expression {
+        Expression::Arrow(arrow) => {
+            let body = match *arrow.body {
+                ArrowFunctionBody::Block(_) => "block",
+                ArrowFunctionBody::Expression(_) => "expression",
+            };
+            (arrow.parameters.len(), arrow.is_async, body)
+        }
+        expression => panic!("expected an arrow function, found {:?}", expression),
+    }
+}
+
+#[test]
+fn arrow_functions_versus_parenthesized_expressions() {
+    let cases = [
+        ("() => {}", (0, false, "block")),
+        ("x => x", (1, false, "expression")),
+        ("(a, b) => ({})", (2, false, "expression")),
+        ("(a: T, b?: U): V => a", (2, false, "expression")),
+        ("({ a }, [b] = [], ...rest) => a", (3, false, "expression")),
+        ("async (a) => { await a; }", (1, true, "block")),
+        ("async a => a", (1, true, "expression")),
+        ("async () => 1", (0, true, "expression")),
+        ("<T>(x: T) => x", (1, false, "expression")),
+        ("(a\n) => a", (1, false, "expression")),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(arrow(&expression(source)), expected, "{}", source);
+    }
+    let Expression::Arrow(typed) = expression("<T,>(x: T): x is T => true") else {
+        panic!("expected an arrow function");
+    };
+    assert_eq!(typed.type_parameters.len(), 1);
+    assert!(matches!(typed.return_type, Some(TypeNode::Predicate(_))));
+
+    assert!(matches!(expression("(a)"), Expression::Parenthesized(_)));
+    assert!(matches!(
+        expression("(a, b)"),
+        Expression::Parenthesized(ref inner) if matches!(*inner.expression, Expression::Sequence(_))
+    ));
+    assert!(matches!(expression("async(a, b)"), Expression::Call(_)));
+    assert!(matches!(expression("(a) + (b)"), Expression::Binary(_)));
+    let Expression::Conditional(conditional) = expression("a ? (b) : c") else {
+        panic!("expected a conditional");
+    };
+    assert!(matches!(*conditional.consequent, Expression::Parenthesized(_)));
+    let Expression::Conditional(conditional) = expression("a ? (b): c => d : e") else {
+        panic!("expected a conditional");
+    };
+    assert_eq!(arrow(&conditional.consequent), (1, false, "expression"));
+}
+
+#[test]
+fn arrow_after_a_line_break_is_reported() {
+    assert_eq!(error_codes("let f = (a)\n=> a;"), [1200]);
+    assert_eq!(error_codes("let f = a\n=> a;"), [1200]);
+}
+
+#[test]
+fn nested_templates_and_object_literals_in_substitutions() {
+    let Expression::Template(template) = expression("`a${ `b${ { c: 1 }.c }` }d${e}`") else {
+        panic!("expected a template literal");
+    };
+    assert_eq!(template.quasis.len(), 3);
+    assert_eq!(template.expressions.len(), 2);
+    let Expression::Template(inner) = &template.expressions[0] else {
+        panic!("expected a nested template literal");
+    };
+    assert!(matches!(&inner.expressions[0], Expression::Member(member) if matches!(*member.object, Expression::Object(_))));
+}
+
+#[test]
+fn unterminated_templates_are_reported_once() {
+    assert_eq!(error_codes("let x = `abc"), [1160]);
+    assert_eq!(error_codes("let x = `a${b}c"), [1160]);
+    assert_eq!(error_codes("let x = `a${b"), [1005]);
+    assert_eq!(error_codes("let x = `a${b;\nlet y = 1;"), [1005]);
+}
+
+#[test]
+fn a_mistake_in_an_enum_member_gives_one_error() {
+    let (program, diagnostics) = parse("enum E { A B, C }\nlet x = 1;");
+    let errors: Vec<_> = diagnostics.iter().map(|d| (d.code, d.span.start.offset)).collect();
+    assert_eq!(errors, [(Some(1005), 11)]);
+    let Declaration::Enum(enumeration) = declaration(&program.statements[0]) else {
+        panic!("expected an enum");
+    };
+    let names: Vec<_> = enumeration.members.iter().map(|member| property_name(&member.name)).collect();
+    assert_eq!(names, ["A", "B", "C"]);
+    assert_eq!(program.statements.len(), 2);
+
+    assert_eq!(error_codes("enum E { A = , B }"), [1109]);
+    assert_eq!(error_codes("enum E { A, , B, }"), [1003]);
+    assert_eq!(error_codes("enum E {\n  A = 1 +,\n  B\n}"), [1109]);
+}
+
+#[test]
+fn a_mistake_in_an_object_literal_element_gives_one_error() {
+    let (program, diagnostics) = parse("let x = {\n  a: 1,,\n  b: 2\n};");
+    let errors: Vec<_> = diagnostics.iter().map(|d| (d.code, d.span.start.offset)).collect();
+    assert_eq!(errors, [(Some(1003), 17)]);
+    let Statement::VariableStatement(variable) = &program.statements[0] else {
+        panic!("expected a variable statement");
+    };
+    let Some(Expression::Object(object)) = &variable.declarations[0].init else {
+        panic!("expected an object literal");
+    };
+    assert_eq!(object.properties.len(), 2);
+
+    assert_eq!(error_codes("let x = { a: 1 b: 2 };"), [1005]);
+    assert_eq!(error_codes("let x = { a: 1; b: 2 };"), [1005]);
+    assert_eq!(error_codes("let x = { a: , b: 2 };"), [1109]);
+    assert_eq!(error_codes("let x = { a: 1, };"), []);
+}
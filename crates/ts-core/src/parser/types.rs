@@ -0,0 +1,860 @@
+//! Type parsing.
+//!
+//! Follows the structure of tsc's type grammar: a type is a function or
+//! constructor type, or a union of intersections of type operators applied to
+//! postfix types, optionally followed by a conditional `extends` clause.
+
+use super::{ParseResult, Parser};
+use crate::ast::{
+    ArrayType, ConditionalType, EntityName, FunctionType, Identifier, ImportType,
+    IndexedAccessType, InferType, IntersectionType, KeywordType, KeywordTypeKind, Literal,
+    LiteralType, MappedType, MappedTypeModifier, ParenthesizedType, QualifiedName,
+    TemplateElement, TemplateLiteralType, TupleElement, TupleType, TypeLiteral, TypeNode,
+    TypeOperator, TypeOperatorKind, TypeParameter, TypePredicate, TypeQuery, TypeReference,
+    UnionType,
+};
+use crate::lexer::{ContextualKeyword, TokenKind};
+use crate::utils::intern::resolve;
+use crate::utils::span::Position;
+
+impl<'a> Parser<'a> {
+    /// Parse `: Type` if the current token is a colon
+    pub(super) fn parse_optional_type_annotation(&mut self) -> ParseResult<Option<TypeNode>> {
+        if self.eat(TokenKind::Colon) {
+            Ok(Some(self.parse_type()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parse `: Type` in return type position, where a type predicate is allowed
+    pub(super) fn parse_optional_return_type(&mut self) -> ParseResult<Option<TypeNode>> {
+        if self.eat(TokenKind::Colon) {
+            Ok(Some(self.parse_type_or_type_predicate()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Parse a type
+    pub(super) fn parse_type(&mut self) -> ParseResult<TypeNode> {
+        self.nested(Self::parse_type_worker)
+    }
+
+    fn parse_type_worker(&mut self) -> ParseResult<TypeNode> {
+        if self.is_start_of_function_or_constructor_type() {
+            return self.parse_function_or_constructor_type();
+        }
+
+        let start = self.start();
+        let check_type = self.parse_union_type()?;
+        if !self.disallow_conditional_types
+            && !self.current_token.preceded_by_newline
+            && self.eat(TokenKind::Extends)
+        {
+            // Conditional type: `T extends U ? X : Y`
+            let extends_type = self.with_conditional_types(false, Self::parse_type)?;
+            self.expect(TokenKind::Question)?;
+            let true_type = self.with_conditional_types(true, Self::parse_type)?;
+            self.expect(TokenKind::Colon)?;
+            let false_type = self.with_conditional_types(true, Self::parse_type)?;
+            return Ok(TypeNode::Conditional(ConditionalType {
+                check_type: Box::new(check_type),
+                extends_type: Box::new(extends_type),
+                true_type: Box::new(true_type),
+                false_type: Box::new(false_type),
+                span: self.span_from(start),
+            }));
+        }
+        Ok(check_type)
+    }
+
+    /// Run `parse` with conditional types allowed or disallowed. They are
+    /// disallowed in the extends clause of a conditional type, so that in
+    /// `T extends infer U extends string ? X : Y` the constraint ends before `?`.
+    fn with_conditional_types<T>(
+        &mut self,
+        allowed: bool,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let saved = self.disallow_conditional_types;
+        self.disallow_conditional_types = !allowed;
+        let result = parse(self);
+        self.disallow_conditional_types = saved;
+        result
+    }
+
+    /// Parse a type, or a type predicate `x is T` as allowed in return types
+    fn parse_type_or_type_predicate(&mut self) -> ParseResult<TypeNode> {
+        let is_predicate = self.is_identifier()
+            && self.peek_token().is_some_and(|token| {
+                token.is_contextual_keyword(ContextualKeyword::Is) && !token.preceded_by_newline
+            });
+        if !is_predicate {
+            return self.parse_type();
+        }
+
+        let start = self.start();
+        let parameter_name = self.parse_identifier()?;
+        self.advance();
+        let type_node = self.parse_type()?;
+        Ok(TypeNode::Predicate(TypePredicate {
+            asserts: false,
+            parameter_name,
+            type_node: Some(Box::new(type_node)),
+            span: self.span_from(start),
+        }))
+    }
+
+    fn is_start_of_function_or_constructor_type(&mut self) -> bool {
+        match self.kind() {
+            TokenKind::Less | TokenKind::New => true,
+            TokenKind::LeftParen => self.look_ahead(Self::is_unambiguously_start_of_function_type),
+            _ => self.at_contextual(ContextualKeyword::Abstract) && self.peek_kind(0) == TokenKind::New,
+        }
+    }
+
+    /// Decide whether a `(` begins the parameters of a function type rather than
+    /// a parenthesized type, by looking at the start of the first parameter
+    fn is_unambiguously_start_of_function_type(&mut self) -> bool {
+        self.advance();
+        if matches!(self.kind(), TokenKind::RightParen | TokenKind::DotDotDot) {
+            // `()` or `(...`
+            return true;
+        }
+        if self.skip_parameter_start() {
+            // `(x:`, `(x,`, `(x?` or `(x=`
+            if matches!(
+                self.kind(),
+                TokenKind::Colon | TokenKind::Comma | TokenKind::Question | TokenKind::Equal
+            ) {
+                return true;
+            }
+            // `(x) =>`
+            if self.at(TokenKind::RightParen) {
+                self.advance();
+                return self.at(TokenKind::Arrow);
+            }
+        }
+        false
+    }
+
+    /// Skip the modifiers and name or binding pattern at the start of a
+    /// parameter, returning whether there was one
+    fn skip_parameter_start(&mut self) -> bool {
+        while self.at_member_modifier() {
+            self.advance();
+        }
+        if self.is_identifier() || self.at(TokenKind::This) {
+            self.advance();
+            return true;
+        }
+        match self.kind() {
+            TokenKind::LeftBracket => self.skip_balanced(TokenKind::LeftBracket, TokenKind::RightBracket).is_ok(),
+            TokenKind::LeftBrace => self.skip_balanced(TokenKind::LeftBrace, TokenKind::RightBrace).is_ok(),
+            _ => false,
+        }
+    }
+
+    /// Parse `<T>(x: T) => U`, `new (x: T) => U` or `abstract new () => U`
+    fn parse_function_or_constructor_type(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        let is_abstract = self.eat_contextual(ContextualKeyword::Abstract);
+        let is_constructor = self.eat(TokenKind::New);
+        let type_parameters = self.parse_type_parameters()?;
+        let parameters = self.parse_parameter_list()?;
+        self.expect(TokenKind::Arrow)?;
+        let return_type = self.parse_type_or_type_predicate()?;
+        let function = Box::new(FunctionType {
+            type_parameters,
+            parameters,
+            return_type,
+            is_abstract,
+            span: self.span_from(start),
+        });
+        Ok(if is_constructor {
+            TypeNode::Constructor(function)
+        } else {
+            TypeNode::Function(function)
+        })
+    }
+
+    fn parse_union_type(&mut self) -> ParseResult<TypeNode> {
+        self.parse_union_or_intersection_type(TokenKind::Pipe, Self::parse_intersection_type)
+    }
+
+    fn parse_intersection_type(&mut self) -> ParseResult<TypeNode> {
+        self.parse_union_or_intersection_type(TokenKind::Ampersand, Self::parse_type_operator_or_higher)
+    }
+
+    /// Parse constituents separated by `|` or `&`, allowing a leading operator
+    fn parse_union_or_intersection_type(
+        &mut self,
+        operator: TokenKind,
+        parse_constituent: fn(&mut Self) -> ParseResult<TypeNode>,
+    ) -> ParseResult<TypeNode> {
+        let start = self.start();
+        let has_leading_operator = self.eat(operator);
+        let first = if has_leading_operator {
+            self.parse_constituent_type(operator, parse_constituent)?
+        } else {
+            parse_constituent(self)?
+        };
+        if !has_leading_operator && !self.at(operator) {
+            return Ok(first);
+        }
+
+        let mut types = vec![first];
+        while self.eat(operator) {
+            types.push(self.parse_constituent_type(operator, parse_constituent)?);
+        }
+        if types.len() == 1 {
+            return Ok(types.pop().unwrap());
+        }
+        let span = self.span_from(start);
+        Ok(if operator == TokenKind::Pipe {
+            TypeNode::Union(UnionType { types, span })
+        } else {
+            TypeNode::Intersection(IntersectionType { types, span })
+        })
+    }
+
+    /// Parse a constituent after `|` or `&`. A function type there must be
+    /// parenthesized; it is parsed anyway and reported.
+    fn parse_constituent_type(
+        &mut self,
+        operator: TokenKind,
+        parse_constituent: fn(&mut Self) -> ParseResult<TypeNode>,
+    ) -> ParseResult<TypeNode> {
+        if !self.is_start_of_function_or_constructor_type() {
+            return parse_constituent(self);
+        }
+        let function = self.parse_function_or_constructor_type()?;
+        let in_union = operator == TokenKind::Pipe;
+        let (code, message) = match (&function, in_union) {
+            (TypeNode::Constructor(_), true) => (1386, "Constructor type notation must be parenthesized when used in a union type."),
+            (TypeNode::Constructor(_), false) => (1388, "Constructor type notation must be parenthesized when used in an intersection type."),
+            (_, true) => (1385, "Function type notation must be parenthesized when used in a union type."),
+            (_, false) => (1387, "Function type notation must be parenthesized when used in an intersection type."),
+        };
+        self.report(code, message, function.span());
+        Ok(function)
+    }
+
+    /// Parse a type with any `keyof`, `unique`, `readonly` or `infer` prefix
+    fn parse_type_operator_or_higher(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        let operator = match self.current_token.contextual_keyword() {
+            Some(ContextualKeyword::Keyof) => Some(TypeOperatorKind::Keyof),
+            Some(ContextualKeyword::Unique) => Some(TypeOperatorKind::Unique),
+            Some(ContextualKeyword::Readonly) => Some(TypeOperatorKind::Readonly),
+            Some(ContextualKeyword::Infer) => return self.parse_infer_type(),
+            _ => None,
+        };
+        if let Some(operator) = operator {
+            self.advance();
+            let type_node = self.parse_type_operator_or_higher()?;
+            return Ok(TypeNode::Operator(TypeOperator {
+                operator,
+                type_node: Box::new(type_node),
+                span: self.span_from(start),
+            }));
+        }
+        self.with_conditional_types(true, Self::parse_postfix_type)
+    }
+
+    /// Parse `infer U`, with a constraint `extends C` unless that would be the
+    /// start of a conditional type
+    fn parse_infer_type(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        self.advance();
+        let name = self.parse_identifier()?;
+        let constraint = self.try_parse_infer_constraint();
+        Ok(TypeNode::Infer(InferType {
+            type_parameter: TypeParameter {
+                span: self.span_from(name.span.start),
+                name,
+                is_in: false,
+                is_out: false,
+                is_const: false,
+                constraint: constraint.map(Box::new),
+                default: None,
+            },
+            span: self.span_from(start),
+        }))
+    }
+
+    fn try_parse_infer_constraint(&mut self) -> Option<TypeNode> {
+        if !self.at(TokenKind::Extends) {
+            return None;
+        }
+        let checkpoint = self.checkpoint();
+        self.advance();
+        let constraint = self.speculate(|parser| parser.with_conditional_types(false, Self::parse_type));
+        match constraint {
+            Ok(constraint) if self.disallow_conditional_types || !self.at(TokenKind::Question) => {
+                Some(constraint)
+            }
+            _ => {
+                self.rewind(checkpoint);
+                None
+            }
+        }
+    }
+
+    /// Parse a type followed by array `[]` and indexed access `[K]` suffixes
+    fn parse_postfix_type(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        let mut type_node = self.parse_non_array_type()?;
+        while self.at(TokenKind::LeftBracket) && !self.current_token.preceded_by_newline {
+            self.advance();
+            type_node = if self.eat(TokenKind::RightBracket) {
+                TypeNode::Array(ArrayType {
+                    element_type: Box::new(type_node),
+                    span: self.span_from(start),
+                })
+            } else {
+                let index_type = self.parse_type()?;
+                self.expect(TokenKind::RightBracket)?;
+                TypeNode::IndexedAccess(IndexedAccessType {
+                    object_type: Box::new(type_node),
+                    index_type: Box::new(index_type),
+                    span: self.span_from(start),
+                })
+            };
+        }
+        Ok(type_node)
+    }
+
+    fn parse_non_array_type(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        if let Some(kind) = self.keyword_type_kind() {
+            if self.peek_kind(0) != TokenKind::Dot {
+                self.advance();
+                return Ok(TypeNode::Keyword(KeywordType {
+                    kind,
+                    span: self.span_from(start),
+                }));
+            }
+        }
+
+        match self.kind() {
+            TokenKind::String(_) | TokenKind::Number(_) | TokenKind::BigInt(_)
+            | TokenKind::Boolean(_) | TokenKind::Null | TokenKind::NoSubstitutionTemplate { .. } => {
+                self.parse_literal_type()
+            }
+            TokenKind::Minus => {
+                self.advance();
+                let value = match self.kind() {
+                    TokenKind::Number(value) => Literal::Number(-value),
+                    TokenKind::BigInt(digits) => Literal::BigInt(format!("-{}", resolve(digits).unwrap_or_default())),
+                    _ => return Err(self.error(1110, "Type expected.")),
+                };
+                self.advance();
+                Ok(TypeNode::Literal(LiteralType {
+                    value,
+                    span: self.span_from(start),
+                }))
+            }
+            TokenKind::This => {
+                let keyword = self.advance();
+                if self.at_contextual(ContextualKeyword::Is) && !self.current_token.preceded_by_newline {
+                    // `this is T`
+                    self.advance();
+                    let type_node = self.parse_type()?;
+                    return Ok(TypeNode::Predicate(TypePredicate {
+                        asserts: false,
+                        parameter_name: self.identifier_from(&keyword),
+                        type_node: Some(Box::new(type_node)),
+                        span: self.span_from(start),
+                    }));
+                }
+                Ok(TypeNode::This(keyword.span))
+            }
+            TokenKind::Typeof => {
+                if self.peek_kind(0) == TokenKind::Import {
+                    self.advance();
+                    self.parse_import_type(start, true)
+                } else {
+                    self.parse_type_query()
+                }
+            }
+            TokenKind::LeftBrace => {
+                if self.look_ahead(Self::is_start_of_mapped_type) {
+                    self.parse_mapped_type()
+                } else {
+                    let members = self.parse_type_members()?;
+                    Ok(TypeNode::TypeLiteral(TypeLiteral {
+                        members,
+                        span: self.span_from(start),
+                    }))
+                }
+            }
+            TokenKind::LeftBracket => self.parse_tuple_type(),
+            TokenKind::LeftParen => {
+                self.advance();
+                let type_node = self.parse_type()?;
+                self.expect(TokenKind::RightParen)?;
+                Ok(TypeNode::Parenthesized(ParenthesizedType {
+                    type_node: Box::new(type_node),
+                    span: self.span_from(start),
+                }))
+            }
+            TokenKind::Import => self.parse_import_type(start, false),
+            TokenKind::TemplateHead { .. } => self.parse_template_literal_type(),
+            _ if self.at_contextual(ContextualKeyword::Asserts)
+                && self.peek_token().is_some_and(|token| {
+                    !token.preceded_by_newline
+                        && (matches!(token.kind, TokenKind::Identifier(_))
+                            || token.kind.text().is_some_and(|text| text.starts_with(char::is_alphabetic)))
+                }) =>
+            {
+                self.parse_asserts_type_predicate()
+            }
+            _ => self.parse_type_reference(),
+        }
+    }
+
+    /// Get the keyword type the current token names, if any
+    fn keyword_type_kind(&self) -> Option<KeywordTypeKind> {
+        if self.at(TokenKind::Void) {
+            return Some(KeywordTypeKind::Void);
+        }
+        let kind = match self.current_token.contextual_keyword()? {
+            ContextualKeyword::Any => KeywordTypeKind::Any,
+            ContextualKeyword::Unknown => KeywordTypeKind::Unknown,
+            ContextualKeyword::Number => KeywordTypeKind::Number,
+            ContextualKeyword::Bigint => KeywordTypeKind::Bigint,
+            ContextualKeyword::Boolean => KeywordTypeKind::Boolean,
+            ContextualKeyword::String => KeywordTypeKind::String,
+            ContextualKeyword::Symbol => KeywordTypeKind::Symbol,
+            ContextualKeyword::Object => KeywordTypeKind::Object,
+            ContextualKeyword::Never => KeywordTypeKind::Never,
+            ContextualKeyword::Undefined => KeywordTypeKind::Undefined,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    fn parse_literal_type(&mut self) -> ParseResult<TypeNode> {
+        let value = match self.kind() {
+            TokenKind::String(value) => Literal::String(resolve(value).unwrap_or_default()),
+            TokenKind::NoSubstitutionTemplate { cooked, .. } => {
+                if cooked.is_none() {
+                    self.report_template_escapes();
+                }
+                Literal::String(cooked.and_then(resolve).unwrap_or_default())
+            }
+            TokenKind::Number(value) => Literal::Number(value),
+            TokenKind::BigInt(digits) => Literal::BigInt(resolve(digits).unwrap_or_default()),
+            TokenKind::Boolean(value) => Literal::Boolean(value),
+            _ => Literal::Null,
+        };
+        let token = self.advance();
+        Ok(TypeNode::Literal(LiteralType {
+            value,
+            span: token.span,
+        }))
+    }
+
+    /// Parse a named type such as `A.B<C>`
+    fn parse_type_reference(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        let first = if self.at(TokenKind::Const) {
+            // `as const` and `<const>x` name the type `const`
+            let token = self.advance();
+            self.identifier_from(&token)
+        } else if self.is_identifier() {
+            self.parse_identifier()?
+        } else {
+            return Err(self.error(1110, "Type expected."));
+        };
+        let name = self.parse_entity_name_rest(start, first)?;
+        let type_arguments = self.parse_type_arguments_on_same_line()?;
+        Ok(TypeNode::Reference(TypeReference {
+            name,
+            type_arguments,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse a possibly dotted name such as `A.B.C`
+    pub(super) fn parse_entity_name(&mut self) -> ParseResult<EntityName> {
+        let start = self.start();
+        let first = self.parse_identifier()?;
+        self.parse_entity_name_rest(start, first)
+    }
+
+    /// Parse the `.B.C` qualifying an entity name that starts with `first`
+    fn parse_entity_name_rest(&mut self, start: Position, first: Identifier) -> ParseResult<EntityName> {
+        let mut name = EntityName::Identifier(first);
+        while self.eat(TokenKind::Dot) {
+            let right = self.parse_identifier_name()?;
+            name = EntityName::Qualified(Box::new(QualifiedName {
+                left: name,
+                right,
+                span: self.span_from(start),
+            }));
+        }
+        Ok(name)
+    }
+
+    /// Parse `<A, B>` if it follows on the same line
+    fn parse_type_arguments_on_same_line(&mut self) -> ParseResult<Vec<TypeNode>> {
+        if self.at(TokenKind::Less) && !self.current_token.preceded_by_newline {
+            self.parse_type_arguments()
+        } else {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Parse a `<A, B>` type argument list
+    pub(super) fn parse_type_arguments(&mut self) -> ParseResult<Vec<TypeNode>> {
+        self.expect(TokenKind::Less)?;
+        let mut arguments = Vec::new();
+        while !self.at(TokenKind::Greater) {
+            arguments.push(self.parse_type()?);
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::Greater)?;
+        Ok(arguments)
+    }
+
+    /// Parse `<T extends C = D, U>` if present
+    pub(super) fn parse_type_parameters(&mut self) -> ParseResult<Vec<TypeParameter>> {
+        if !self.eat(TokenKind::Less) {
+            return Ok(Vec::new());
+        }
+        let mut parameters = Vec::new();
+        while !self.at(TokenKind::Greater) {
+            parameters.push(self.parse_type_parameter()?);
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::Greater)?;
+        Ok(parameters)
+    }
+
+    fn parse_type_parameter(&mut self) -> ParseResult<TypeParameter> {
+        let start = self.start();
+        let (mut is_in, mut is_out, mut is_const) = (false, false, false);
+        while self.next_is_type_parameter_name() {
+            match self.kind() {
+                TokenKind::In => is_in = true,
+                TokenKind::Const => is_const = true,
+                _ if self.at_contextual(ContextualKeyword::Out) => is_out = true,
+                _ => break,
+            }
+            self.advance();
+        }
+        let name = self.parse_identifier()?;
+        let constraint = if self.eat(TokenKind::Extends) {
+            Some(Box::new(self.parse_type()?))
+        } else {
+            None
+        };
+        let default = if self.eat(TokenKind::Equal) {
+            Some(Box::new(self.parse_type()?))
+        } else {
+            None
+        };
+        Ok(TypeParameter {
+            name,
+            is_in,
+            is_out,
+            is_const,
+            constraint,
+            default,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Check whether the token after a possible `in`, `out` or `const` modifier
+    /// is a name, so that the current token is a modifier rather than the name
+    fn next_is_type_parameter_name(&mut self) -> bool {
+        self.peek_token().is_some_and(|token| {
+            matches!(token.kind, TokenKind::Identifier(_) | TokenKind::In | TokenKind::Const)
+        })
+    }
+
+    /// Parse `typeof a.b` with optional type arguments
+    fn parse_type_query(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        self.expect(TokenKind::Typeof)?;
+        let name_start = self.start();
+        let first = if self.at(TokenKind::This) {
+            let token = self.advance();
+            self.identifier_from(&token)
+        } else {
+            self.parse_identifier()?
+        };
+        let expr_name = self.parse_entity_name_rest(name_start, first)?;
+        let type_arguments = self.parse_type_arguments_on_same_line()?;
+        Ok(TypeNode::Query(TypeQuery {
+            expr_name,
+            type_arguments,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse `import("module").Name<T>`, after any `typeof`
+    fn parse_import_type(&mut self, start: Position, is_type_of: bool) -> ParseResult<TypeNode> {
+        self.expect(TokenKind::Import)?;
+        self.expect(TokenKind::LeftParen)?;
+        let argument = match self.kind() {
+            TokenKind::String(value) => {
+                self.advance();
+                resolve(value).unwrap_or_default()
+            }
+            _ => return Err(self.error(1141, "String literal expected.")),
+        };
+        if self.eat(TokenKind::Comma) && self.at(TokenKind::LeftBrace) {
+            // Import attributes: `, { with: { "resolution-mode": "import" } }`
+            self.skip_balanced(TokenKind::LeftBrace, TokenKind::RightBrace)?;
+            self.eat(TokenKind::Comma);
+        }
+        self.expect(TokenKind::RightParen)?;
+
+        let qualifier = if self.eat(TokenKind::Dot) {
+            let qualifier_start = self.start();
+            let first = self.parse_identifier_name()?;
+            Some(self.parse_entity_name_rest(qualifier_start, first)?)
+        } else {
+            None
+        };
+        let type_arguments = self.parse_type_arguments_on_same_line()?;
+        Ok(TypeNode::Import(ImportType {
+            argument,
+            qualifier,
+            type_arguments,
+            is_type_of,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Parse `asserts x`, `asserts this` or `asserts x is T`
+    fn parse_asserts_type_predicate(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        self.advance();
+        let parameter_name = if self.at(TokenKind::This) {
+            let token = self.advance();
+            self.identifier_from(&token)
+        } else {
+            self.parse_identifier()?
+        };
+        let type_node = if self.at_contextual(ContextualKeyword::Is) && !self.current_token.preceded_by_newline {
+            self.advance();
+            Some(Box::new(self.parse_type()?))
+        } else {
+            None
+        };
+        Ok(TypeNode::Predicate(TypePredicate {
+            asserts: true,
+            parameter_name,
+            type_node,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Check whether a `{` starts a mapped type: `{ [K in`, optionally after a
+    /// `readonly`, `+readonly` or `-readonly` modifier
+    fn is_start_of_mapped_type(&mut self) -> bool {
+        self.advance();
+        if matches!(self.kind(), TokenKind::Plus | TokenKind::Minus) {
+            self.advance();
+            return self.at_contextual(ContextualKeyword::Readonly);
+        }
+        self.eat_contextual(ContextualKeyword::Readonly);
+        if !self.eat(TokenKind::LeftBracket) || !self.is_identifier() {
+            return false;
+        }
+        self.advance();
+        self.at(TokenKind::In)
+    }
+
+    fn parse_mapped_type(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        self.expect(TokenKind::LeftBrace)?;
+        let readonly_modifier = match self.kind() {
+            TokenKind::Plus | TokenKind::Minus => {
+                let modifier = self.parse_mapped_type_modifier_sign();
+                if !self.eat_contextual(ContextualKeyword::Readonly) {
+                    return Err(self.expected("readonly"));
+                }
+                Some(modifier)
+            }
+            _ if self.eat_contextual(ContextualKeyword::Readonly) => Some(MappedTypeModifier::Add),
+            _ => None,
+        };
+
+        self.expect(TokenKind::LeftBracket)?;
+        let parameter_start = self.start();
+        let name = self.parse_identifier()?;
+        self.expect(TokenKind::In)?;
+        let constraint = self.parse_type()?;
+        let type_parameter = TypeParameter {
+            name,
+            is_in: false,
+            is_out: false,
+            is_const: false,
+            constraint: Some(Box::new(constraint)),
+            default: None,
+            span: self.span_from(parameter_start),
+        };
+        let name_type = if self.eat_contextual(ContextualKeyword::As) {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        self.expect(TokenKind::RightBracket)?;
+
+        let optional_modifier = match self.kind() {
+            TokenKind::Plus | TokenKind::Minus => {
+                let modifier = self.parse_mapped_type_modifier_sign();
+                self.expect(TokenKind::Question)?;
+                Some(modifier)
+            }
+            TokenKind::Question => {
+                self.advance();
+                Some(MappedTypeModifier::Add)
+            }
+            _ => None,
+        };
+        let type_node = self.parse_optional_type_annotation()?;
+        if !self.eat(TokenKind::Semicolon) {
+            self.eat(TokenKind::Comma);
+        }
+        self.expect(TokenKind::RightBrace)?;
+
+        Ok(TypeNode::Mapped(Box::new(MappedType {
+            readonly_modifier,
+            type_parameter,
+            name_type,
+            optional_modifier,
+            type_node,
+            span: self.span_from(start),
+        })))
+    }
+
+    /// Consume a `+` or `-` before a mapped type modifier
+    fn parse_mapped_type_modifier_sign(&mut self) -> MappedTypeModifier {
+        if self.advance().kind == TokenKind::Minus {
+            MappedTypeModifier::Remove
+        } else {
+            MappedTypeModifier::Add
+        }
+    }
+
+    /// Parse `[A, B?, ...C]` or `[a: A, b?: B, ...rest: C[]]`
+    fn parse_tuple_type(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        self.expect(TokenKind::LeftBracket)?;
+        let mut elements = Vec::new();
+        while !self.at(TokenKind::RightBracket) {
+            elements.push(self.parse_tuple_element()?);
+            if !self.eat(TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(TokenKind::RightBracket)?;
+        Ok(TypeNode::Tuple(TupleType {
+            elements,
+            span: self.span_from(start),
+        }))
+    }
+
+    fn parse_tuple_element(&mut self) -> ParseResult<TupleElement> {
+        let start = self.start();
+        if self.look_ahead(Self::is_tuple_element_name) {
+            let rest = self.eat(TokenKind::DotDotDot);
+            let name = self.parse_identifier_name()?;
+            let optional = self.eat(TokenKind::Question);
+            self.expect(TokenKind::Colon)?;
+            let type_node = self.parse_type()?;
+            return Ok(TupleElement {
+                name: Some(name),
+                type_node,
+                optional,
+                rest,
+                span: self.span_from(start),
+            });
+        }
+
+        let rest = self.eat(TokenKind::DotDotDot);
+        let type_node = self.parse_type()?;
+        let optional = !rest && self.eat(TokenKind::Question);
+        Ok(TupleElement {
+            name: None,
+            type_node,
+            optional,
+            rest,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Check for a tuple element label: a name followed by `:` or `?:`
+    fn is_tuple_element_name(&mut self) -> bool {
+        self.eat(TokenKind::DotDotDot);
+        if !self.is_identifier_name() {
+            return false;
+        }
+        self.advance();
+        self.eat(TokenKind::Question);
+        self.at(TokenKind::Colon)
+    }
+
+    /// Parse `` `prefix${T}suffix` `` in type position
+    fn parse_template_literal_type(&mut self) -> ParseResult<TypeNode> {
+        let start = self.start();
+        let mut quasis = Vec::new();
+        let mut types = Vec::new();
+        loop {
+            let first = quasis.is_empty();
+            let (cooked, raw, is_tail) = match self.kind() {
+                TokenKind::TemplateHead { cooked, raw } if first => (cooked, raw, false),
+                TokenKind::TemplateMiddle { cooked, raw } if !first => (cooked, raw, false),
+                TokenKind::TemplateTail { cooked, raw } if !first => (cooked, raw, true),
+                _ => return Err(self.expected("}")),
+            };
+            if cooked.is_none() {
+                self.report_template_escapes();
+            }
+            let token = self.advance();
+            quasis.push(TemplateElement {
+                cooked: cooked.and_then(resolve),
+                raw: resolve(raw).unwrap_or_default(),
+                span: token.span,
+            });
+            if is_tail {
+                break;
+            }
+            types.push(self.parse_type()?);
+        }
+        Ok(TypeNode::TemplateLiteral(TemplateLiteralType {
+            quasis,
+            types,
+            span: self.span_from(start),
+        }))
+    }
+
+    /// Skip from an opening bracket to its matching closing bracket
+    pub(super) fn skip_balanced(&mut self, open: TokenKind, close: TokenKind) -> ParseResult<()> {
+        self.expect(open)?;
+        let mut depth = 1;
+        while depth > 0 {
+            if self.at(TokenKind::Eof) {
+                return Err(self.expected(close.text().unwrap_or("token")));
+            }
+            if self.at(open) {
+                depth += 1;
+            } else if self.at(close) {
+                depth -= 1;
+            }
+            self.advance();
+        }
+        Ok(())
+    }
+}
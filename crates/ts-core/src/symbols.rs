@@ -2,7 +2,7 @@
 
 use std::collections::HashMap;
 use crate::types::Type;
-use crate::utils::span::{Span, Position};
+use crate::utils::span::Span;
 
 /// Symbol kind
 #[derive(Debug, Clone, PartialEq)]
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 use crate::utils::span::Span;
 use crate::ast::AstNode;
-use crate::diagnostics::{Diagnostic, DiagnosticKind};
+use crate::diagnostics::Diagnostic;
 use serde::{Serialize, Deserialize};
 
 /// TypeScript type representation
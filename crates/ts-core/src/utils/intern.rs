@@ -1,25 +1,46 @@
 //! String interning utilities
+//!
+//! The interner is shared by every thread and never takes a lock: symbols are
+//! looked up in a concurrent hash map, and their strings live in an append-only
+//! vector indexed by the symbol. Interned strings are never freed.
 
-use string_interner::{StringInterner, DefaultSymbol};
-use std::sync::Mutex;
+use boxcar::Vec as AppendVec;
 use once_cell::sync::Lazy;
+use papaya::HashMap;
 
 /// Global string interner
-static INTERNER: Lazy<Mutex<StringInterner>> = Lazy::new(|| {
-    Mutex::new(StringInterner::default())
-});
+static INTERNER: Lazy<Interner> = Lazy::new(Interner::default);
+
+#[derive(Default)]
+struct Interner {
+    symbols: HashMap<&'static str, Symbol>,
+    strings: AppendVec<&'static str>,
+}
 
 /// Interned string symbol
-pub type Symbol = DefaultSymbol;
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
 
 /// Intern a string and return its symbol
 pub fn intern(s: &str) -> Symbol {
-    INTERNER.lock().unwrap().get_or_intern(s)
+    let symbols = INTERNER.symbols.pin();
+    if let Some(&symbol) = symbols.get(s) {
+        return symbol;
+    }
+    // Two threads interning the same new string can both get here. Only one
+    // insert wins; the loser's slot is left unused and its symbol never escapes.
+    let s: &'static str = Box::leak(s.into());
+    let index = INTERNER.strings.push(s);
+    let symbol = Symbol(u32::try_from(index).expect("too many interned strings"));
+    match symbols.try_insert(s, symbol) {
+        Ok(&symbol) => symbol,
+        Err(occupied) => *occupied.current,
+    }
 }
 
 /// Resolve a symbol back to its string
 pub fn resolve(symbol: Symbol) -> Option<String> {
-    INTERNER.lock().unwrap().resolve(symbol).map(|s| s.to_string())
+    INTERNER.strings.get(symbol.0 as usize).map(|s| s.to_string())
 }
 
 /// Get or intern a string
@@ -29,5 +50,66 @@ pub fn get_or_intern(s: &str) -> Symbol {
 
 /// Check if a symbol exists
 pub fn contains(symbol: Symbol) -> bool {
-    INTERNER.lock().unwrap().resolve(symbol).is_some()
-}
\ No newline at end of file
+    INTERNER.strings.get(symbol.0 as usize).is_some()
+}
+
+/// Serde support for fields holding a [`Symbol`], which serialize as the interned
+/// string and are re-interned when deserialized.
+///
+/// Use with `#[serde(with = "crate::utils::intern::serde_symbol")]`.
+pub mod serde_symbol {
+    use super::{intern, resolve, Symbol};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(symbol: &Symbol, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&resolve(*symbol).unwrap_or_default())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Symbol, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Ok(intern(&s))
+    }
+}
+
+/// Serde support for fields holding an `Option<Symbol>`, in the same form as
+/// [`serde_symbol`].
+pub mod serde_option_symbol {
+    use super::{intern, resolve, Symbol};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(symbol: &Option<Symbol>, serializer: S) -> Result<S::Ok, S::Error> {
+        match symbol {
+            Some(symbol) => serializer.serialize_some(&resolve(*symbol).unwrap_or_default()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Symbol>, D::Error> {
+        let s = Option::<String>::deserialize(deserializer)?;
+        Ok(s.map(|s| intern(&s)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threads_interning_the_same_strings_get_the_same_symbols() {
+        let words: Vec<String> = (0..500).map(|i| format!("interned_across_threads_{i}")).collect();
+        let per_thread: Vec<Vec<Symbol>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| words.iter().map(|w| intern(w)).collect::<Vec<_>>()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        for symbols in &per_thread[1..] {
+            assert_eq!(symbols, &per_thread[0]);
+        }
+        for (word, symbol) in words.iter().zip(&per_thread[0]) {
+            assert_eq!(resolve(*symbol).as_deref(), Some(word.as_str()));
+            assert_eq!(intern(word), *symbol);
+        }
+    }
+}
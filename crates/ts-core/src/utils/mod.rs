@@ -7,5 +7,3 @@ pub mod source;
 // Re-export commonly used types
 pub use span::{Span, Position};
 pub use source::SourceManager;
-// Import StringInterner directly from string-interner crate
-pub use string_interner::StringInterner;
\ No newline at end of file
@@ -53,12 +53,14 @@ impl SourceFile {
     /// Create a new source file
     pub fn new(name: String, content: String) -> Self {
         let mut line_starts = vec![0];
-        for (i, ch) in content.char_indices() {
-            if ch == '\n' {
+        let bytes = content.as_bytes();
+        for (i, &byte) in bytes.iter().enumerate() {
+            // `\r\n` is a single line break, recorded on its `\n`
+            if byte == b'\n' || (byte == b'\r' && bytes.get(i + 1) != Some(&b'\n')) {
                 line_starts.push(i + 1);
             }
         }
-        
+
         Self {
             name,
             content,
@@ -66,18 +68,14 @@ impl SourceFile {
         }
     }
 
-    /// Convert a byte offset to line/column position
+    /// Convert a byte offset to line/column position.
+    ///
+    /// The column counts Unicode scalar values, matching the positions produced by
+    /// the scanner. Returns `None` if the offset is out of bounds or not on a
+    /// character boundary.
     pub fn offset_to_position(&self, offset: usize) -> Option<Position> {
-        if offset > self.content.len() {
-            return None;
-        }
-
-        let line = self.line_starts
-            .binary_search(&offset)
-            .unwrap_or_else(|i| i.saturating_sub(1));
-        
-        let line_start = self.line_starts[line];
-        let column = offset - line_start;
+        let (line, line_start) = self.line_of_offset(offset)?;
+        let column = self.content[line_start..offset].chars().count();
 
         Some(Position {
             line: line + 1,
@@ -86,15 +84,148 @@ impl SourceFile {
         })
     }
 
-    /// Get the content of a span
+    /// Convert a byte offset to a 1-based column in UTF-16 code units, as used by
+    /// the Language Server Protocol and source maps
+    pub fn offset_to_utf16_column(&self, offset: usize) -> Option<usize> {
+        let (_, line_start) = self.line_of_offset(offset)?;
+        let column: usize = self.content[line_start..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+        Some(column + 1)
+    }
+
+    /// Convert a 1-based line and 1-based UTF-16 column back to a byte offset.
+    ///
+    /// A column inside a surrogate pair, or past the end of the line, is clamped to
+    /// the nearest character boundary before it.
+    pub fn utf16_position_to_offset(&self, line: usize, utf16_column: usize) -> Option<usize> {
+        let line_start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let line_end = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or(self.content.len());
+
+        let mut remaining = utf16_column.checked_sub(1)?;
+        let mut offset = line_start;
+        for ch in self.content[line_start..line_end].chars() {
+            if ch == '\n' || ch == '\r' || ch.len_utf16() > remaining {
+                break;
+            }
+            remaining -= ch.len_utf16();
+            offset += ch.len_utf8();
+        }
+        Some(offset)
+    }
+
+    /// Find the 0-based line containing a byte offset and the byte offset where it starts
+    fn line_of_offset(&self, offset: usize) -> Option<(usize, usize)> {
+        if !self.content.is_char_boundary(offset) {
+            return None;
+        }
+
+        let line = self.line_starts
+            .binary_search(&offset)
+            .unwrap_or_else(|i| i.saturating_sub(1));
+        Some((line, self.line_starts[line]))
+    }
+
+    /// Get the content of a span, or `None` if it does not fall on character boundaries
     pub fn get_span_content(&self, span: &Span) -> Option<&str> {
-        let start = span.start.offset;
-        let end = span.end.offset;
-        
-        if end <= self.content.len() {
-            Some(&self.content[start..end])
-        } else {
-            None
+        self.content.get(span.start.offset..span.end.offset)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{tokenize, TokenKind};
+
+    fn file(content: &str) -> SourceFile {
+        SourceFile::new("test.ts".to_string(), content.to_string())
+    }
+
+    /// The text of every token scanned from `source`, read back through its span
+    fn token_texts(source: &str) -> Vec<String> {
+        let file = file(source);
+        let (tokens, _) = tokenize(source);
+        tokens
+            .iter()
+            .filter(|token| token.kind != TokenKind::Eof)
+            .map(|token| file.get_span_content(&token.span).unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn spans_after_multi_byte_characters_slice_the_right_text() {
+        assert_eq!(token_texts("é = 1"), ["é", "=", "1"]);
+        assert_eq!(token_texts("'€' + x"), ["'€'", "+", "x"]);
+        assert_eq!(token_texts("'😀'; y"), ["'😀'", ";", "y"]);
+        assert_eq!(token_texts("café.€😀"), ["café", ".", "€", "😀"]);
+    }
+
+    #[test]
+    fn scanner_positions_are_byte_offsets_with_scalar_columns() {
+        let source = "'é€😀' + x";
+        let (tokens, _) = tokenize(source);
+        let plus = &tokens[1];
+        assert_eq!(plus.kind, TokenKind::Plus);
+        // 2 quotes + 2 + 3 + 4 bytes, then a space
+        assert_eq!(plus.span.start.offset, 12);
+        assert_eq!(plus.span.start.column, 7);
+        assert_eq!(file(source).offset_to_position(12), Some(plus.span.start));
+    }
+
+    #[test]
+    fn utf16_columns_count_surrogate_pairs_as_two() {
+        let file = file("é€😀x");
+        assert_eq!(file.offset_to_utf16_column(0), Some(1));
+        assert_eq!(file.offset_to_utf16_column(2), Some(2));
+        assert_eq!(file.offset_to_utf16_column(5), Some(3));
+        assert_eq!(file.offset_to_utf16_column(9), Some(5));
+        // Not on a character boundary
+        assert_eq!(file.offset_to_utf16_column(1), None);
+        assert_eq!(file.offset_to_utf16_column(6), None);
+    }
+
+    #[test]
+    fn utf16_columns_round_trip_to_offsets() {
+        let file = file("let é = '€😀';\n  😀 + x");
+        for (offset, _) in file.content.char_indices() {
+            let line = file.offset_to_position(offset).unwrap().line;
+            let column = file.offset_to_utf16_column(offset).unwrap();
+            assert_eq!(file.utf16_position_to_offset(line, column), Some(offset));
         }
+        // A column in the middle of a surrogate pair clamps to its start
+        assert_eq!(file.utf16_position_to_offset(2, 4), Some(22));
+        // A column past the end of the line clamps to the line break
+        assert_eq!(file.utf16_position_to_offset(1, 100), Some(19));
+    }
+
+    #[test]
+    fn crlf_is_a_single_line_break() {
+        let source = "a\r\n€b\rc";
+        let file = file(source);
+        assert_eq!(file.offset_to_position(3), Some(Position::new(2, 1, 3)));
+        assert_eq!(file.offset_to_position(6), Some(Position::new(2, 2, 6)));
+        assert_eq!(file.offset_to_position(8), Some(Position::new(3, 1, 8)));
+        assert_eq!(file.utf16_position_to_offset(1, 2), Some(1));
+        assert_eq!(file.utf16_position_to_offset(2, 2), Some(6));
+
+        let (tokens, _) = tokenize(source);
+        let c = &tokens[3];
+        assert!(c.preceded_by_newline);
+        assert_eq!(c.span.start, Position::new(3, 1, 8));
+        assert_eq!(file.get_span_content(&c.span), Some("c"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn span_content_is_none_off_character_boundaries() {
+        let file = file("é€");
+        let span = |start, end| Span::new(Position::new(1, 1, start), Position::new(1, 1, end));
+        assert_eq!(file.get_span_content(&span(0, 2)), Some("é"));
+        assert_eq!(file.get_span_content(&span(2, 5)), Some("€"));
+        assert_eq!(file.get_span_content(&span(1, 5)), None);
+        assert_eq!(file.get_span_content(&span(0, 9)), None);
+    }
+}
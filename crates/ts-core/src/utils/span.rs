@@ -15,7 +15,7 @@ pub struct Position {
 }
 
 /// A span of source code
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Span {
     /// Start position
     pub start: Position,
@@ -33,15 +33,6 @@ impl Default for Position {
     }
 }
 
-impl Default for Span {
-    fn default() -> Self {
-        Self {
-            start: Position::default(),
-            end: Position::default(),
-        }
-    }
-}
-
 impl Position {
     /// Create a new position
     pub fn new(line: usize, column: usize, offset: usize) -> Self {
@@ -61,7 +52,7 @@ impl Span {
         Self { start, end }
     }
 
-    /// Get the length of this span in characters
+    /// Get the length of this span in bytes
     pub fn len(&self) -> usize {
         self.end.offset.saturating_sub(self.start.offset)
     }